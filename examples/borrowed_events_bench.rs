@@ -0,0 +1,67 @@
+use slack_morphism::api::SlackApiConversationsHistoryResponse;
+use slack_morphism::borrowed::*;
+use slack_morphism::events::SlackPushEvent;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+// Counts allocations to compare the owned and borrowed models
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ITERATIONS: usize = 10_000;
+
+fn bench<F>(name: &str, mut parse: F)
+where
+    F: FnMut(),
+{
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        parse();
+    }
+    let elapsed = started.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before;
+    println!(
+        "{name:<32} {:>10.2?}/iter {:>8} allocations/iter",
+        elapsed / ITERATIONS as u32,
+        allocations / ITERATIONS
+    );
+}
+
+fn main() {
+    let push_event = include_str!("../src/models/events/fixtures/message_changed.json");
+    let history = include_str!("../src/api/fixtures/slack_api_conversations_history_response.json");
+
+    bench("push event (owned)", || {
+        std::hint::black_box(serde_json::from_str::<SlackPushEvent>(push_event).unwrap());
+    });
+    bench("push event (borrowed)", || {
+        std::hint::black_box(serde_json::from_str::<SlackPushEventRef>(push_event).unwrap());
+    });
+    bench("history page (owned)", || {
+        std::hint::black_box(
+            serde_json::from_str::<SlackApiConversationsHistoryResponse>(history).unwrap(),
+        );
+    });
+    bench("history page (borrowed)", || {
+        std::hint::black_box(
+            serde_json::from_str::<SlackConversationsHistoryPageRef>(history).unwrap(),
+        );
+    });
+}
@@ -37,7 +37,7 @@ async fn test_command_events_function(
 
     Ok(SlackCommandEventResponse::new(
         SlackMessageContent::new()
-            .with_text(format!("Working on it: {:?}", user_info_resp.user.team_id).into())
+            .with_text(format!("Working on it: {:?}", user_info_resp.user.team_id))
             .with_blocks(slack_blocks![
                 some_into(SlackSectionBlock::new().with_text(md!(
                     "Working section for {}. Team ID: {:?}",
@@ -118,7 +118,7 @@ impl SlackBlocksTemplate for SlackHomeTabBlocksTemplateExample {
             .latest_news
             .clone()
             .into_iter()
-            .map(|news_item| {
+            .flat_map(|news_item| {
                 vec![
                     SlackSectionBlock::new()
                         .with_text(md!(" • *{}*\n>{}", news_item.title, news_item.body))
@@ -134,7 +134,6 @@ impl SlackBlocksTemplate for SlackHomeTabBlocksTemplateExample {
                     .into(),
                 ]
             })
-            .flatten()
             .collect();
 
         [
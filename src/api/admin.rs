@@ -0,0 +1,452 @@
+//!
+//! Support for Slack Admin API methods
+//!
+
+use chrono::NaiveDate;
+use futures::future::{BoxFuture, FutureExt};
+use futures::io::{AsyncBufRead, AsyncBufReadExt};
+use futures::stream::{BoxStream, StreamExt};
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none};
+
+use crate::api::SlackSearchSortDirection;
+use crate::errors::*;
+use crate::models::*;
+use crate::ratectl::*;
+use crate::*;
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// https://api.slack.com/methods/admin.analytics.getFile
+    ///
+    /// Returns gzip compressed NDJSON content as is. After decompression,
+    /// use `SlackAnalyticsRecords` to parse it into typed records.
+    pub async fn admin_analytics_get_file(
+        &self,
+        req: &SlackApiAdminAnalyticsGetFileRequest,
+    ) -> ClientResult<bytes::Bytes> {
+        let full_uri = SlackClientHttpApiUri::create_url_with_params(
+            self.http_session_api
+                .client
+                .create_method_uri_path("admin.analytics.getFile")?,
+            &vec![
+                ("type", Some(req.analytics_type.to_string())),
+                (
+                    "date",
+                    req.date.map(|date| date.format("%Y-%m-%d").to_string()),
+                ),
+                ("metadata_only", req.metadata_only.map(|v| v.to_string())),
+            ],
+        )?;
+
+        let content = self.http_session_api.http_get_uri_binary(full_uri).await?;
+
+        // Slack responds with JSON for errors instead of a file
+        match serde_json::from_slice::<SlackEnvelopeMessage>(&content) {
+            Ok(slack_message) if slack_message.error.is_some() => {
+                let warnings = slack_message.all_warnings();
+                Err(SlackClientError::ApiError(
+                    SlackClientApiError::new(slack_message.error.unwrap_or_default())
+                        .opt_errors(slack_message.errors)
+                        .opt_warnings(warnings),
+                ))
+            }
+            _ => Ok(content),
+        }
+    }
+
+    ///
+    /// https://api.slack.com/methods/admin.conversations.search
+    ///
+    pub async fn admin_conversations_search(
+        &self,
+        req: &SlackApiAdminConversationsSearchRequest,
+    ) -> ClientResult<SlackApiAdminConversationsSearchResponse> {
+        self.http_session_api
+            .http_get(
+                "admin.conversations.search",
+                &vec![
+                    ("query", req.query.as_ref()),
+                    (
+                        "search_channel_types",
+                        req.search_channel_types
+                            .as_ref()
+                            .map(|xs| xs.join(","))
+                            .as_ref(),
+                    ),
+                    ("sort", req.sort.as_ref()),
+                    (
+                        "sort_dir",
+                        req.sort_dir.as_ref().map(|v| v.to_string()).as_ref(),
+                    ),
+                    (
+                        "team_ids",
+                        req.team_ids
+                            .as_ref()
+                            .map(|xs| {
+                                xs.iter()
+                                    .map(|x| x.to_string())
+                                    .collect::<Vec<String>>()
+                                    .join(",")
+                            })
+                            .as_ref(),
+                    ),
+                    (
+                        "connected_team_ids",
+                        req.connected_team_ids
+                            .as_ref()
+                            .map(|xs| {
+                                xs.iter()
+                                    .map(|x| x.to_string())
+                                    .collect::<Vec<String>>()
+                                    .join(",")
+                            })
+                            .as_ref(),
+                    ),
+                    (
+                        "total_count_only",
+                        req.total_count_only.map(|v| v.to_string()).as_ref(),
+                    ),
+                    ("limit", req.limit.map(|v| v.to_string()).as_ref()),
+                    ("cursor", req.cursor.as_ref().map(|x| x.value())),
+                ],
+                Some(&SLACK_TIER2_METHOD_CONFIG),
+            )
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/admin.teams.list
+    ///
+    pub async fn admin_teams_list(
+        &self,
+        req: &SlackApiAdminTeamsListRequest,
+    ) -> ClientResult<SlackApiAdminTeamsListResponse> {
+        self.http_session_api
+            .http_get(
+                "admin.teams.list",
+                &vec![
+                    ("limit", req.limit.map(|v| v.to_string()).as_ref()),
+                    ("cursor", req.cursor.as_ref().map(|x| x.value())),
+                ],
+                Some(&SLACK_TIER2_METHOD_CONFIG),
+            )
+            .await
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum SlackAnalyticsType {
+    #[serde(rename = "member")]
+    Member,
+    #[serde(rename = "public_channel")]
+    PublicChannel,
+}
+
+impl std::fmt::Display for SlackAnalyticsType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlackAnalyticsType::Member => write!(f, "member"),
+            SlackAnalyticsType::PublicChannel => write!(f, "public_channel"),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiAdminAnalyticsGetFileRequest {
+    #[serde(rename = "type")]
+    pub analytics_type: SlackAnalyticsType,
+    pub date: Option<NaiveDate>,
+    pub metadata_only: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAnalyticsMemberRecord {
+    pub date: NaiveDate,
+    pub enterprise_id: Option<SlackEnterpriseId>,
+    pub team_id: SlackTeamId,
+    pub user_id: SlackUserId,
+    pub email_address: Option<EmailAddress>,
+    pub enterprise_employee_number: Option<String>,
+    pub is_guest: Option<bool>,
+    pub is_billable_seat: Option<bool>,
+    pub is_active: Option<bool>,
+    pub is_active_ios: Option<bool>,
+    pub is_active_android: Option<bool>,
+    pub is_active_desktop: Option<bool>,
+    pub is_active_apps: Option<bool>,
+    pub is_active_workflows: Option<bool>,
+    pub is_active_slack_connect: Option<bool>,
+    pub reactions_added_count: Option<u64>,
+    pub messages_posted_count: Option<u64>,
+    pub channel_messages_posted_count: Option<u64>,
+    pub files_added_count: Option<u64>,
+    pub total_calls_count: Option<u64>,
+    pub slack_calls_count: Option<u64>,
+    pub slack_huddles_count: Option<u64>,
+    pub search_count: Option<u64>,
+    pub date_claimed: Option<SlackDateTime>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAnalyticsPublicChannelRecord {
+    pub date: NaiveDate,
+    pub enterprise_id: Option<SlackEnterpriseId>,
+    pub team_id: SlackTeamId,
+    pub channel_id: SlackChannelId,
+    pub originating_team: Option<SlackAnalyticsTeamInfo>,
+    pub date_created: Option<SlackDateTime>,
+    pub date_last_active: Option<SlackDateTime>,
+    pub total_members_count: Option<u64>,
+    pub full_members_count: Option<u64>,
+    pub guest_member_count: Option<u64>,
+    pub messages_posted_count: Option<u64>,
+    pub messages_posted_by_members_count: Option<u64>,
+    pub members_who_viewed_count: Option<u64>,
+    pub members_who_posted_count: Option<u64>,
+    pub reactions_added_count: Option<u64>,
+    pub visibility: Option<String>,
+    pub channel_type: Option<String>,
+    pub is_shared_externally: Option<bool>,
+    pub shared_with: Option<Vec<SlackAnalyticsTeamInfo>>,
+    pub externally_shared_with_organizations: Option<Vec<SlackAnalyticsOrganizationInfo>>,
+}
+
+/// Returned by `admin.analytics.getFile` with `metadata_only`
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAnalyticsPublicChannelMetadataRecord {
+    pub channel_id: SlackChannelId,
+    pub name: Option<String>,
+    pub topic: Option<String>,
+    pub description: Option<String>,
+    pub date: Option<NaiveDate>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAnalyticsTeamInfo {
+    pub team_id: Option<SlackTeamId>,
+    pub name: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAnalyticsOrganizationInfo {
+    pub name: Option<String>,
+    pub domain: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiAdminConversationsSearchRequest {
+    pub query: Option<String>,
+    /// `private`, `private_exclude`, `archived`, `exclude_archived`, `private_exclude_archived`, `multi_workspace`, `org_wide`, `external_shared_exclude`, `external_shared`, `external_shared_private`, `external_shared_archived`, `exclude_org_shared`
+    pub search_channel_types: Option<Vec<String>>,
+    /// `relevant` (default), `name`, `member_count` or `created`
+    pub sort: Option<String>,
+    pub sort_dir: Option<SlackSearchSortDirection>,
+    pub team_ids: Option<Vec<SlackTeamId>>,
+    pub connected_team_ids: Option<Vec<SlackTeamId>>,
+    pub total_count_only: Option<bool>,
+    pub limit: Option<u16>,
+    pub cursor: Option<SlackCursorId>,
+}
+
+/// Unlike most of the methods, the cursor is returned as a top level `next_cursor` field
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiAdminConversationsSearchResponse {
+    #[serde(default)]
+    pub conversations: Vec<SlackAdminConversation>,
+    #[serde(default)]
+    #[serde_as(as = "serde_with::NoneAsEmptyString")]
+    pub next_cursor: Option<SlackCursorId>,
+    pub total_count: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAdminConversation {
+    pub id: SlackChannelId,
+    pub name: String,
+    pub purpose: Option<String>,
+    pub member_count: Option<u64>,
+    pub creator_id: Option<SlackUserId>,
+    pub is_private: Option<bool>,
+    pub is_archived: Option<bool>,
+    pub is_general: Option<bool>,
+    pub is_ext_shared: Option<bool>,
+    pub is_org_shared: Option<bool>,
+    pub is_org_default: Option<bool>,
+    pub is_org_mandatory: Option<bool>,
+    pub is_frozen: Option<bool>,
+    pub connected_team_ids: Option<Vec<SlackTeamId>>,
+    pub internal_team_ids: Option<Vec<SlackTeamId>>,
+    pub internal_team_ids_count: Option<u64>,
+    pub last_activity_ts: Option<SlackTs>,
+    pub context_team_id: Option<SlackTeamId>,
+}
+
+impl<SCHC> SlackApiScrollableRequest<SCHC> for SlackApiAdminConversationsSearchRequest
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + Clone + 'static,
+{
+    type ResponseType = SlackApiAdminConversationsSearchResponse;
+    type CursorType = SlackCursorId;
+    type ResponseItemType = SlackAdminConversation;
+
+    fn with_new_cursor(&self, new_cursor: Option<&Self::CursorType>) -> Self {
+        self.clone().opt_cursor(new_cursor.cloned())
+    }
+
+    fn scroll<'a, 's>(
+        &'a self,
+        session: &'a SlackClientSession<'s, SCHC>,
+    ) -> BoxFuture<'a, ClientResult<Self::ResponseType>> {
+        async move { session.admin_conversations_search(self).await }.boxed()
+    }
+}
+
+impl SlackApiScrollableResponse for SlackApiAdminConversationsSearchResponse {
+    type CursorType = SlackCursorId;
+    type ResponseItemType = SlackAdminConversation;
+
+    fn next_cursor(&self) -> Option<&Self::CursorType> {
+        self.next_cursor.as_ref()
+    }
+
+    fn scrollable_items<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self::ResponseItemType> + 'a> {
+        Box::new(self.conversations.iter())
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiAdminTeamsListRequest {
+    pub limit: Option<u16>,
+    pub cursor: Option<SlackCursorId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiAdminTeamsListResponse {
+    pub teams: Vec<SlackAdminTeam>,
+    pub response_metadata: Option<SlackResponseMetadata>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAdminTeam {
+    pub id: SlackTeamId,
+    pub name: String,
+    pub discoverability: Option<String>,
+    pub team_url: Option<String>,
+}
+
+impl<SCHC> SlackApiScrollableRequest<SCHC> for SlackApiAdminTeamsListRequest
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + Clone + 'static,
+{
+    type ResponseType = SlackApiAdminTeamsListResponse;
+    type CursorType = SlackCursorId;
+    type ResponseItemType = SlackAdminTeam;
+
+    fn with_new_cursor(&self, new_cursor: Option<&Self::CursorType>) -> Self {
+        self.clone().opt_cursor(new_cursor.cloned())
+    }
+
+    fn scroll<'a, 's>(
+        &'a self,
+        session: &'a SlackClientSession<'s, SCHC>,
+    ) -> BoxFuture<'a, ClientResult<Self::ResponseType>> {
+        async move { session.admin_teams_list(self).await }.boxed()
+    }
+}
+
+impl SlackApiScrollableResponse for SlackApiAdminTeamsListResponse {
+    type CursorType = SlackCursorId;
+    type ResponseItemType = SlackAdminTeam;
+
+    fn next_cursor(&self) -> Option<&Self::CursorType> {
+        self.response_metadata
+            .as_ref()
+            .and_then(|rm| rm.next_cursor.as_ref())
+    }
+
+    fn scrollable_items<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self::ResponseItemType> + 'a> {
+        Box::new(self.teams.iter())
+    }
+}
+
+/// Parses decompressed analytics NDJSON content into typed records
+pub struct SlackAnalyticsRecords;
+
+impl SlackAnalyticsRecords {
+    pub fn stream<'a, T, R>(reader: R) -> BoxStream<'a, ClientResult<T>>
+    where
+        T: for<'de> serde::de::Deserialize<'de> + Send + 'a,
+        R: AsyncBufRead + Send + Unpin + 'a,
+    {
+        reader
+            .lines()
+            .filter_map(|line| {
+                std::future::ready(match line {
+                    Ok(line) if line.trim().is_empty() => None,
+                    Ok(line) => Some(
+                        serde_json::from_str(&line)
+                            .map_err(|err| map_serde_error(err, Some(line.as_str()))),
+                    ),
+                    Err(err) => Some(Err(SlackClientError::SystemError(
+                        SlackClientSystemError::new()
+                            .with_message("Unable to read analytics content".into())
+                            .with_cause(Box::new(err)),
+                    ))),
+                })
+            })
+            .boxed()
+    }
+
+    pub fn from_bytes<'a, T>(content: &'a [u8]) -> BoxStream<'a, ClientResult<T>>
+    where
+        T: for<'de> serde::de::Deserialize<'de> + Send + 'a,
+    {
+        Self::stream(futures::io::Cursor::new(content))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::TryStreamExt;
+
+    #[test]
+    fn test_slack_api_admin_conversations_search_response() {
+        let payload = include_str!("./fixtures/slack_api_admin_conversations_search_response.json");
+        let model: SlackApiAdminConversationsSearchResponse =
+            serde_json::from_str(payload).unwrap();
+        assert_eq!(model.conversations.len(), 1);
+        assert_eq!(model.next_cursor, Some("dXNlcjpVMEc5V0ZYTlo=".into()));
+    }
+
+    #[test]
+    fn test_slack_analytics_member_records() {
+        let payload = include_str!("./fixtures/slack_api_admin_analytics_member.ndjson");
+        let records: Vec<SlackAnalyticsMemberRecord> = futures::executor::block_on(
+            SlackAnalyticsRecords::from_bytes(payload.as_bytes()).try_collect(),
+        )
+        .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].user_id, "W1F83A9F9".into());
+        assert_eq!(records[1].messages_posted_count, Some(12));
+    }
+}
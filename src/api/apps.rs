@@ -201,4 +201,24 @@ mod test {
         assert_eq!(model.app_id, SlackAppId::from("test-app-id"));
         assert_eq!(model.manifest.display_information.name, "My App");
     }
+
+    #[test]
+    fn test_slack_app_manifest_full() {
+        let payload = include_str!("./fixtures/slack_app_manifest_full.json");
+        let model: SlackAppManifest = serde_json::from_str(payload).unwrap();
+        let deploy_function = &model.functions.as_ref().unwrap()["deploy_service"];
+        assert_eq!(deploy_function.input_parameters.properties.len(), 3);
+        assert_eq!(
+            model.workflows.as_ref().unwrap()["deploy_workflow"].steps[0].function_id,
+            "#/functions/deploy_service"
+        );
+
+        let reparsed: SlackAppManifest =
+            serde_json::from_value(serde_json::to_value(&model).unwrap()).unwrap();
+        assert_eq!(reparsed, model);
+        assert_eq!(
+            serde_json::to_value(&model).unwrap(),
+            serde_json::from_str::<serde_json::Value>(payload).unwrap()
+        );
+    }
 }
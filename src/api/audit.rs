@@ -0,0 +1,189 @@
+//!
+//! Support for Slack Audit Logs API methods
+//!
+
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+
+use crate::errors::*;
+use crate::models::*;
+use crate::ratectl::*;
+use crate::*;
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// https://api.slack.com/admins/audit-logs-call
+    ///
+    pub async fn audit_logs(
+        &self,
+        req: &SlackApiAuditLogsRequest,
+    ) -> ClientResult<SlackApiAuditLogsResponse> {
+        let full_uri = SlackClientHttpApiUri::create_url_with_params(
+            self.http_session_api
+                .client
+                .endpoints()
+                .audit_logs_url
+                .parse()?,
+            &vec![
+                (
+                    "latest",
+                    req.latest.as_ref().map(|v| v.0.timestamp().to_string()),
+                ),
+                (
+                    "oldest",
+                    req.oldest.as_ref().map(|v| v.0.timestamp().to_string()),
+                ),
+                ("limit", req.limit.map(|v| v.to_string())),
+                ("action", req.action.clone()),
+                ("actor", req.actor.as_ref().map(|v| v.value().clone())),
+                ("entity", req.entity.clone()),
+                ("cursor", req.cursor.as_ref().map(|v| v.value().clone())),
+            ],
+        )?;
+
+        // Audit Logs API responses have no `ok` field, so only error responses have the Web API envelope
+        let content = self.http_session_api.http_get_uri_binary(full_uri).await?;
+        match serde_json::from_slice::<SlackEnvelopeMessage>(&content) {
+            Ok(slack_message) if slack_message.error.is_some() => {
+                let warnings = slack_message.all_warnings();
+                Err(SlackClientError::ApiError(
+                    SlackClientApiError::new(slack_message.error.unwrap_or_default())
+                        .opt_errors(slack_message.errors)
+                        .opt_warnings(warnings),
+                ))
+            }
+            _ => decode_json_body(&String::from_utf8_lossy(&content)),
+        }
+    }
+
+    /// Audit log entries (newest first) as a stream following the cursor through all pages
+    pub fn audit_logs_stream<'s>(
+        &'s self,
+        req: &SlackApiAuditLogsRequest,
+    ) -> BoxStream<'s, ClientResult<SlackAuditLogEntry>>
+    where
+        SCHC: Sync,
+    {
+        let first_page_req = req.clone();
+        futures::stream::try_unfold(Some(first_page_req), move |maybe_req| async move {
+            match maybe_req {
+                Some(page_req) => {
+                    let resp = self.audit_logs(&page_req).await?;
+                    let next_page_req = resp
+                        .response_metadata
+                        .and_then(|rm| rm.next_cursor)
+                        .filter(|next_cursor| !next_cursor.value().is_empty())
+                        .map(|next_cursor| page_req.clone().with_cursor(next_cursor));
+                    ClientResult::Ok(Some((resp.entries, next_page_req)))
+                }
+                None => Ok(None),
+            }
+        })
+        .map_ok(|entries| futures::stream::iter(entries.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiAuditLogsRequest {
+    pub latest: Option<SlackDateTime>,
+    /// Inclusive (with seconds precision)
+    pub oldest: Option<SlackDateTime>,
+    pub limit: Option<u16>,
+    pub action: Option<String>,
+    pub actor: Option<SlackUserId>,
+    pub entity: Option<String>,
+    pub cursor: Option<SlackCursorId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiAuditLogsResponse {
+    pub entries: Vec<SlackAuditLogEntry>,
+    pub response_metadata: Option<SlackResponseMetadata>,
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackAuditLogEntryId(pub String);
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAuditLogEntry {
+    pub id: SlackAuditLogEntryId,
+    pub date_create: SlackDateTime,
+    pub action: String,
+    pub actor: SlackAuditLogActor,
+    pub entity: SlackAuditLogEntity,
+    pub context: Option<SlackAuditLogContext>,
+    pub details: Option<serde_json::Value>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAuditLogActor {
+    #[serde(rename = "type")]
+    pub actor_type: String,
+    pub user: Option<SlackAuditLogUser>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAuditLogUser {
+    pub id: SlackUserId,
+    pub name: Option<String>,
+    pub email: Option<EmailAddress>,
+    pub team: Option<SlackTeamId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAuditLogEntity {
+    /// `user`, `channel`, `file`, `app`, `workspace`, `enterprise` etc.
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    /// The entity details under the key named after the entity type
+    #[serde(flatten)]
+    pub fields: HashMap<String, serde_json::Value>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAuditLogContext {
+    pub location: Option<SlackAuditLogLocation>,
+    pub ua: Option<String>,
+    pub ip_address: Option<String>,
+    pub session_id: Option<serde_json::Value>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAuditLogLocation {
+    #[serde(rename = "type")]
+    pub location_type: String,
+    pub id: String,
+    pub name: Option<String>,
+    pub domain: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slack_api_audit_logs_response() {
+        let payload = include_str!("./fixtures/slack_api_audit_logs_response.json");
+        let model: SlackApiAuditLogsResponse = serde_json::from_str(payload).unwrap();
+        assert_eq!(model.entries.len(), 1);
+        assert_eq!(model.entries[0].entity.entity_type, "user");
+        assert!(model.entries[0].entity.fields.contains_key("user"));
+    }
+}
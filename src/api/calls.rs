@@ -0,0 +1,253 @@
+//!
+//! Support for Slack Calls API methods
+//!
+
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+use crate::models::*;
+use crate::ratectl::*;
+use crate::SlackClientSession;
+use crate::{ClientResult, SlackClientHttpConnector};
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// https://api.slack.com/methods/calls.add
+    ///
+    pub async fn calls_add(
+        &self,
+        req: &SlackApiCallsAddRequest,
+    ) -> ClientResult<SlackApiCallsAddResponse> {
+        self.http_session_api
+            .http_post("calls.add", req, Some(&SLACK_TIER2_METHOD_CONFIG))
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/calls.info
+    ///
+    pub async fn calls_info(
+        &self,
+        req: &SlackApiCallsInfoRequest,
+    ) -> ClientResult<SlackApiCallsInfoResponse> {
+        self.http_session_api
+            .http_post("calls.info", req, Some(&SLACK_TIER3_METHOD_CONFIG))
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/calls.update
+    ///
+    pub async fn calls_update(
+        &self,
+        req: &SlackApiCallsUpdateRequest,
+    ) -> ClientResult<SlackApiCallsUpdateResponse> {
+        self.http_session_api
+            .http_post("calls.update", req, Some(&SLACK_TIER2_METHOD_CONFIG))
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/calls.end
+    ///
+    pub async fn calls_end(
+        &self,
+        req: &SlackApiCallsEndRequest,
+    ) -> ClientResult<SlackApiCallsEndResponse> {
+        self.http_session_api
+            .http_post("calls.end", req, Some(&SLACK_TIER2_METHOD_CONFIG))
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/calls.participants.add
+    ///
+    pub async fn calls_participants_add(
+        &self,
+        req: &SlackApiCallsParticipantsAddRequest,
+    ) -> ClientResult<SlackApiCallsParticipantsAddResponse> {
+        self.http_session_api
+            .http_post(
+                "calls.participants.add",
+                req,
+                Some(&SLACK_TIER2_METHOD_CONFIG),
+            )
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/calls.participants.remove
+    ///
+    pub async fn calls_participants_remove(
+        &self,
+        req: &SlackApiCallsParticipantsRemoveRequest,
+    ) -> ClientResult<SlackApiCallsParticipantsRemoveResponse> {
+        self.http_session_api
+            .http_post(
+                "calls.participants.remove",
+                req,
+                Some(&SLACK_TIER2_METHOD_CONFIG),
+            )
+            .await
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCall {
+    pub id: SlackCallId,
+    pub date_start: SlackDateTime,
+    pub external_unique_id: String,
+    pub join_url: Url,
+    pub desktop_app_join_url: Option<Url>,
+    pub external_display_id: Option<String>,
+    pub title: Option<String>,
+    pub created_by: Option<SlackUserId>,
+    pub users: Option<Vec<SlackCallUser>>,
+    pub date_end: Option<SlackDateTime>,
+    pub duration: Option<u64>,
+    pub channels: Option<Vec<SlackChannelId>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsAddRequest {
+    pub external_unique_id: String,
+    pub join_url: Url,
+    pub created_by: Option<SlackUserId>,
+    pub date_start: Option<SlackDateTime>,
+    pub desktop_app_join_url: Option<Url>,
+    pub external_display_id: Option<String>,
+    pub title: Option<String>,
+    pub users: Option<Vec<SlackCallUser>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsAddResponse {
+    pub call: SlackCall,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsInfoRequest {
+    pub id: SlackCallId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsInfoResponse {
+    pub call: SlackCall,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsUpdateRequest {
+    pub id: SlackCallId,
+    pub desktop_app_join_url: Option<Url>,
+    pub join_url: Option<Url>,
+    pub title: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsUpdateResponse {
+    pub call: SlackCall,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsEndRequest {
+    pub id: SlackCallId,
+    /// Call duration in seconds
+    pub duration: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsEndResponse {}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsParticipantsAddRequest {
+    pub id: SlackCallId,
+    pub users: Vec<SlackCallUser>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsParticipantsAddResponse {}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsParticipantsRemoveRequest {
+    pub id: SlackCallId,
+    pub users: Vec<SlackCallUser>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCallsParticipantsRemoveResponse {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slack_api_calls_add_request() {
+        let req = SlackApiCallsAddRequest::new(
+            "meeting-1".into(),
+            "https://example.com/join/1".parse().unwrap(),
+        )
+        .with_title("Standup".into())
+        .with_users(vec![
+            SlackCallUser::slack_user("U1".into()),
+            SlackCallUser::external_user("ext-1".into(), "Guest".into()),
+        ]);
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({
+                "external_unique_id": "meeting-1",
+                "join_url": "https://example.com/join/1",
+                "title": "Standup",
+                "users": [
+                    { "slack_id": "U1" },
+                    { "external_id": "ext-1", "display_name": "Guest" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_slack_api_calls_info_response() {
+        let model: SlackApiCallsInfoResponse = serde_json::from_value(serde_json::json!({
+            "ok": true,
+            "call": {
+                "id": "R0E69JAIF",
+                "date_start": 1562002086,
+                "external_unique_id": "025169F6-E37A-4E62-BB54-7F93A0FC4C1F",
+                "join_url": "https://callmebeepme.com/calls/1234567890",
+                "desktop_app_join_url": "callapp://join/1234567890",
+                "external_display_id": "705-292-868",
+                "title": "Kimpossible sync up",
+                "users": [
+                    { "slack_id": "U0MQG83FD" },
+                    {
+                        "external_id": "54321678",
+                        "display_name": "Kim Possible",
+                        "avatar_url": "https://callmebeepme.com/users/avatar1234.jpg"
+                    }
+                ]
+            }
+        }))
+        .unwrap();
+        assert_eq!(model.call.id, "R0E69JAIF".into());
+        assert_eq!(model.call.date_start.0.timestamp(), 1562002086);
+        assert_eq!(model.call.users.map(|users| users.len()), Some(2));
+    }
+}
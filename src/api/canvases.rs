@@ -0,0 +1,246 @@
+//!
+//! Support for Slack Canvases API methods
+//!
+
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::models::*;
+use crate::ratectl::*;
+use crate::SlackClientSession;
+use crate::{ClientResult, SlackClientHttpConnector};
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// https://api.slack.com/methods/canvases.create
+    ///
+    pub async fn canvases_create(
+        &self,
+        req: &SlackApiCanvasesCreateRequest,
+    ) -> ClientResult<SlackApiCanvasesCreateResponse> {
+        self.http_session_api
+            .http_post("canvases.create", req, Some(&SLACK_TIER2_METHOD_CONFIG))
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/canvases.edit
+    ///
+    pub async fn canvases_edit(
+        &self,
+        req: &SlackApiCanvasesEditRequest,
+    ) -> ClientResult<SlackApiCanvasesEditResponse> {
+        self.http_session_api
+            .http_post("canvases.edit", req, Some(&SLACK_TIER3_METHOD_CONFIG))
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/canvases.delete
+    ///
+    pub async fn canvases_delete(
+        &self,
+        req: &SlackApiCanvasesDeleteRequest,
+    ) -> ClientResult<SlackApiCanvasesDeleteResponse> {
+        self.http_session_api
+            .http_post("canvases.delete", req, Some(&SLACK_TIER3_METHOD_CONFIG))
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/canvases.sections.lookup
+    ///
+    pub async fn canvases_sections_lookup(
+        &self,
+        req: &SlackApiCanvasesSectionsLookupRequest,
+    ) -> ClientResult<SlackApiCanvasesSectionsLookupResponse> {
+        self.http_session_api
+            .http_post(
+                "canvases.sections.lookup",
+                req,
+                Some(&SLACK_TIER3_METHOD_CONFIG),
+            )
+            .await
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlackCanvasDocumentContent {
+    Markdown { markdown: String },
+}
+
+impl SlackCanvasDocumentContent {
+    pub fn markdown(markdown: String) -> Self {
+        SlackCanvasDocumentContent::Markdown { markdown }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlackCanvasEditOperation {
+    InsertAfter,
+    InsertBefore,
+    InsertAtStart,
+    InsertAtEnd,
+    Replace,
+    Delete,
+    Rename,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCanvasChange {
+    pub operation: SlackCanvasEditOperation,
+    pub section_id: Option<SlackCanvasSectionId>,
+    pub document_content: Option<SlackCanvasDocumentContent>,
+    pub title_content: Option<SlackCanvasDocumentContent>,
+}
+
+impl SlackCanvasChange {
+    pub fn insert_at_start(content: SlackCanvasDocumentContent) -> Self {
+        Self::new(SlackCanvasEditOperation::InsertAtStart).with_document_content(content)
+    }
+
+    pub fn insert_at_end(content: SlackCanvasDocumentContent) -> Self {
+        Self::new(SlackCanvasEditOperation::InsertAtEnd).with_document_content(content)
+    }
+
+    pub fn insert_after(
+        section_id: SlackCanvasSectionId,
+        content: SlackCanvasDocumentContent,
+    ) -> Self {
+        Self::new(SlackCanvasEditOperation::InsertAfter)
+            .with_section_id(section_id)
+            .with_document_content(content)
+    }
+
+    pub fn insert_before(
+        section_id: SlackCanvasSectionId,
+        content: SlackCanvasDocumentContent,
+    ) -> Self {
+        Self::new(SlackCanvasEditOperation::InsertBefore)
+            .with_section_id(section_id)
+            .with_document_content(content)
+    }
+
+    /// Replaces a section, or the whole canvas if `section_id` isn't specified
+    pub fn replace(
+        section_id: Option<SlackCanvasSectionId>,
+        content: SlackCanvasDocumentContent,
+    ) -> Self {
+        Self::new(SlackCanvasEditOperation::Replace)
+            .opt_section_id(section_id)
+            .with_document_content(content)
+    }
+
+    pub fn delete(section_id: SlackCanvasSectionId) -> Self {
+        Self::new(SlackCanvasEditOperation::Delete).with_section_id(section_id)
+    }
+
+    pub fn rename(title: String) -> Self {
+        Self::new(SlackCanvasEditOperation::Rename)
+            .with_title_content(SlackCanvasDocumentContent::markdown(title))
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCanvasesCreateRequest {
+    pub title: Option<String>,
+    pub document_content: Option<SlackCanvasDocumentContent>,
+    pub channel_id: Option<SlackChannelId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCanvasesCreateResponse {
+    pub canvas_id: SlackCanvasId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCanvasesEditRequest {
+    pub canvas_id: SlackCanvasId,
+    pub changes: Vec<SlackCanvasChange>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCanvasesEditResponse {}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCanvasesDeleteRequest {
+    pub canvas_id: SlackCanvasId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCanvasesDeleteResponse {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlackCanvasSectionType {
+    H1,
+    H2,
+    H3,
+    AnyHeader,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCanvasSectionsCriteria {
+    pub section_types: Option<Vec<SlackCanvasSectionType>>,
+    pub contains_text: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCanvasesSectionsLookupRequest {
+    pub canvas_id: SlackCanvasId,
+    pub criteria: SlackCanvasSectionsCriteria,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCanvasSection {
+    pub id: SlackCanvasSectionId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiCanvasesSectionsLookupResponse {
+    pub sections: Vec<SlackCanvasSection>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slack_api_canvases_edit_request() {
+        let req = SlackApiCanvasesEditRequest::new(
+            "F123".into(),
+            vec![SlackCanvasChange::replace(
+                Some("temp:C:123".into()),
+                SlackCanvasDocumentContent::markdown("# Title".into()),
+            )],
+        );
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({
+                "canvas_id": "F123",
+                "changes": [{
+                    "operation": "replace",
+                    "section_id": "temp:C:123",
+                    "document_content": { "type": "markdown", "markdown": "# Title" }
+                }]
+            })
+        );
+    }
+}
@@ -76,6 +76,10 @@ where
         &self,
         req: &SlackApiChatPostEphemeralRequest,
     ) -> ClientResult<SlackApiChatPostEphemeralResponse> {
+        let req_with_defaults = self
+            .message_defaults()
+            .map(|defaults| defaults.apply_to_post_ephemeral(req));
+        let req = req_with_defaults.as_ref().unwrap_or(req);
         self.http_session_api
             .http_post("chat.postEphemeral", req, Some(&SLACK_TIER4_METHOD_CONFIG))
             .await
@@ -88,6 +92,10 @@ where
         &self,
         req: &SlackApiChatPostMessageRequest,
     ) -> ClientResult<SlackApiChatPostMessageResponse> {
+        let req_with_defaults = self
+            .message_defaults()
+            .map(|defaults| defaults.apply_to_post_message(req));
+        let req = req_with_defaults.as_ref().unwrap_or(req);
         self.http_session_api
             .http_post(
                 "chat.postMessage",
@@ -104,6 +112,10 @@ where
         &self,
         req: &SlackApiChatScheduleMessageRequest,
     ) -> ClientResult<SlackApiChatScheduleMessageResponse> {
+        let req_with_defaults = self
+            .message_defaults()
+            .map(|defaults| defaults.apply_to_schedule_message(req));
+        let req = req_with_defaults.as_ref().unwrap_or(req);
         self.http_session_api
             .http_post(
                 "chat.scheduleMessage",
@@ -217,17 +229,22 @@ pub struct SlackApiChatPostEphemeralRequest {
     #[serde(flatten)]
     pub content: SlackMessageContent,
     pub as_user: Option<bool>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub icon_emoji: Option<String>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub icon_url: Option<String>,
     pub link_names: Option<bool>,
     pub parse: Option<String>,
     pub thread_ts: Option<SlackTs>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub username: Option<String>,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
-pub struct SlackApiChatPostEphemeralResponse {}
+pub struct SlackApiChatPostEphemeralResponse {
+    pub message_ts: Option<SlackTs>,
+}
 
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
@@ -236,11 +253,14 @@ pub struct SlackApiChatPostMessageRequest {
     #[serde(flatten)]
     pub content: SlackMessageContent,
     pub as_user: Option<bool>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub icon_emoji: Option<String>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub icon_url: Option<String>,
     pub link_names: Option<bool>,
     pub parse: Option<String>,
     pub thread_ts: Option<SlackTs>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub username: Option<String>,
     pub reply_broadcast: Option<bool>,
     pub unfurl_links: Option<bool>,
@@ -263,11 +283,14 @@ pub struct SlackApiChatScheduleMessageRequest {
     pub content: SlackMessageContent,
     pub post_at: SlackDateTime,
     pub as_user: Option<bool>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub icon_emoji: Option<String>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub icon_url: Option<String>,
     pub link_names: Option<bool>,
     pub parse: Option<String>,
     pub thread_ts: Option<SlackTs>,
+    /// Requires `chat:write.customize` scope (see `SlackBotPersona`)
     pub username: Option<String>,
     pub reply_broadcast: Option<bool>,
     pub unfurl_links: Option<bool>,
@@ -382,6 +405,7 @@ pub struct SlackApiChatScheduledMessageInfo {
     pub channel_id: SlackChannelId,
     pub post_at: SlackDateTime,
     pub date_created: SlackDateTime,
+    pub text: Option<String>,
 }
 
 impl<SCHC> SlackApiScrollableRequest<SCHC> for SlackApiChatScheduledMessagesListRequest
@@ -13,6 +13,8 @@ use crate::*;
 use futures::future::{BoxFuture, FutureExt};
 use std::collections::HashSet;
 
+const FETCH_THREAD_PAGE_LIMIT: u16 = 200;
+
 impl<'a, SCHC> SlackClientSession<'a, SCHC>
 where
     SCHC: SlackClientHttpConnector + Send,
@@ -295,6 +297,36 @@ where
             .await
     }
 
+    /// Fetches all messages of a thread (the parent message first),
+    /// following `conversations.replies` pagination
+    pub async fn fetch_thread(
+        &self,
+        channel: &SlackChannelId,
+        ts: &SlackTs,
+    ) -> ClientResult<Vec<SlackHistoryMessage>> {
+        let mut messages = Vec::new();
+        let mut req = SlackApiConversationsRepliesRequest::new(channel.clone(), ts.clone())
+            .with_limit(FETCH_THREAD_PAGE_LIMIT);
+        loop {
+            let resp = self.conversations_replies(&req).await?;
+            // Slack repeats the parent message at the top of every page
+            let is_first_page = messages.is_empty();
+            messages.extend(
+                resp.messages
+                    .into_iter()
+                    .filter(|message| is_first_page || message.origin.ts != *ts),
+            );
+            match resp
+                .response_metadata
+                .and_then(|metadata| metadata.next_cursor)
+                .filter(|cursor| !cursor.value().is_empty())
+            {
+                Some(cursor) => req = req.with_cursor(cursor),
+                None => return Ok(messages),
+            }
+        }
+    }
+
     ///
     /// https://api.slack.com/methods/conversations.setPurpose
     ///
@@ -723,3 +755,47 @@ pub struct SlackApiConversationsUnarchiveRequest {
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackApiConversationsUnarchiveResponse {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_connector::*;
+
+    #[test]
+    fn test_fetch_thread_pages() {
+        let connector = SlackTestConnector::new(|call| {
+            let (messages, next_cursor) = match call.query_param("cursor").as_deref() {
+                None => (vec!["1.1", "1.2", "1.3"], "page2"),
+                _ => (vec!["1.1", "1.4", "1.5"], ""),
+            };
+            Ok(serde_json::json!({
+                "messages": messages
+                    .into_iter()
+                    .map(|ts| serde_json::json!({ "ts": ts }))
+                    .collect::<Vec<_>>(),
+                "response_metadata": { "next_cursor": next_cursor }
+            }))
+        });
+        let client = SlackClient::new(connector.clone());
+        let token = SlackApiToken::new("xoxb-test".into());
+        let session = client.open_session(&token);
+
+        let messages =
+            futures::executor::block_on(session.fetch_thread(&"C1".into(), &"1.1".into())).unwrap();
+
+        assert_eq!(connector.calls_of("conversations.replies").len(), 2);
+        assert_eq!(
+            connector.calls_of("conversations.replies")[1]
+                .query_param("cursor")
+                .as_deref(),
+            Some("page2")
+        );
+        assert_eq!(
+            messages
+                .iter()
+                .map(|message| message.origin.ts.value().as_str())
+                .collect::<Vec<_>>(),
+            vec!["1.1", "1.2", "1.3", "1.4", "1.5"]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//!
+//! Serialization of ID lists as comma separated strings for API methods expecting them
+//!
+
+use serde::Serializer;
+use std::fmt::Display;
+
+pub(crate) fn to_csv<S: Serializer, T: Display>(values: &[T], s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(
+        &values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<String>>()
+            .join(","),
+    )
+}
+
+pub(crate) fn to_opt_csv<S: Serializer, T: Display>(
+    values: &Option<Vec<T>>,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    match values {
+        None => s.serialize_none(),
+        Some(values) => to_csv(values, s),
+    }
+}
+
+#[test]
+fn check_csv_serialization() {
+    use crate::models::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct CsvFields {
+        #[serde(serialize_with = "to_csv")]
+        users: Vec<SlackUserId>,
+        #[serde(serialize_with = "to_opt_csv")]
+        channels: Option<Vec<SlackChannelId>>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(CsvFields {
+            users: vec!["U1".into(), "U2".into()],
+            channels: None,
+        })
+        .unwrap(),
+        serde_json::json!({ "users": "U1,U2", "channels": null })
+    );
+    assert_eq!(
+        serde_json::to_value(CsvFields {
+            users: vec![],
+            channels: Some(vec!["C1".into()]),
+        })
+        .unwrap(),
+        serde_json::json!({ "users": "", "channels": "C1" })
+    );
+}
@@ -4,9 +4,10 @@
 
 use rsb_derive::Builder;
 use rvstruct::ValueStruct;
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
+use crate::api::csv::*;
 use crate::api::{
     SlackApiUsersConversationsRequest, SlackApiUsersConversationsResponse,
     SlackApiUsersProfileSetRequest, SlackApiUsersProfileSetResponse,
@@ -16,6 +17,8 @@ use crate::multipart_form::FileMultipartData;
 use crate::ratectl::*;
 use crate::SlackClientSession;
 use crate::{ClientResult, SlackClientHttpConnector};
+use futures::stream::BoxStream;
+use url::Url;
 
 impl<'a, SCHC> SlackClientSession<'a, SCHC>
 where
@@ -72,6 +75,65 @@ where
             .await
     }
 
+    ///
+    /// https://api.slack.com/methods/files.list
+    ///
+    pub async fn files_list(
+        &self,
+        req: &SlackApiFilesListRequest,
+    ) -> ClientResult<SlackApiFilesListResponse> {
+        self.http_session_api
+            .http_get(
+                "files.list",
+                &vec![
+                    ("channel", req.channel.as_ref().map(|x| x.value())),
+                    ("user", req.user.as_ref().map(|x| x.value())),
+                    (
+                        "ts_from",
+                        req.ts_from
+                            .as_ref()
+                            .map(|x| x.0.timestamp().to_string())
+                            .as_ref(),
+                    ),
+                    (
+                        "ts_to",
+                        req.ts_to
+                            .as_ref()
+                            .map(|x| x.0.timestamp().to_string())
+                            .as_ref(),
+                    ),
+                    ("types", req.types.as_ref()),
+                    ("count", req.count.map(|v| v.to_string()).as_ref()),
+                    ("page", req.page.map(|v| v.to_string()).as_ref()),
+                    (
+                        "show_files_hidden_by_limit",
+                        req.show_files_hidden_by_limit
+                            .map(|v| v.to_string())
+                            .as_ref(),
+                    ),
+                    ("team_id", req.team_id.as_ref().map(|x| x.value())),
+                ],
+                Some(&SLACK_TIER3_METHOD_CONFIG),
+            )
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/files.info
+    ///
+    pub async fn files_info(
+        &self,
+        req: &SlackApiFilesInfoRequest,
+    ) -> ClientResult<SlackApiFilesInfoResponse> {
+        self.http_session_api
+            .http_get(
+                "files.info",
+                &vec![("file", Some(req.file.value()))],
+                Some(&SLACK_TIER4_METHOD_CONFIG),
+            )
+            .await
+    }
+
     ///
     /// https://api.slack.com/methods/files.getUploadURLExternal
     ///
@@ -107,6 +169,45 @@ where
             .await
     }
 
+    /// Uploads file content from a stream (see `files_upload_via_url`)
+    /// without loading the whole file in memory.
+    pub async fn files_upload_via_url_stream(
+        &self,
+        upload_url: &SlackFileUploadUrl,
+        content_type: String,
+        content_length: u64,
+        content: BoxStream<'static, std::io::Result<bytes::Bytes>>,
+    ) -> ClientResult<SlackApiFilesUploadViaUrlResponse> {
+        self.http_session_api
+            .http_post_uri_stream(
+                upload_url.value().clone(),
+                content_type,
+                content_length,
+                content,
+                Some(&SLACK_TIER4_METHOD_CONFIG),
+            )
+            .await
+    }
+
+    ///
+    /// Downloads file content using private file URLs (`url_private` or `url_private_download`)
+    ///
+    pub async fn files_download(&self, file_url: &Url) -> ClientResult<bytes::Bytes> {
+        self.http_session_api
+            .http_get_uri_binary(file_url.clone())
+            .await
+    }
+
+    /// Downloads file content as a stream of chunks, without loading large files in memory
+    pub async fn files_download_stream(
+        &self,
+        file_url: &Url,
+    ) -> ClientResult<BoxStream<'_, ClientResult<bytes::Bytes>>> {
+        self.http_session_api
+            .http_get_uri_binary_stream(file_url.clone())
+            .await
+    }
+
     ///
     /// https://api.slack.com/methods/files.completeUploadExternal
     ///
@@ -127,7 +228,7 @@ where
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackApiFilesUploadRequest {
-    #[serde(serialize_with = "to_csv")]
+    #[serde(serialize_with = "to_opt_csv")]
     pub channels: Option<Vec<SlackChannelId>>,
     pub content: Option<String>,
     pub binary_content: Option<Vec<u8>>,
@@ -145,6 +246,49 @@ pub struct SlackApiFilesUploadResponse {
     pub file: SlackFile,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiFilesListRequest {
+    pub channel: Option<SlackChannelId>,
+    pub user: Option<SlackUserId>,
+    pub ts_from: Option<SlackDateTime>,
+    pub ts_to: Option<SlackDateTime>,
+    /// Comma separated file types (e.g. `images,pdfs`)
+    pub types: Option<String>,
+    pub count: Option<u16>,
+    pub page: Option<u32>,
+    pub show_files_hidden_by_limit: Option<bool>,
+    pub team_id: Option<SlackTeamId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiFilesListResponse {
+    pub files: Vec<SlackFile>,
+    pub paging: Option<SlackApiFilesListPaging>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiFilesListPaging {
+    pub count: u32,
+    pub total: u32,
+    pub page: u32,
+    pub pages: u32,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiFilesInfoRequest {
+    pub file: SlackFileId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiFilesInfoResponse {
+    pub file: SlackFile,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackApiFilesGetUploadUrlExternalRequest {
@@ -194,13 +338,3 @@ pub struct SlackApiFilesComplete {
     pub id: SlackFileId,
     pub title: Option<String>,
 }
-
-fn to_csv<S: Serializer>(x: &Option<Vec<SlackChannelId>>, s: S) -> Result<S::Ok, S::Error> {
-    match x {
-        None => s.serialize_none(),
-        Some(ids) => {
-            let y: Vec<String> = ids.iter().map(|v| v.0.clone()).collect();
-            y.join(",").serialize(s)
-        }
-    }
-}
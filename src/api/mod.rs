@@ -1,13 +1,21 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 
+mod admin;
 mod apps;
+mod audit;
 mod auth;
 mod bots;
+mod calls;
+mod canvases;
 mod chat;
 mod conversations;
+mod csv;
 mod files;
 mod oauth;
+mod openid;
+mod pins;
 mod reactions;
+mod search;
 mod stars;
 mod team;
 mod test;
@@ -15,15 +23,23 @@ mod usergroups;
 mod users;
 mod views;
 mod webhook;
+mod workflows;
 
+pub use admin::*;
 pub use apps::*;
+pub use audit::*;
 pub use auth::*;
 pub use bots::*;
+pub use calls::*;
+pub use canvases::*;
 pub use chat::*;
 pub use conversations::*;
 pub use files::*;
 pub use oauth::*;
+pub use openid::*;
+pub use pins::*;
 pub use reactions::*;
+pub use search::*;
 pub use stars::*;
 pub use team::*;
 pub use test::*;
@@ -31,3 +47,4 @@ pub use usergroups::*;
 pub use users::*;
 pub use views::*;
 pub use webhook::*;
+pub use workflows::*;
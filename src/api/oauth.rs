@@ -5,7 +5,7 @@
 use rsb_derive::Builder;
 use rvstruct::*;
 use serde::{Deserialize, Serialize};
-use serde_with::skip_serializing_none;
+use serde_with::{serde_as, skip_serializing_none};
 use std::fmt;
 
 use crate::client::*;
@@ -25,9 +25,7 @@ where
         req: &SlackOAuthV2AccessTokenRequest,
     ) -> ClientResult<SlackOAuthV2AccessTokenResponse> {
         let full_uri: Url = SlackClientHttpApiUri::create_url_with_params(
-            self.http_api
-                .connector
-                .create_method_uri_path("oauth.v2.access")?,
+            self.create_method_uri_path("oauth.v2.access")?,
             &vec![
                 ("code", Some(req.code.value())),
                 (
@@ -56,6 +54,7 @@ pub struct SlackOAuthV2AccessTokenRequest {
     pub redirect_uri: Option<Url>,
 }
 
+#[serde_as]
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackOAuthV2AccessTokenResponse {
@@ -64,11 +63,50 @@ pub struct SlackOAuthV2AccessTokenResponse {
     pub scope: SlackApiTokenScope,
     pub bot_user_id: Option<SlackUserId>,
     pub app_id: SlackAppId,
+    /// Empty (with an empty team ID) for org-wide installations, which have no team
+    #[serde(default)]
+    #[serde_as(as = "serde_with::DefaultOnNull")]
     pub team: SlackTeamInfo,
+    pub enterprise: Option<SlackEnterpriseInfo>,
+    pub is_enterprise_install: Option<bool>,
     pub authed_user: SlackOAuthV2AuthedUser,
     pub incoming_webhook: Option<SlackOAuthIncomingWebHook>,
 }
 
+impl SlackOAuthV2AccessTokenResponse {
+    /// Team ID of the installation or `None` for org-wide installations
+    pub fn team_id(&self) -> Option<&SlackTeamId> {
+        Some(&self.team.id).filter(|team_id| !team_id.value().is_empty())
+    }
+
+    pub fn installation_key(&self) -> Option<SlackInstallationKey> {
+        SlackInstallationKey::from_ids(
+            self.team_id(),
+            self.enterprise.as_ref().map(|enterprise| &enterprise.id),
+            self.is_enterprise_install.unwrap_or(false),
+        )
+    }
+
+    /// Creates a bot token for this installation.
+    /// Org-wide installations have no team, so the token is bound to the enterprise and
+    /// API calls need a team context (see `SlackApiToken::team_id`).
+    pub fn to_bot_token(&self) -> SlackApiToken {
+        SlackApiToken::new(self.access_token.clone())
+            .with_token_type(self.token_type.clone())
+            .with_scope(self.scope.clone())
+            .opt_team_id(self.team_id().cloned())
+            .opt_enterprise_id(
+                self.is_enterprise_install
+                    .filter(|is_enterprise_install| *is_enterprise_install)
+                    .and(
+                        self.enterprise
+                            .as_ref()
+                            .map(|enterprise| enterprise.id.clone()),
+                    ),
+            )
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackOAuthV2AuthedUser {
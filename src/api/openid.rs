@@ -0,0 +1,259 @@
+//!
+//! Support for Slack OpenID Connect (Sign in with Slack) API methods
+//!
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rsb_derive::Builder;
+use rvstruct::*;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::fmt;
+use tracing::*;
+
+use crate::api::SlackOAuthCode;
+use crate::client::*;
+use crate::endpoints::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::ratectl::*;
+use crate::token::*;
+use url::Url;
+
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// https://api.slack.com/methods/openid.connect.token
+    ///
+    pub async fn openid_connect_token(
+        &self,
+        req: &SlackOpenIdConnectTokenRequest,
+    ) -> ClientResult<SlackOpenIdConnectTokenResponse> {
+        let full_uri: Url = SlackClientHttpApiUri::create_url_with_params(
+            self.create_method_uri_path("openid.connect.token")?,
+            &vec![
+                ("code", req.code.as_ref().map(|x| x.value())),
+                ("grant_type", req.grant_type.as_ref()),
+                (
+                    "redirect_uri",
+                    req.redirect_uri
+                        .as_ref()
+                        .map(|url| url.as_str().to_string())
+                        .as_ref(),
+                ),
+                (
+                    "refresh_token",
+                    req.refresh_token.as_ref().map(|x| x.value()),
+                ),
+            ],
+        )?;
+
+        self.http_api
+            .connector
+            .http_get_with_client_secret(full_uri, &req.client_id, &req.client_secret)
+            .await
+    }
+
+    ///
+    /// Slack OpenID Connect public keys (JWKS) to verify ID tokens
+    /// https://slack.com/openid/connect/keys
+    /// (not a Web API method, so the response has no `ok` envelope)
+    ///
+    pub async fn openid_connect_keys(&self) -> ClientResult<SlackOpenIdConnectKeysResponse> {
+        let http_openid_span = span!(Level::DEBUG, "Slack OpenID Connect Keys");
+
+        let context = SlackClientApiCallContext {
+            rate_control_params: None,
+            token: None,
+            tracing_span: &http_openid_span,
+            is_sensitive_url: false,
+        };
+
+        let body = self
+            .http_api
+            .connector
+            .http_get_uri_binary(self.endpoints().openid_keys_url.parse()?, context)
+            .await?;
+        decode_json_body(&String::from_utf8_lossy(&body))
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// https://api.slack.com/methods/openid.connect.userInfo
+    ///
+    pub async fn openid_connect_user_info(
+        &self,
+    ) -> ClientResult<SlackOpenIdConnectUserInfoResponse> {
+        self.http_session_api
+            .http_get(
+                "openid.connect.userInfo",
+                &crate::client::SLACK_HTTP_EMPTY_GET_PARAMS.clone(),
+                Some(&SLACK_TIER3_METHOD_CONFIG),
+            )
+            .await
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackOpenIdConnectConfig {
+    pub client_id: SlackClientId,
+    pub client_secret: SlackClientSecret,
+    pub redirect_uri: Url,
+    #[default = "SlackOpenIdConnectConfig::DEFAULT_SCOPE_VALUE.into()"]
+    pub scope: String,
+    pub team_id: Option<SlackTeamId>,
+}
+
+impl SlackOpenIdConnectConfig {
+    pub const DEFAULT_SCOPE_VALUE: &'static str = "openid profile email";
+
+    pub const OPENID_AUTHORIZE_URL_VALUE: &'static str =
+        "https://slack.com/openid/connect/authorize";
+
+    pub const OPENID_KEYS_URL_VALUE: &'static str = "https://slack.com/openid/connect/keys";
+
+    pub const OPENID_ISSUER_VALUE: &'static str = "https://slack.com";
+
+    pub fn to_authorize_url(&self, state: &str, nonce: &SlackOpenIdNonce) -> ClientResult<Url> {
+        self.to_authorize_url_with(&SLACK_DEFAULT_API_ENDPOINTS, state, nonce)
+    }
+
+    /// Authorize URL for region-specific endpoints (use `SlackClient::endpoints()`)
+    pub fn to_authorize_url_with(
+        &self,
+        endpoints: &SlackApiEndpoints,
+        state: &str,
+        nonce: &SlackOpenIdNonce,
+    ) -> ClientResult<Url> {
+        SlackClientHttpApiUri::create_url_with_params(
+            endpoints.openid_authorize_url.parse()?,
+            &vec![
+                ("response_type", Some("code")),
+                ("scope", Some(self.scope.as_str())),
+                ("client_id", Some(self.client_id.value().as_str())),
+                ("state", Some(state)),
+                ("nonce", Some(nonce.value().as_str())),
+                ("redirect_uri", Some(self.redirect_uri.as_str())),
+                ("team", self.team_id.as_ref().map(|x| x.value().as_str())),
+            ],
+        )
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackOpenIdNonce(pub String);
+
+impl SlackOpenIdNonce {
+    const GENERATED_NONCE_LEN: usize = 32;
+
+    pub fn generate() -> Self {
+        Self(
+            rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(Self::GENERATED_NONCE_LEN)
+                .map(char::from)
+                .collect(),
+        )
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackOpenIdToken(pub String);
+
+impl fmt::Debug for SlackOpenIdToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SlackOpenIdToken(len:{})", self.value().len())
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackOpenIdConnectTokenRequest {
+    pub client_id: SlackClientId,
+    pub client_secret: SlackClientSecret,
+    pub code: Option<SlackOAuthCode>,
+    pub grant_type: Option<String>,
+    pub redirect_uri: Option<Url>,
+    pub refresh_token: Option<SlackApiTokenValue>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackOpenIdConnectTokenResponse {
+    pub access_token: SlackApiTokenValue,
+    pub token_type: String,
+    pub id_token: SlackOpenIdToken,
+    pub refresh_token: Option<SlackApiTokenValue>,
+    pub expires_in: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackOpenIdConnectUserProfile {
+    pub sub: SlackUserId,
+    #[serde(rename = "https://slack.com/user_id")]
+    pub user_id: SlackUserId,
+    #[serde(rename = "https://slack.com/team_id")]
+    pub team_id: SlackTeamId,
+    #[serde(rename = "https://slack.com/enterprise_id")]
+    pub enterprise_id: Option<SlackEnterpriseId>,
+    pub email: Option<EmailAddress>,
+    pub email_verified: Option<bool>,
+    pub date_email_verified: Option<SlackDateTime>,
+    pub name: Option<String>,
+    pub picture: Option<Url>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    pub locale: Option<SlackLocale>,
+    #[serde(rename = "https://slack.com/team_name")]
+    pub team_name: Option<String>,
+    #[serde(rename = "https://slack.com/team_domain")]
+    pub team_domain: Option<String>,
+    #[serde(rename = "https://slack.com/enterprise_name")]
+    pub enterprise_name: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackOpenIdConnectUserInfoResponse {
+    #[serde(flatten)]
+    pub profile: SlackOpenIdConnectUserProfile,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackOpenIdConnectIdTokenClaims {
+    pub iss: String,
+    pub aud: SlackClientId,
+    pub exp: SlackDateTime,
+    pub iat: SlackDateTime,
+    pub auth_time: Option<SlackDateTime>,
+    pub nonce: Option<SlackOpenIdNonce>,
+    pub at_hash: Option<String>,
+    #[serde(flatten)]
+    pub profile: SlackOpenIdConnectUserProfile,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackOpenIdConnectKeysResponse {
+    pub keys: Vec<SlackOpenIdConnectJsonWebKey>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackOpenIdConnectJsonWebKey {
+    pub kid: String,
+    pub kty: String,
+    pub alg: Option<String>,
+    #[serde(rename = "use")]
+    pub key_use: Option<String>,
+    pub n: String,
+    pub e: String,
+}
@@ -0,0 +1,236 @@
+//!
+//! Support for Slack Search API methods
+//!
+
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::models::*;
+use crate::ratectl::*;
+use crate::*;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use url::Url;
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// https://api.slack.com/methods/search.messages
+    ///
+    pub async fn search_messages(
+        &self,
+        req: &SlackApiSearchMessagesRequest,
+    ) -> ClientResult<SlackApiSearchMessagesResponse> {
+        self.http_session_api
+            .http_get(
+                "search.messages",
+                &vec![
+                    ("query", Some(&req.query)),
+                    ("count", req.count.map(|v| v.to_string()).as_ref()),
+                    ("page", req.page.map(|v| v.to_string()).as_ref()),
+                    ("sort", req.sort.as_ref().map(|v| v.to_string()).as_ref()),
+                    (
+                        "sort_dir",
+                        req.sort_dir.as_ref().map(|v| v.to_string()).as_ref(),
+                    ),
+                    ("highlight", req.highlight.map(|v| v.to_string()).as_ref()),
+                    ("team_id", req.team_id.as_ref().map(|x| x.value())),
+                ],
+                Some(&SLACK_TIER2_METHOD_CONFIG),
+            )
+            .await
+    }
+
+    /// Search messages matches as a stream walking through all result pages
+    /// (search API uses page numbers instead of cursors), starting from `req.page` if specified.
+    pub fn search_messages_stream<'s>(
+        &'s self,
+        req: &SlackApiSearchMessagesRequest,
+    ) -> BoxStream<'s, ClientResult<SlackSearchMessageMatch>>
+    where
+        SCHC: Sync,
+    {
+        let first_page_req = req.clone();
+        futures::stream::try_unfold(Some(first_page_req), move |maybe_req| async move {
+            match maybe_req {
+                Some(page_req) => {
+                    let resp = self.search_messages(&page_req).await?;
+                    let next_page_req = resp
+                        .messages
+                        .paging
+                        .as_ref()
+                        .filter(|paging| paging.page < paging.pages)
+                        .map(|paging| page_req.clone().with_page(paging.page + 1));
+                    ClientResult::Ok(Some((resp.messages.matches, next_page_req)))
+                }
+                None => Ok(None),
+            }
+        })
+        .map_ok(|matches| futures::stream::iter(matches.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum SlackSearchSortType {
+    #[serde(rename = "score")]
+    Score,
+    #[serde(rename = "timestamp")]
+    Timestamp,
+}
+
+impl std::fmt::Display for SlackSearchSortType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlackSearchSortType::Score => write!(f, "score"),
+            SlackSearchSortType::Timestamp => write!(f, "timestamp"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum SlackSearchSortDirection {
+    #[serde(rename = "asc")]
+    Asc,
+    #[serde(rename = "desc")]
+    Desc,
+}
+
+impl std::fmt::Display for SlackSearchSortDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlackSearchSortDirection::Asc => write!(f, "asc"),
+            SlackSearchSortDirection::Desc => write!(f, "desc"),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiSearchMessagesRequest {
+    pub query: String,
+    pub count: Option<u16>,
+    pub page: Option<u32>,
+    pub sort: Option<SlackSearchSortType>,
+    pub sort_dir: Option<SlackSearchSortDirection>,
+    pub highlight: Option<bool>,
+    pub team_id: Option<SlackTeamId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiSearchMessagesResponse {
+    pub query: String,
+    pub messages: SlackSearchMessagesResult,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackSearchMessagesResult {
+    pub matches: Vec<SlackSearchMessageMatch>,
+    pub total: Option<u64>,
+    pub paging: Option<SlackSearchPaging>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackSearchPaging {
+    pub count: u32,
+    pub total: u32,
+    pub page: u32,
+    pub pages: u32,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackSearchMessageMatch {
+    pub iid: Option<String>,
+    pub ts: SlackTs,
+    pub channel: SlackSearchMatchChannel,
+    pub team: Option<SlackTeamId>,
+    pub user: Option<SlackUserId>,
+    pub username: Option<String>,
+    #[serde(flatten)]
+    pub content: SlackMessageContent,
+    pub permalink: Option<Url>,
+    #[serde(rename = "type")]
+    pub match_type: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackSearchMatchChannel {
+    pub id: SlackChannelId,
+    pub name: Option<String>,
+    pub is_channel: Option<bool>,
+    pub is_group: Option<bool>,
+    pub is_im: Option<bool>,
+    pub is_mpim: Option<bool>,
+    pub is_private: Option<bool>,
+    pub is_shared: Option<bool>,
+    pub is_ext_shared: Option<bool>,
+    pub is_org_shared: Option<bool>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slack_api_search_messages_response() {
+        let payload = include_str!("./fixtures/slack_api_search_messages_response.json");
+        let model: SlackApiSearchMessagesResponse = serde_json::from_str(payload).unwrap();
+        assert_eq!(model.messages.matches.len(), 1);
+        assert_eq!(model.messages.matches[0].channel.id, "C12345678".into());
+        assert_eq!(
+            model.messages.paging,
+            Some(SlackSearchPaging::new(1, 2, 1, 2))
+        );
+    }
+
+    #[test]
+    fn test_search_messages_stream_pages() {
+        use crate::test_connector::*;
+
+        let connector = SlackTestConnector::new(|call: &SlackTestCall| {
+            let page: u32 = call.param("page").map_or(1, |page| page.parse().unwrap());
+            Ok(serde_json::json!({
+                "query": "deploy",
+                "messages": {
+                    "matches": [
+                        { "ts": format!("{page}.1"), "channel": { "id": "C1" } },
+                        { "ts": format!("{page}.2"), "channel": { "id": "C1" } }
+                    ],
+                    "paging": { "count": 2, "total": 4, "page": page, "pages": 2 }
+                }
+            }))
+        });
+        let client = SlackClient::new(connector.clone());
+        let token = SlackApiToken::new("xoxb-test".into());
+        let session = client.open_session(&token);
+
+        let matches: Vec<SlackSearchMessageMatch> = futures::executor::block_on(
+            session
+                .search_messages_stream(
+                    &SlackApiSearchMessagesRequest::new("deploy".into()).with_count(2),
+                )
+                .try_collect(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            matches.iter().map(|m| m.ts.clone()).collect::<Vec<_>>(),
+            vec!["1.1".into(), "1.2".into(), "2.1".into(), "2.2".into()]
+        );
+        let calls = connector.calls_of("search.messages");
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].param("page"), None);
+        assert_eq!(calls[1].param("page").as_deref(), Some("2"));
+        assert_eq!(calls[1].param("query").as_deref(), Some("deploy"));
+    }
+}
@@ -21,8 +21,6 @@ where
         let full_uri = SlackClientHttpApiUri::create_url_with_params(
             self.http_session_api
                 .client
-                .http_api
-                .connector
                 .create_method_uri_path("api.test")?,
             &vec![("foo", req.foo.as_ref()), ("error", req.error.as_ref())],
         )?;
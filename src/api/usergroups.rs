@@ -7,6 +7,7 @@ use rvstruct::ValueStruct;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
+use crate::api::csv::*;
 use crate::models::*;
 use crate::ratectl::*;
 use crate::SlackClientSession;
@@ -68,6 +69,22 @@ where
             )
             .await
     }
+
+    ///
+    /// https://api.slack.com/methods/usergroups.users.update
+    ///
+    pub async fn usergroups_users_update(
+        &self,
+        req: &SlackApiUserGroupsUsersUpdateRequest,
+    ) -> ClientResult<SlackApiUserGroupsUsersUpdateResponse> {
+        self.http_session_api
+            .http_post(
+                "usergroups.users.update",
+                req,
+                Some(&SLACK_TIER2_METHOD_CONFIG),
+            )
+            .await
+    }
 }
 
 #[skip_serializing_none]
@@ -96,3 +113,18 @@ pub struct SlackApiUserGroupsUsersListRequest {
 pub struct SlackApiUserGroupsUsersListResponse {
     pub users: Vec<SlackUserId>,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiUserGroupsUsersUpdateRequest {
+    pub usergroup: SlackUserGroupId,
+    #[serde(serialize_with = "to_csv")]
+    pub users: Vec<SlackUserId>,
+    pub include_count: Option<bool>,
+    pub team_id: Option<SlackTeamId>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiUserGroupsUsersUpdateResponse {
+    pub usergroup: SlackUserGroup,
+}
@@ -60,6 +60,8 @@ pub struct SlackApiPostWebhookMessageRequest {
     #[serde(flatten)]
     pub content: SlackMessageContent,
     pub thread_ts: Option<SlackTs>,
+    /// For `response_url`s: post visibly in the channel or only to the user
+    pub response_type: Option<SlackMessageResponseType>,
     pub replace_original: Option<bool>,
     pub delete_original: Option<bool>,
 }
@@ -77,3 +79,15 @@ lazy_static! {
             )
         );
 }
+
+#[test]
+fn check_webhook_message_request_body() {
+    let req =
+        SlackApiPostWebhookMessageRequest::new(SlackMessageContent::new().with_text("Hey".into()))
+            .with_response_type(SlackMessageResponseType::Ephemeral)
+            .with_replace_original(true);
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        serde_json::json!({ "text": "Hey", "response_type": "ephemeral", "replace_original": true })
+    );
+}
@@ -0,0 +1,265 @@
+//!
+//! Support for Slack automation platform workflow triggers
+//!
+
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::BTreeMap;
+use tracing::*;
+use url::Url;
+
+use crate::models::*;
+use crate::ratectl::*;
+use crate::{ClientResult, SlackClientHttpConnector};
+use crate::{SlackClient, SlackClientSession};
+use rvstruct::ValueStruct;
+
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// Start a workflow using a webhook trigger url and trigger inputs
+    ///
+    pub async fn trigger_workflow_webhook(
+        &self,
+        webhook_trigger_url: &Url,
+        inputs: &SlackWorkflowTriggerInputs,
+    ) -> ClientResult<SlackApiWorkflowWebhookTriggerResponse> {
+        let http_webhook_span = span!(Level::DEBUG, "Slack Workflow Trigger");
+
+        let context = crate::SlackClientApiCallContext {
+            rate_control_params: Some(&SLACK_TIER3_METHOD_CONFIG),
+            token: None,
+            tracing_span: &http_webhook_span,
+            is_sensitive_url: true,
+        };
+
+        self.http_api
+            .connector
+            .http_post_uri(webhook_trigger_url.clone(), inputs, context)
+            .await
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// https://api.slack.com/methods/workflows.triggers.permissions.add
+    ///
+    pub async fn workflows_triggers_permissions_add(
+        &self,
+        req: &SlackApiWorkflowsTriggersPermissionsAddRequest,
+    ) -> ClientResult<SlackApiWorkflowsTriggersPermissionsResponse> {
+        self.http_session_api
+            .http_post(
+                "workflows.triggers.permissions.add",
+                req,
+                Some(&SLACK_TIER3_METHOD_CONFIG),
+            )
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/workflows.triggers.permissions.list
+    ///
+    pub async fn workflows_triggers_permissions_list(
+        &self,
+        req: &SlackApiWorkflowsTriggersPermissionsListRequest,
+    ) -> ClientResult<SlackApiWorkflowsTriggersPermissionsResponse> {
+        self.http_session_api
+            .http_post(
+                "workflows.triggers.permissions.list",
+                req,
+                Some(&SLACK_TIER3_METHOD_CONFIG),
+            )
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/workflows.triggers.permissions.remove
+    ///
+    pub async fn workflows_triggers_permissions_remove(
+        &self,
+        req: &SlackApiWorkflowsTriggersPermissionsRemoveRequest,
+    ) -> ClientResult<SlackApiWorkflowsTriggersPermissionsResponse> {
+        self.http_session_api
+            .http_post(
+                "workflows.triggers.permissions.remove",
+                req,
+                Some(&SLACK_TIER3_METHOD_CONFIG),
+            )
+            .await
+    }
+
+    ///
+    /// https://api.slack.com/methods/workflows.triggers.permissions.set
+    ///
+    pub async fn workflows_triggers_permissions_set(
+        &self,
+        req: &SlackApiWorkflowsTriggersPermissionsSetRequest,
+    ) -> ClientResult<SlackApiWorkflowsTriggersPermissionsResponse> {
+        self.http_session_api
+            .http_post(
+                "workflows.triggers.permissions.set",
+                req,
+                Some(&SLACK_TIER3_METHOD_CONFIG),
+            )
+            .await
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SlackWorkflowInputValue {
+    Text(String),
+    Integer(i64),
+    Number(f64),
+    Boolean(bool),
+    List(Vec<SlackWorkflowInputValue>),
+}
+
+impl From<String> for SlackWorkflowInputValue {
+    fn from(value: String) -> Self {
+        SlackWorkflowInputValue::Text(value)
+    }
+}
+
+impl From<&str> for SlackWorkflowInputValue {
+    fn from(value: &str) -> Self {
+        SlackWorkflowInputValue::Text(value.to_string())
+    }
+}
+
+impl From<i64> for SlackWorkflowInputValue {
+    fn from(value: i64) -> Self {
+        SlackWorkflowInputValue::Integer(value)
+    }
+}
+
+impl From<f64> for SlackWorkflowInputValue {
+    fn from(value: f64) -> Self {
+        SlackWorkflowInputValue::Number(value)
+    }
+}
+
+impl From<bool> for SlackWorkflowInputValue {
+    fn from(value: bool) -> Self {
+        SlackWorkflowInputValue::Boolean(value)
+    }
+}
+
+impl From<SlackUserId> for SlackWorkflowInputValue {
+    fn from(value: SlackUserId) -> Self {
+        SlackWorkflowInputValue::Text(value.0)
+    }
+}
+
+impl From<SlackChannelId> for SlackWorkflowInputValue {
+    fn from(value: SlackChannelId) -> Self {
+        SlackWorkflowInputValue::Text(value.0)
+    }
+}
+
+impl From<SlackDateTime> for SlackWorkflowInputValue {
+    fn from(value: SlackDateTime) -> Self {
+        SlackWorkflowInputValue::Integer(value.0.timestamp())
+    }
+}
+
+impl<T: Into<SlackWorkflowInputValue>> From<Vec<T>> for SlackWorkflowInputValue {
+    fn from(values: Vec<T>) -> Self {
+        SlackWorkflowInputValue::List(values.into_iter().map(|value| value.into()).collect())
+    }
+}
+
+/// Webhook trigger inputs (variables defined for the trigger)
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default, ValueStruct)]
+pub struct SlackWorkflowTriggerInputs(pub BTreeMap<String, SlackWorkflowInputValue>);
+
+impl SlackWorkflowTriggerInputs {
+    pub fn with_input<V: Into<SlackWorkflowInputValue>>(mut self, name: &str, value: V) -> Self {
+        self.0.insert(name.to_string(), value.into());
+        self
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiWorkflowWebhookTriggerResponse {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlackWorkflowTriggerPermissionType {
+    Everyone,
+    AppCollaborators,
+    NamedEntities,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackWorkflowTriggerNamedEntities {
+    pub channel_ids: Option<Vec<SlackChannelId>>,
+    pub org_ids: Option<Vec<SlackEnterpriseId>>,
+    pub team_ids: Option<Vec<SlackTeamId>>,
+    pub user_ids: Option<Vec<SlackUserId>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiWorkflowsTriggersPermissionsAddRequest {
+    pub trigger_id: SlackWorkflowTriggerId,
+    #[serde(flatten)]
+    pub entities: SlackWorkflowTriggerNamedEntities,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiWorkflowsTriggersPermissionsListRequest {
+    pub trigger_id: SlackWorkflowTriggerId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiWorkflowsTriggersPermissionsRemoveRequest {
+    pub trigger_id: SlackWorkflowTriggerId,
+    #[serde(flatten)]
+    pub entities: SlackWorkflowTriggerNamedEntities,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiWorkflowsTriggersPermissionsSetRequest {
+    pub trigger_id: SlackWorkflowTriggerId,
+    pub permission_type: SlackWorkflowTriggerPermissionType,
+    #[serde(flatten)]
+    pub entities: Option<SlackWorkflowTriggerNamedEntities>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiWorkflowsTriggersPermissionsResponse {
+    pub permission_type: SlackWorkflowTriggerPermissionType,
+    #[serde(flatten)]
+    pub entities: SlackWorkflowTriggerNamedEntities,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slack_workflow_trigger_inputs() {
+        let inputs = SlackWorkflowTriggerInputs::default()
+            .with_input("title", "Deploy")
+            .with_input("attempt", 2)
+            .with_input("reviewers", vec![SlackUserId::new("U1".into())]);
+        assert_eq!(
+            serde_json::to_value(&inputs).unwrap(),
+            serde_json::json!({ "attempt": 2, "reviewers": ["U1"], "title": "Deploy" })
+        );
+    }
+}
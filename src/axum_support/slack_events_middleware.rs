@@ -2,6 +2,7 @@ use crate::axum_support::slack_events_extractors::{
     SlackEventsEmptyExtractor, SlackEventsExtractor,
 };
 use crate::axum_support::SlackEventsAxumListener;
+use crate::events::SlackPushEvent;
 use crate::hyper_tokio::SlackClientHyperConnector;
 use crate::listener::SlackClientEventsListenerEnvironment;
 use crate::prelude::hyper_ext::HyperExtensions;
@@ -106,6 +107,24 @@ where
                             .body(Body::default())
                             .unwrap())
                     } else {
+                        if let (Some(api_cache), Some(push_event)) = (
+                            environment.client.api_cache.as_ref(),
+                            verified_request.extensions().get::<SlackPushEvent>(),
+                        ) {
+                            api_cache.invalidate_by_push_event(push_event);
+                        }
+
+                        if let Some(SlackPushEvent::AppRateLimited(rate_limited_event)) =
+                            verified_request.extensions().get::<SlackPushEvent>()
+                        {
+                            environment
+                                .client
+                                .http_api
+                                .connector
+                                .app_rate_limited(rate_limited_event)
+                                .await;
+                        }
+
                         *verified_request.body_mut() = Body::from(verified_body);
 
                         debug!("Calling a route service with Slack event: {}", &request_uri);
@@ -28,18 +28,9 @@ impl<H: 'static + Send + Sync + Connect + Clone> SlackEventsAxumListener<H> {
         move |_| {
             let config = config.clone();
             let environment = environment.clone();
+            let oauth_endpoints = environment.client.endpoints().clone();
             async move {
-                let full_uri = SlackClientHttpApiUri::create_url_with_params(
-                    SlackOAuthListenerConfig::OAUTH_AUTHORIZE_URL_VALUE.parse()?,
-                    &vec![
-                        ("client_id", Some(config.client_id.value())),
-                        ("scope", Some(&config.bot_scope)),
-                        (
-                            "redirect_uri",
-                            Some(config.to_redirect_url()?.as_str().to_string()).as_ref(),
-                        ),
-                    ],
-                )?;
+                let full_uri = config.to_authorize_url_with(&oauth_endpoints, None)?;
                 debug!("Redirecting to Slack OAuth authorize: {}", &full_uri);
                 HyperExtensions::hyper_redirect_to(full_uri.as_ref()).map(|r| r.into_response())
             }
@@ -88,13 +79,18 @@ impl<H: 'static + Send + Sync + Connect + Clone> SlackEventsAxumListener<H> {
                         match oauth_access_resp {
                             Ok(oauth_resp) => {
                                 info!(
-                                    "Received slack OAuth access resp for: {} / {} / {}",
-                                    &oauth_resp.team.id,
+                                    "Received slack OAuth access resp for: {:?} / {} / {}",
+                                    oauth_resp.installation_key(),
                                     &oauth_resp
                                         .team
                                         .name
-                                        .as_ref()
-                                        .cloned()
+                                        .clone()
+                                        .or_else(|| {
+                                            oauth_resp
+                                                .enterprise
+                                                .as_ref()
+                                                .and_then(|enterprise| enterprise.name.clone())
+                                        })
                                         .unwrap_or_else(|| "".into()),
                                     &oauth_resp.authed_user.id
                                 );
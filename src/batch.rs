@@ -0,0 +1,192 @@
+//!
+//! Concurrent batches of heterogeneous API calls with a parallelism cap.
+//! Calls go through the session connector, so they share its rate limiting.
+//!
+
+use crate::errors::*;
+use crate::*;
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::StreamExt;
+use std::any::Any;
+use std::future::Future;
+use std::marker::PhantomData;
+
+type SlackApiBatchCallResult = ClientResult<Box<dyn Any + Send>>;
+
+/// Calls to run concurrently, created with `session.batch()`
+pub struct SlackApiBatch<'b> {
+    parallelism: usize,
+    calls: Vec<BoxFuture<'b, SlackApiBatchCallResult>>,
+}
+
+/// Typed handle to take a call result from `SlackApiBatchResults`
+pub struct SlackApiBatchCall<T> {
+    index: usize,
+    _result: PhantomData<fn() -> T>,
+}
+
+impl<T> SlackApiBatchCall<T> {
+    /// Position of the call in the batch
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<T> Clone for SlackApiBatchCall<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SlackApiBatchCall<T> {}
+
+impl<'b> SlackApiBatch<'b> {
+    pub const DEFAULT_PARALLELISM: usize = 4;
+
+    pub fn new() -> Self {
+        Self {
+            parallelism: Self::DEFAULT_PARALLELISM,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Max number of calls running at the same time
+    pub fn with_parallelism(self, parallelism: usize) -> Self {
+        Self {
+            parallelism: parallelism.max(1),
+            ..self
+        }
+    }
+
+    /// Adds a call (such as `session.conversations_info(&req)`) to the batch.
+    /// Calls don't start until the batch runs.
+    pub fn add<T, F>(&mut self, call: F) -> SlackApiBatchCall<T>
+    where
+        F: Future<Output = ClientResult<T>> + Send + 'b,
+        T: Send + 'static,
+    {
+        self.calls.push(
+            call.map(|result| result.map(|value| Box::new(value) as Box<dyn Any + Send>))
+                .boxed(),
+        );
+        SlackApiBatchCall {
+            index: self.calls.len() - 1,
+            _result: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Runs all calls and returns their results in order. Failed calls don't stop other calls.
+    pub async fn run(self) -> SlackApiBatchResults {
+        let results: Vec<Option<SlackApiBatchCallResult>> = futures::stream::iter(self.calls)
+            .buffered(self.parallelism)
+            .map(Some)
+            .collect()
+            .await;
+        SlackApiBatchResults { results }
+    }
+}
+
+impl Default for SlackApiBatch<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct SlackApiBatchResults {
+    results: Vec<Option<SlackApiBatchCallResult>>,
+}
+
+impl SlackApiBatchResults {
+    /// Takes the result of a call (results can be taken only once)
+    pub fn take<T>(&mut self, call: SlackApiBatchCall<T>) -> ClientResult<T>
+    where
+        T: 'static,
+    {
+        match self
+            .results
+            .get_mut(call.index)
+            .and_then(|result| result.take())
+        {
+            Some(Ok(value)) => value.downcast::<T>().map(|value| *value).map_err(|_| {
+                SlackClientError::SystemError(SlackClientSystemError::new().with_message(format!(
+                    "Unexpected result type for batch call {}",
+                    call.index
+                )))
+            }),
+            Some(Err(err)) => Err(err),
+            None => Err(SlackClientError::SystemError(
+                SlackClientSystemError::new().with_message(format!(
+                    "No result for batch call {} (already taken?)",
+                    call.index
+                )),
+            )),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Errors of failed calls with the call positions
+    pub fn errors(&self) -> Vec<(usize, &SlackClientError)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| match result {
+                Some(Err(err)) => Some((index, err)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Batch of calls to run concurrently with this session:
+    ///
+    /// ```ignore
+    /// let mut batch = session.batch().with_parallelism(2);
+    /// let info = batch.add(session.conversations_info(&info_req));
+    /// let members = batch.add(session.conversations_members(&members_req));
+    /// let mut results = batch.run().await;
+    /// let info = results.take(info)?;
+    /// ```
+    pub fn batch<'b>(&self) -> SlackApiBatch<'b> {
+        SlackApiBatch::new()
+    }
+}
+
+#[test]
+fn check_batch_results_in_order() {
+    let mut batch = SlackApiBatch::new().with_parallelism(2);
+    let first = batch.add(async { Ok::<_, SlackClientError>("first".to_string()) });
+    let failed = batch.add(async {
+        Err::<u32, _>(SlackClientError::SystemError(
+            SlackClientSystemError::new().with_message("failed".into()),
+        ))
+    });
+    let last = batch.add(async { Ok(42_u32) });
+
+    let mut results = futures::executor::block_on(batch.run());
+    assert_eq!(results.len(), 3);
+    assert_eq!(results.errors().len(), 1);
+    assert_eq!(results.errors()[0].0, failed.index());
+    assert_eq!(results.take(first).unwrap(), "first");
+    assert_eq!(results.take(last).unwrap(), 42);
+    assert!(results.take(last).is_err());
+    assert!(results.take(failed).is_err());
+}
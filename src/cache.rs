@@ -0,0 +1,435 @@
+//!
+//! In-memory caches for frequently resolved Slack objects (such as users and channels)
+//! to avoid spending rate limits repeating the same lookups.
+//!
+
+use crate::api::*;
+use crate::events::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackApiCacheConfig {
+    #[default = "SlackApiCacheConfig::DEFAULT_TTL"]
+    pub ttl: Duration,
+    #[default = "SlackApiCacheConfig::DEFAULT_MAX_ENTRIES"]
+    pub max_entries: usize,
+}
+
+impl SlackApiCacheConfig {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+    pub const DEFAULT_MAX_ENTRIES: usize = 10000;
+}
+
+/// Cached objects are stored per workspace of the session token (if known),
+/// since they may look differently for different workspaces (such as channel membership).
+pub type SlackApiCacheKey<K> = (Option<SlackTeamId>, K);
+
+#[derive(Debug)]
+pub struct SlackApiCache {
+    pub users: SlackApiCacheStorage<SlackApiCacheKey<SlackUserId>, SlackUser>,
+    pub channels: SlackApiCacheStorage<SlackApiCacheKey<SlackChannelId>, SlackChannelInfo>,
+    pub usergroup_members:
+        SlackApiCacheStorage<SlackApiCacheKey<SlackUserGroupId>, Vec<SlackUserId>>,
+}
+
+impl SlackApiCache {
+    pub fn new(config: SlackApiCacheConfig) -> Self {
+        Self {
+            users: SlackApiCacheStorage::new(config.clone()),
+            channels: SlackApiCacheStorage::new(config.clone()),
+            usergroup_members: SlackApiCacheStorage::new(config),
+        }
+    }
+
+    /// Invalidates cached objects changed by the specified event (in all workspaces).
+    pub fn invalidate_by_event(&self, event: &SlackEventCallbackBody) {
+        match event {
+            SlackEventCallbackBody::UserChange(user_change) => {
+                trace!("Invalidating cached user: {}", user_change.user.id);
+                self.users
+                    .invalidate_matching(|(_, user_id)| user_id == &user_change.user.id);
+            }
+            SlackEventCallbackBody::ChannelRename(SlackChannelRenameEvent {
+                channel: SlackChannelInfo { id: channel, .. },
+            })
+            | SlackEventCallbackBody::ChannelArchive(SlackChannelArchiveEvent {
+                channel, ..
+            })
+            | SlackEventCallbackBody::ChannelUnarchive(SlackChannelUnarchiveEvent {
+                channel,
+                ..
+            })
+            | SlackEventCallbackBody::ChannelDeleted(SlackChannelDeletedEvent { channel }) => {
+                trace!("Invalidating cached channel: {}", channel);
+                self.channels
+                    .invalidate_matching(|(_, channel_id)| channel_id == channel);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn invalidate_by_push_event(&self, event: &SlackPushEvent) {
+        if let SlackPushEvent::EventCallback(callback) = event {
+            self.invalidate_by_event(&callback.event);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.users.clear();
+        self.channels.clear();
+        self.usergroup_members.clear();
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SlackApiCacheEntry<V> {
+    value: V,
+    created: Instant,
+}
+
+#[derive(Debug)]
+pub struct SlackApiCacheStorage<K, V> {
+    config: SlackApiCacheConfig,
+    entries: Mutex<HashMap<K, SlackApiCacheEntry<V>>>,
+}
+
+impl<K, V> SlackApiCacheStorage<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub fn new(config: SlackApiCacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some(entry) if entry.created.elapsed() < self.config.ttl => Some(entry.value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn put(&self, key: K, value: V) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.config.max_entries && !entries.contains_key(&key) {
+            let ttl = self.config.ttl;
+            entries.retain(|_, entry| entry.created.elapsed() < ttl);
+
+            if entries.len() >= self.config.max_entries {
+                if let Some(oldest_key) = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.created)
+                    .map(|(key, _)| key.clone())
+                {
+                    entries.remove(&oldest_key);
+                }
+            }
+        }
+
+        entries.insert(
+            key,
+            SlackApiCacheEntry {
+                value,
+                created: Instant::now(),
+            },
+        );
+    }
+
+    pub fn invalidate(&self, key: &K) {
+        self.entries.lock().unwrap().remove(key);
+    }
+
+    pub fn invalidate_matching<F>(&self, predicate: F)
+    where
+        F: Fn(&K) -> bool,
+    {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|key, _| !predicate(key));
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    pub fn with_api_cache(self, config: SlackApiCacheConfig) -> Self {
+        Self {
+            api_cache: Some(Arc::new(SlackApiCache::new(config))),
+            ..self
+        }
+    }
+}
+
+/// Session methods using the client cache (if configured with `SlackClient::with_api_cache`).
+/// Without a configured cache all calls go directly to Slack Web API.
+pub struct SlackClientCachedSession<'s, 'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    session: &'s SlackClientSession<'a, SCHC>,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    pub fn cached(&self) -> SlackClientCachedSession<'_, 'a, SCHC> {
+        SlackClientCachedSession { session: self }
+    }
+}
+
+impl<'s, 'a, SCHC> SlackClientCachedSession<'s, 'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    fn api_cache(&self) -> Option<&SlackApiCache> {
+        self.session.http_session_api.client.api_cache.as_deref()
+    }
+
+    fn cache_key<K: Clone>(&self, id: &K) -> SlackApiCacheKey<K> {
+        let team_id =
+            self.session
+                .team_id()
+                .or(self.session.http_session_api.token().team_id.as_ref());
+        (team_id.cloned(), id.clone())
+    }
+
+    ///
+    /// https://api.slack.com/methods/users.info
+    ///
+    pub async fn users_info(
+        &self,
+        req: &SlackApiUsersInfoRequest,
+    ) -> ClientResult<SlackApiUsersInfoResponse> {
+        match self.api_cache() {
+            Some(cache) => {
+                // Cached users without a locale can't be used when a locale was requested
+                let key = self.cache_key(&req.user);
+                let cached_user = cache
+                    .users
+                    .get(&key)
+                    .filter(|user| !req.include_locale.unwrap_or(false) || user.locale.is_some());
+                match cached_user {
+                    Some(user) => Ok(SlackApiUsersInfoResponse::new(user)),
+                    None => {
+                        let resp = self.session.users_info(req).await?;
+                        cache.users.put(key, resp.user.clone());
+                        Ok(resp)
+                    }
+                }
+            }
+            None => self.session.users_info(req).await,
+        }
+    }
+
+    ///
+    /// https://api.slack.com/methods/conversations.info
+    ///
+    pub async fn conversations_info(
+        &self,
+        req: &SlackApiConversationsInfoRequest,
+    ) -> ClientResult<SlackApiConversationsInfoResponse> {
+        match self.api_cache() {
+            Some(cache) => {
+                let key = self.cache_key(&req.channel);
+                let cached_channel = cache.channels.get(&key).filter(|channel| {
+                    (!req.include_locale.unwrap_or(false) || channel.locale.is_some())
+                        && (!req.include_num_members.unwrap_or(false)
+                            || channel.num_members.is_some())
+                });
+                match cached_channel {
+                    Some(channel) => Ok(SlackApiConversationsInfoResponse::new(channel)),
+                    None => {
+                        let resp = self.session.conversations_info(req).await?;
+                        cache.channels.put(key, resp.channel.clone());
+                        Ok(resp)
+                    }
+                }
+            }
+            None => self.session.conversations_info(req).await,
+        }
+    }
+
+    ///
+    /// https://api.slack.com/methods/usergroups.users.list
+    ///
+    pub async fn usergroups_users_list(
+        &self,
+        req: &SlackApiUserGroupsUsersListRequest,
+    ) -> ClientResult<SlackApiUserGroupsUsersListResponse> {
+        // Only active members are cached
+        match self
+            .api_cache()
+            .filter(|_| !req.include_disabled.unwrap_or(false) && req.team_id.is_none())
+        {
+            Some(cache) => {
+                let key = self.cache_key(&req.usergroup);
+                match cache.usergroup_members.get(&key) {
+                    Some(users) => Ok(SlackApiUserGroupsUsersListResponse::new(users)),
+                    None => {
+                        let resp = self.session.usergroups_users_list(req).await?;
+                        cache.usergroup_members.put(key, resp.users.clone());
+                        Ok(resp)
+                    }
+                }
+            }
+            None => self.session.usergroups_users_list(req).await,
+        }
+    }
+}
+
+#[test]
+fn check_cache_ttl_and_max_entries() {
+    let storage: SlackApiCacheStorage<String, String> =
+        SlackApiCacheStorage::new(SlackApiCacheConfig::new().with_max_entries(2));
+
+    storage.put("a".into(), "1".into());
+    storage.put("b".into(), "2".into());
+    storage.put("c".into(), "3".into());
+    assert_eq!(storage.len(), 2);
+    assert_eq!(storage.get(&"a".into()), None);
+    assert_eq!(storage.get(&"c".into()), Some("3".into()));
+
+    storage.invalidate(&"c".into());
+    assert_eq!(storage.get(&"c".into()), None);
+
+    let expired_storage: SlackApiCacheStorage<String, String> =
+        SlackApiCacheStorage::new(SlackApiCacheConfig::new().with_ttl(Duration::ZERO));
+    expired_storage.put("a".into(), "1".into());
+    assert_eq!(expired_storage.get(&"a".into()), None);
+}
+
+#[test]
+fn check_cached_users_per_team() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|call: &SlackTestCall| {
+        Ok(serde_json::json!({
+            "ok": true,
+            "user": { "id": call.param("user").unwrap_or_default() }
+        }))
+    });
+    let client = SlackClient::new(connector.clone()).with_api_cache(SlackApiCacheConfig::new());
+    let token = SlackApiToken::new("xoxb-test".into()).with_team_id("T1".into());
+    let other_team_token = SlackApiToken::new("xoxb-other".into()).with_team_id("T2".into());
+    let req = SlackApiUsersInfoRequest::new("U1".into());
+
+    futures::executor::block_on(async {
+        let session = client.open_session(&token);
+        assert_eq!(
+            session.cached().users_info(&req).await.unwrap().user.id,
+            "U1".into()
+        );
+        assert_eq!(
+            session.cached().users_info(&req).await.unwrap().user.id,
+            "U1".into()
+        );
+        assert_eq!(connector.calls_of("users.info").len(), 1);
+
+        client
+            .open_session(&other_team_token)
+            .cached()
+            .users_info(&req)
+            .await
+            .unwrap();
+        assert_eq!(connector.calls_of("users.info").len(), 2);
+
+        client.api_cache.as_ref().unwrap().invalidate_by_event(
+            &serde_json::from_value(serde_json::json!({
+                "type": "user_change",
+                "user": { "id": "U1" },
+                "event_ts": "1.1",
+                "cache_ts": 1
+            }))
+            .unwrap(),
+        );
+        assert!(client.api_cache.as_ref().unwrap().users.is_empty());
+        session.cached().users_info(&req).await.unwrap();
+        assert_eq!(connector.calls_of("users.info").len(), 3);
+    });
+}
+
+#[test]
+fn check_cached_conversations_info() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|call: &SlackTestCall| {
+        let mut channel = serde_json::json!({
+            "id": call.param("channel").unwrap_or_default(),
+            "created": 0
+        });
+        if call.param("include_locale").as_deref() == Some("true") {
+            channel["locale"] = "en-US".into();
+        }
+        Ok(serde_json::json!({ "ok": true, "channel": channel }))
+    });
+    let client = SlackClient::new(connector.clone()).with_api_cache(SlackApiCacheConfig::new());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let req = SlackApiConversationsInfoRequest::new("C1".into());
+
+    futures::executor::block_on(async {
+        session.cached().conversations_info(&req).await.unwrap();
+        session.cached().conversations_info(&req).await.unwrap();
+        assert_eq!(connector.calls_of("conversations.info").len(), 1);
+
+        // Cached channel has no locale, so it is loaded again
+        let with_locale = req.clone().with_include_locale(true);
+        let resp = session
+            .cached()
+            .conversations_info(&with_locale)
+            .await
+            .unwrap();
+        assert_eq!(resp.channel.locale, Some("en-US".into()));
+        session
+            .cached()
+            .conversations_info(&with_locale)
+            .await
+            .unwrap();
+        assert_eq!(connector.calls_of("conversations.info").len(), 2);
+
+        client.api_cache.as_ref().unwrap().invalidate_by_event(
+            &serde_json::from_value(serde_json::json!({
+                "type": "channel_archive",
+                "channel": "C1",
+                "user": "U1"
+            }))
+            .unwrap(),
+        );
+        session.cached().conversations_info(&req).await.unwrap();
+        assert_eq!(connector.calls_of("conversations.info").len(), 3);
+    });
+}
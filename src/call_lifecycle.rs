@@ -0,0 +1,293 @@
+//!
+//! Calls integration helper: registers a call with `calls.add`, posts a message with the call block
+//! and manages the call until it ends, bundling the steps telephony apps need to perform.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::blocks::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackCallStartRequest {
+    pub channel: SlackChannelId,
+    pub call: SlackApiCallsAddRequest,
+    /// Post the call message in a thread
+    pub thread_ts: Option<SlackTs>,
+    /// Fallback text for notifications and clients without call blocks support
+    pub text: Option<String>,
+}
+
+pub struct SlackCallHandle<'s, 'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    session: &'s SlackClientSession<'a, SCHC>,
+    call: SlackCall,
+    channel: SlackChannelId,
+    message_ts: SlackTs,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Registers a call and posts a message with the call block to the channel.
+    /// If the message can't be posted, the registered call is ended.
+    pub async fn start_call(
+        &self,
+        req: &SlackCallStartRequest,
+    ) -> ClientResult<SlackCallHandle<'_, 'a, SCHC>> {
+        let call = self.calls_add(&req.call).await?.call;
+
+        let post_result = self
+            .chat_post_message(
+                &SlackApiChatPostMessageRequest::new(
+                    req.channel.clone(),
+                    SlackMessageContent::new()
+                        .opt_text(req.text.clone())
+                        .with_blocks(vec![SlackCallBlock::new(call.id.clone()).into()]),
+                )
+                .opt_thread_ts(req.thread_ts.clone()),
+            )
+            .await;
+
+        match post_result {
+            Ok(post_resp) => Ok(SlackCallHandle {
+                session: self,
+                call,
+                channel: post_resp.channel,
+                message_ts: post_resp.ts,
+            }),
+            Err(err) => {
+                if let Err(end_err) = self
+                    .calls_end(&SlackApiCallsEndRequest::new(call.id.clone()))
+                    .await
+                {
+                    warn!(
+                        "Unable to end call {} after failing to post its message: {}",
+                        call.id, end_err
+                    );
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+impl<'s, 'a, SCHC> SlackCallHandle<'s, 'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    pub fn call(&self) -> &SlackCall {
+        &self.call
+    }
+
+    pub fn channel(&self) -> &SlackChannelId {
+        &self.channel
+    }
+
+    /// Timestamp of the message with the call block
+    pub fn message_ts(&self) -> &SlackTs {
+        &self.message_ts
+    }
+
+    /// Updates the call title or join URLs. The call block is refreshed by Slack.
+    pub async fn update(&mut self, req: &SlackApiCallsUpdateRequest) -> ClientResult<&SlackCall> {
+        let req = req.clone().with_id(self.call.id.clone());
+        self.call = self.session.calls_update(&req).await?.call;
+        Ok(&self.call)
+    }
+
+    pub async fn add_participants(
+        &self,
+        users: Vec<SlackCallUser>,
+    ) -> ClientResult<SlackApiCallsParticipantsAddResponse> {
+        self.session
+            .calls_participants_add(&SlackApiCallsParticipantsAddRequest::new(
+                self.call.id.clone(),
+                users,
+            ))
+            .await
+    }
+
+    pub async fn remove_participants(
+        &self,
+        users: Vec<SlackCallUser>,
+    ) -> ClientResult<SlackApiCallsParticipantsRemoveResponse> {
+        self.session
+            .calls_participants_remove(&SlackApiCallsParticipantsRemoveRequest::new(
+                self.call.id.clone(),
+                users,
+            ))
+            .await
+    }
+
+    /// Ends the call. The duration is calculated from the call start time if it isn't specified.
+    pub async fn end(self, duration: Option<std::time::Duration>) -> ClientResult<SlackCall> {
+        let duration_secs = duration.map(|d| d.as_secs()).unwrap_or_else(|| {
+            chrono::Utc::now()
+                .signed_duration_since(self.call.date_start.0)
+                .num_seconds()
+                .max(0) as u64
+        });
+        self.session
+            .calls_end(
+                &SlackApiCallsEndRequest::new(self.call.id.clone()).with_duration(duration_secs),
+            )
+            .await?;
+
+        Ok(self
+            .session
+            .calls_info(&SlackApiCallsInfoRequest::new(self.call.id.clone()))
+            .await?
+            .call)
+    }
+}
+
+#[cfg(test)]
+fn call_test_connector() -> crate::test_connector::SlackTestConnector {
+    use crate::test_connector::*;
+
+    SlackTestConnector::new(|call: &SlackTestCall| {
+        let call_json = |title: &str| {
+            serde_json::json!({
+                "call": {
+                    "id": "R1",
+                    "date_start": 1562002086,
+                    "external_unique_id": "meeting-1",
+                    "join_url": "https://example.com/join/1",
+                    "title": title
+                }
+            })
+        };
+        match call.method.as_str() {
+            "calls.add" | "calls.info" => Ok(call_json("Standup")),
+            "calls.update" => Ok(call_json("Retro")),
+            "chat.postMessage" if call.param("channel").as_deref() == Some("CFAIL") => Err(
+                SlackClientError::ApiError(SlackClientApiError::new("channel_not_found".into())),
+            ),
+            "chat.postMessage" => Ok(serde_json::json!({
+                "channel": "C1",
+                "ts": "1.1",
+                "message": { "ts": "1.1" }
+            })),
+            _ => Ok(serde_json::json!({})),
+        }
+    })
+}
+
+#[cfg(test)]
+fn call_start_request(channel: &str) -> SlackCallStartRequest {
+    SlackCallStartRequest::new(
+        channel.into(),
+        SlackApiCallsAddRequest::new(
+            "meeting-1".into(),
+            "https://example.com/join/1".parse().unwrap(),
+        ),
+    )
+    .with_thread_ts("0.1".into())
+    .with_text("Standup call".into())
+}
+
+#[test]
+fn check_start_call() {
+    let connector = call_test_connector();
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let handle =
+        futures::executor::block_on(session.start_call(&call_start_request("C1"))).unwrap();
+    assert_eq!(handle.call().id, "R1".into());
+    assert_eq!(handle.channel(), &"C1".into());
+    assert_eq!(handle.message_ts(), &"1.1".into());
+
+    let calls = connector.calls();
+    assert_eq!(
+        calls
+            .iter()
+            .map(|call| call.method.as_str())
+            .collect::<Vec<_>>(),
+        vec!["calls.add", "chat.postMessage"]
+    );
+    let post = calls[1].body.clone().unwrap();
+    assert_eq!(
+        post["blocks"],
+        serde_json::json!([{ "type": "call", "call_id": "R1" }])
+    );
+    assert_eq!(post["text"], "Standup call");
+    assert_eq!(post["thread_ts"], "0.1");
+}
+
+#[test]
+fn check_start_call_post_failure() {
+    let connector = call_test_connector();
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let result = futures::executor::block_on(session.start_call(&call_start_request("CFAIL")));
+    assert!(matches!(result, Err(SlackClientError::ApiError(_))));
+
+    let ended = connector.calls_of("calls.end");
+    assert_eq!(ended.len(), 1);
+    assert_eq!(ended[0].param("id").as_deref(), Some("R1"));
+}
+
+#[test]
+fn check_call_handle() {
+    let connector = call_test_connector();
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    futures::executor::block_on(async {
+        let mut handle = session.start_call(&call_start_request("C1")).await.unwrap();
+        let updated = handle
+            .update(&SlackApiCallsUpdateRequest::new("".into()).with_title("Retro".into()))
+            .await
+            .unwrap();
+        assert_eq!(updated.title.as_deref(), Some("Retro"));
+        handle
+            .add_participants(vec![SlackCallUser::slack_user("U1".into())])
+            .await
+            .unwrap();
+        handle
+            .remove_participants(vec![SlackCallUser::slack_user("U1".into())])
+            .await
+            .unwrap();
+        handle
+            .end(Some(std::time::Duration::from_secs(60)))
+            .await
+            .unwrap();
+    });
+
+    for method in [
+        "calls.update",
+        "calls.participants.add",
+        "calls.participants.remove",
+        "calls.end",
+        "calls.info",
+    ] {
+        let calls = connector.calls_of(method);
+        assert_eq!(calls.len(), 1, "{method}");
+        assert_eq!(calls[0].param("id").as_deref(), Some("R1"), "{method}");
+    }
+    assert_eq!(
+        connector.calls_of("calls.participants.add")[0]
+            .body
+            .clone()
+            .unwrap()["users"],
+        serde_json::json!([{ "slack_id": "U1" }])
+    );
+    assert_eq!(
+        connector.calls_of("calls.end")[0]
+            .param("duration")
+            .as_deref(),
+        Some("60")
+    );
+}
@@ -0,0 +1,477 @@
+//!
+//! Markdown canvas content builder, converting Markdown documents
+//! into `canvases.edit` operations without dealing with the raw operations format.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackCanvasMarkdownSection {
+    /// Heading text without `#`, or `None` for content before the first heading
+    pub heading: Option<String>,
+    #[default = "1"]
+    pub level: u8,
+    pub body: String,
+}
+
+impl SlackCanvasMarkdownSection {
+    pub fn to_markdown(&self) -> String {
+        match self.heading.as_ref() {
+            Some(heading) if self.body.is_empty() => {
+                format!("{} {}\n", "#".repeat(self.level as usize), heading)
+            }
+            Some(heading) => format!(
+                "{} {}\n\n{}",
+                "#".repeat(self.level as usize),
+                heading,
+                self.body
+            ),
+            None => self.body.clone(),
+        }
+    }
+
+    pub fn to_document_content(&self) -> SlackCanvasDocumentContent {
+        SlackCanvasDocumentContent::markdown(self.to_markdown())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SlackCanvasMarkdown {
+    sections: Vec<SlackCanvasMarkdownSection>,
+}
+
+impl SlackCanvasMarkdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Splits an existing Markdown document (e.g. from docs-as-code repositories) into heading sections.
+    /// Headings inside fenced code blocks are ignored.
+    pub fn from_markdown(markdown: &str) -> Self {
+        let mut result = Self::new();
+        let mut in_code_block = false;
+
+        for line in markdown.lines() {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            match Self::parse_heading(line).filter(|_| !in_code_block) {
+                Some((level, heading)) => {
+                    result.sections.push(
+                        SlackCanvasMarkdownSection::new(String::new())
+                            .with_heading(heading.to_string())
+                            .with_level(level),
+                    );
+                }
+                None => {
+                    if result.sections.is_empty() && line.trim().is_empty() {
+                        continue;
+                    }
+                    result.push_body_line(line);
+                }
+            }
+        }
+
+        for section in result.sections.iter_mut() {
+            let trimmed_len = section.body.trim_end().len();
+            section.body.truncate(trimmed_len);
+            let trimmed_body = section.body.trim_start_matches('\n').to_string();
+            section.body = trimmed_body;
+            if !section.body.is_empty() {
+                section.body.push('\n');
+            }
+        }
+        result
+    }
+
+    pub fn heading(mut self, level: u8, text: &str) -> Self {
+        self.sections.push(
+            SlackCanvasMarkdownSection::new(String::new())
+                .with_heading(text.to_string())
+                .with_level(level.clamp(1, 3)),
+        );
+        self
+    }
+
+    pub fn paragraph(self, text: &str) -> Self {
+        self.block(text.trim_end())
+    }
+
+    pub fn bullet_list<S: AsRef<str>>(self, items: &[S]) -> Self {
+        let list = items
+            .iter()
+            .map(|item| format!("- {}", item.as_ref()))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.block(&list)
+    }
+
+    pub fn numbered_list<S: AsRef<str>>(self, items: &[S]) -> Self {
+        let list = items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| format!("{}. {}", idx + 1, item.as_ref()))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.block(&list)
+    }
+
+    pub fn code_block(self, language: Option<&str>, code: &str) -> Self {
+        let block = format!(
+            "```{}\n{}\n```",
+            language.unwrap_or_default(),
+            code.trim_end()
+        );
+        self.block(&block)
+    }
+
+    /// Appends raw Markdown to the current section
+    pub fn markdown(self, markdown: &str) -> Self {
+        self.block(markdown.trim_end())
+    }
+
+    pub fn sections(&self) -> &[SlackCanvasMarkdownSection] {
+        &self.sections
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.sections
+            .iter()
+            .map(|section| section.to_markdown())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn to_document_content(&self) -> SlackCanvasDocumentContent {
+        SlackCanvasDocumentContent::markdown(self.to_markdown())
+    }
+
+    /// Changes replacing the whole canvas content
+    pub fn to_replace_changes(&self) -> Vec<SlackCanvasChange> {
+        vec![SlackCanvasChange::replace(None, self.to_document_content())]
+    }
+
+    /// Changes appending every section to the end of a canvas
+    pub fn to_insert_changes(&self) -> Vec<SlackCanvasChange> {
+        self.sections
+            .iter()
+            .map(|section| SlackCanvasChange::insert_at_end(section.to_document_content()))
+            .collect()
+    }
+
+    fn block(mut self, block: &str) -> Self {
+        if self.sections.is_empty() {
+            self.sections
+                .push(SlackCanvasMarkdownSection::new(String::new()));
+        }
+        if let Some(section) = self.sections.last_mut() {
+            if !section.body.is_empty() {
+                section.body.push('\n');
+            }
+            section.body.push_str(block);
+            section.body.push('\n');
+        }
+        self
+    }
+
+    fn push_body_line(&mut self, line: &str) {
+        if self.sections.is_empty() {
+            self.sections
+                .push(SlackCanvasMarkdownSection::new(String::new()));
+        }
+        if let Some(section) = self.sections.last_mut() {
+            section.body.push_str(line);
+            section.body.push('\n');
+        }
+    }
+
+    fn parse_heading(line: &str) -> Option<(u8, &str)> {
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+            // Canvases support only three heading levels
+            Some(((level as u8).min(3), line[level..].trim()))
+        } else {
+            None
+        }
+    }
+}
+
+/// A top-level canvas section, read from the canvas HTML content
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackCanvasContentSection {
+    pub id: SlackCanvasSectionId,
+    /// Heading text for heading sections
+    pub heading: Option<String>,
+}
+
+impl SlackCanvasContentSection {
+    /// Parses the canvas HTML content (downloaded using the canvas file URL),
+    /// where section elements have section IDs as `id` attributes. Nested sections are skipped.
+    pub fn parse_html(html: &str) -> Vec<Self> {
+        let mut result = Vec::new();
+        let mut rest = html;
+
+        while let Some(tag_start) = rest.find('<') {
+            rest = &rest[tag_start + 1..];
+            let Some(tag_end) = rest.find('>') else {
+                break;
+            };
+            let tag = &rest[..tag_end];
+            rest = &rest[tag_end + 1..];
+
+            let name = tag
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+                .to_ascii_lowercase();
+            if let Some(id) = Self::attribute(tag, "id").filter(|_| !name.is_empty()) {
+                let (inner, after) = Self::split_element(rest, &name);
+                let heading =
+                    matches!(name.as_str(), "h1" | "h2" | "h3").then(|| Self::text(inner));
+                result.push(Self::new(id.into()).opt_heading(heading));
+                rest = after;
+            }
+        }
+        result
+    }
+
+    fn attribute<'t>(tag: &'t str, name: &str) -> Option<&'t str> {
+        ['\'', '"'].into_iter().find_map(|quote| {
+            let prefix = format!(" {name}={quote}");
+            tag.find(&prefix).and_then(|pos| {
+                let value = &tag[pos + prefix.len()..];
+                value.find(quote).map(|end| &value[..end])
+            })
+        })
+    }
+
+    /// Splits the element content from the rest of the document at its closing tag
+    fn split_element<'h>(html: &'h str, name: &str) -> (&'h str, &'h str) {
+        let close = format!("</{name}>");
+        let mut depth = 0;
+        let mut pos = 0;
+
+        while let Some(close_pos) = html[pos..].find(&close).map(|found| pos + found) {
+            let nested_open = html[pos..close_pos].match_indices('<').find(|(idx, _)| {
+                let tag = &html[pos + idx + 1..close_pos];
+                tag.get(..name.len())
+                    .is_some_and(|tag_name| tag_name.eq_ignore_ascii_case(name))
+                    && !tag[name.len()..].starts_with(|c: char| c.is_ascii_alphanumeric())
+            });
+            match nested_open {
+                Some((idx, _)) => {
+                    depth += 1;
+                    pos += idx + 1;
+                }
+                None if depth == 0 => {
+                    return (&html[..close_pos], &html[close_pos + close.len()..]);
+                }
+                None => {
+                    depth -= 1;
+                    pos = close_pos + close.len();
+                }
+            }
+        }
+        (html, "")
+    }
+
+    fn text(html: &str) -> String {
+        let mut text = String::with_capacity(html.len());
+        let mut in_tag = false;
+        for c in html.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        text.replace("&nbsp;", " ")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&")
+            .trim()
+            .to_string()
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Replaces the whole canvas content with the Markdown document
+    pub async fn canvas_replace_markdown(
+        &self,
+        canvas_id: &SlackCanvasId,
+        markdown: &SlackCanvasMarkdown,
+    ) -> ClientResult<SlackApiCanvasesEditResponse> {
+        self.canvases_edit(&SlackApiCanvasesEditRequest::new(
+            canvas_id.clone(),
+            markdown.to_replace_changes(),
+        ))
+        .await
+    }
+
+    /// Reads the canvas sections from the canvas HTML content
+    pub async fn canvas_content_sections(
+        &self,
+        canvas_id: &SlackCanvasId,
+    ) -> ClientResult<Vec<SlackCanvasContentSection>> {
+        let file = self
+            .files_info(&SlackApiFilesInfoRequest::new(
+                canvas_id.value().clone().into(),
+            ))
+            .await?
+            .file;
+        let content_url = file
+            .url_private_download
+            .or(file.url_private)
+            .ok_or_else(|| {
+                SlackClientError::SystemError(
+                    SlackClientSystemError::new()
+                        .with_message(format!("Canvas {} has no content URL", canvas_id.value())),
+                )
+            })?;
+        let content = self.files_download(&content_url).await?;
+        Ok(SlackCanvasContentSection::parse_html(
+            &String::from_utf8_lossy(&content),
+        ))
+    }
+
+    /// Replaces canvas sections having exactly the same headings as the Markdown document sections
+    /// (with their content up to the next heading) and appends the sections not found in the canvas.
+    /// Content before the first heading replaces the content before the first canvas heading.
+    pub async fn canvas_upsert_markdown_sections(
+        &self,
+        canvas_id: &SlackCanvasId,
+        markdown: &SlackCanvasMarkdown,
+    ) -> ClientResult<SlackApiCanvasesEditResponse> {
+        let canvas_sections = self.canvas_content_sections(canvas_id).await?;
+        let mut changes = Vec::with_capacity(markdown.sections().len());
+
+        for section in markdown.sections() {
+            let found = match section.heading.as_ref() {
+                Some(heading) => canvas_sections
+                    .iter()
+                    .position(|existing| existing.heading.as_ref() == Some(heading)),
+                None => canvas_sections
+                    .first()
+                    .filter(|existing| existing.heading.is_none())
+                    .map(|_| 0),
+            };
+            match found {
+                Some(idx) => {
+                    changes.push(SlackCanvasChange::replace(
+                        Some(canvas_sections[idx].id.clone()),
+                        section.to_document_content(),
+                    ));
+                    changes.extend(
+                        canvas_sections[idx + 1..]
+                            .iter()
+                            .take_while(|existing| existing.heading.is_none())
+                            .map(|existing| SlackCanvasChange::delete(existing.id.clone())),
+                    );
+                }
+                None if section.heading.is_some() => {
+                    changes.push(SlackCanvasChange::insert_at_end(
+                        section.to_document_content(),
+                    ));
+                }
+                None => {
+                    changes.push(SlackCanvasChange::insert_at_start(
+                        section.to_document_content(),
+                    ));
+                }
+            }
+        }
+
+        self.canvases_edit(&SlackApiCanvasesEditRequest::new(
+            canvas_id.clone(),
+            changes,
+        ))
+        .await
+    }
+}
+
+#[test]
+fn check_canvas_markdown_sections() {
+    let markdown = SlackCanvasMarkdown::from_markdown(
+        "Intro\n\n# Setup\n\nRun it:\n\n```sh\n# not a heading\n```\n\n#### Deep\n- item\n",
+    );
+    let sections = markdown.sections();
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections[0].heading, None);
+    assert_eq!(sections[0].body, "Intro\n");
+    assert_eq!(sections[1].heading, Some("Setup".into()));
+    assert_eq!(sections[1].body, "Run it:\n\n```sh\n# not a heading\n```\n");
+    assert_eq!(sections[2].level, 3);
+
+    let built = SlackCanvasMarkdown::new()
+        .heading(1, "Setup")
+        .paragraph("Run it:")
+        .bullet_list(&["one", "two"]);
+    assert_eq!(built.to_markdown(), "# Setup\n\nRun it:\n\n- one\n- two\n");
+    assert_eq!(
+        SlackCanvasMarkdown::from_markdown(&built.to_markdown()),
+        built
+    );
+}
+
+#[test]
+fn check_canvas_upsert_markdown_sections() {
+    use crate::test_connector::*;
+
+    let html = "<h1 id='temp:C:AA1'>Setup (advanced)</h1>\
+        <p id='temp:C:AA2' class='line'>Old advanced</p>\
+        <h1 id='temp:C:AA3'>Setup</h1>\
+        <p id='temp:C:AA4' class='line'>Old <b>setup</b></p>\
+        <ul id='temp:C:AA5'><li id='temp:C:AA6'>Old item</li><ul><li>Nested</li></ul></ul>\
+        <h2 id='temp:C:AA7' class='line'>Usage &amp; notes</h2>";
+    let connector = SlackTestConnector::new(move |call| match call.method.as_str() {
+        "files.info" => Ok(serde_json::json!({
+            "file": {
+                "id": "F1",
+                "url_private": "https://files.slack.com/files-pri/T1-F1/canvas"
+            }
+        })),
+        "canvases.edit" => Ok(serde_json::json!({})),
+        _ => Ok(serde_json::Value::String(html.into())),
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let markdown = SlackCanvasMarkdown::from_markdown(
+        "# Setup\n\nNew setup\n\n## Usage & notes\n\nNew usage\n\n# Links\n\n- one\n",
+    );
+
+    futures::executor::block_on(session.canvas_upsert_markdown_sections(&"F1".into(), &markdown))
+        .unwrap();
+
+    let sections = markdown.sections();
+    let expected = SlackApiCanvasesEditRequest::new(
+        "F1".into(),
+        vec![
+            SlackCanvasChange::replace(
+                Some("temp:C:AA3".into()),
+                sections[0].to_document_content(),
+            ),
+            SlackCanvasChange::delete("temp:C:AA4".into()),
+            SlackCanvasChange::delete("temp:C:AA5".into()),
+            SlackCanvasChange::replace(
+                Some("temp:C:AA7".into()),
+                sections[1].to_document_content(),
+            ),
+            SlackCanvasChange::insert_at_end(sections[2].to_document_content()),
+        ],
+    );
+    let edits = connector.calls_of("canvases.edit");
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].body, Some(serde_json::to_value(expected).unwrap()));
+}
@@ -4,12 +4,18 @@ use std::sync::Arc;
 
 use crate::token::*;
 
-use crate::errors::SlackClientError;
+use crate::cache::SlackApiCache;
+use crate::endpoints::*;
+use crate::errors::*;
+use crate::message_defaults::SlackMessageDefaults;
+use crate::models::events::SlackAppRateLimitedEvent;
 use crate::models::*;
 use crate::multipart_form::FileMultipartData;
 use crate::ratectl::SlackApiMethodRateControlConfig;
+use crate::sandbox::SlackSandboxConfig;
 use futures::future::BoxFuture;
-use futures::FutureExt;
+use futures::stream::BoxStream;
+use futures::{FutureExt, StreamExt, TryFutureExt};
 use lazy_static::*;
 use rvstruct::ValueStruct;
 use tracing::*;
@@ -21,6 +27,11 @@ where
     SCHC: SlackClientHttpConnector + Send,
 {
     pub http_api: SlackClientHttpApi<SCHC>,
+    pub api_cache: Option<Arc<SlackApiCache>>,
+    /// Region-specific endpoints. If not specified, Web API method URLs are built by the connector.
+    pub endpoints: Option<SlackApiEndpoints>,
+    /// Sandbox to redirect all calls except read-only methods to
+    pub sandbox: Option<Arc<SlackSandboxConfig>>,
 }
 
 #[derive(Clone, Debug)]
@@ -46,6 +57,8 @@ where
 {
     pub client: &'a SlackClient<SCHC>,
     token: &'a SlackApiToken,
+    team_id: Option<SlackTeamId>,
+    message_defaults: Option<SlackMessageDefaults>,
     pub span: Span,
 }
 
@@ -162,9 +175,62 @@ pub trait SlackClientHttpConnector {
     where
         RS: for<'de> serde::de::Deserialize<'de> + Send + 'a + Send + 'a;
 
+    /// Posts binary content from a stream without loading it in memory.
+    /// Streams can't be replayed, so such requests aren't retried after rate limit errors.
+    fn http_post_uri_stream<'a, RS>(
+        &'a self,
+        full_uri: Url,
+        _content_type: String,
+        _content_length: u64,
+        _content: BoxStream<'static, std::io::Result<bytes::Bytes>>,
+        _context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<RS>>
+    where
+        RS: for<'de> serde::de::Deserialize<'de> + Send + 'a,
+    {
+        std::future::ready(Err(SlackClientError::SystemError(
+            SlackClientSystemError::new().with_message(format!(
+                "Streaming uploads aren't supported by this connector: {full_uri}"
+            )),
+        )))
+        .boxed()
+    }
+
+    /// Downloads binary content (such as private file URLs) using the context token.
+    fn http_get_uri_binary<'a>(
+        &'a self,
+        full_uri: Url,
+        _context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<bytes::Bytes>> {
+        std::future::ready(Err(SlackClientError::SystemError(
+            SlackClientSystemError::new().with_message(format!(
+                "Binary downloads aren't supported by this connector: {full_uri}"
+            )),
+        )))
+        .boxed()
+    }
+
+    /// Downloads binary content as a stream of chunks, so large files aren't loaded in memory.
+    /// Connectors without streaming support download the whole content as one chunk.
+    fn http_get_uri_binary_stream<'a>(
+        &'a self,
+        full_uri: Url,
+        context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<BoxStream<'a, ClientResult<bytes::Bytes>>>> {
+        self.http_get_uri_binary(full_uri, context)
+            .map_ok(|content| futures::stream::once(std::future::ready(Ok(content))).boxed())
+            .boxed()
+    }
+
     fn create_method_uri_path(&self, method_relative_uri: &str) -> ClientResult<Url> {
         Ok(SlackClientHttpApiUri::create_method_uri_path(method_relative_uri).parse()?)
     }
+
+    /// Signals that Slack throttles event deliveries to the app (`app_rate_limited` events),
+    /// so connectors with rate control can slow down API calls. Ignored by default.
+    fn app_rate_limited<'a>(&'a self, _event: &'a SlackAppRateLimitedEvent) -> BoxFuture<'a, ()> {
+        std::future::ready(()).boxed()
+    }
 }
 
 pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -182,6 +248,27 @@ pub struct SlackEnvelopeMessage {
     // Slack may return validation errors in `errors` field with `ok: false` for some methods (such as `apps.manifest.validate`.
     pub errors: Option<Vec<String>>,
     pub warnings: Option<Vec<String>>,
+    /// Comma separated warnings (such as `superfluous_charset`)
+    pub warning: Option<String>,
+}
+
+impl SlackEnvelopeMessage {
+    /// Warnings from both `warning` and `warnings` fields
+    pub fn all_warnings(&self) -> Option<Vec<String>> {
+        let warnings: Vec<String> = self
+            .warning
+            .iter()
+            .flat_map(|warning| warning.split(','))
+            .map(|warning| warning.trim().to_string())
+            .filter(|warning| !warning.is_empty())
+            .chain(self.warnings.iter().flatten().cloned())
+            .collect();
+        if warnings.is_empty() {
+            None
+        } else {
+            Some(warnings)
+        }
+    }
 }
 
 lazy_static! {
@@ -231,6 +318,17 @@ where
     pub fn new(http_connector: SCHC) -> Self {
         Self {
             http_api: SlackClientHttpApi::new(Arc::new(http_connector)),
+            api_cache: None,
+            endpoints: None,
+            sandbox: None,
+        }
+    }
+
+    /// Use region-specific endpoints (such as `SlackApiEndpoints::gov_slack()`) for Web API, OAuth and OpenID Connect calls
+    pub fn with_endpoints(self, endpoints: SlackApiEndpoints) -> Self {
+        Self {
+            endpoints: Some(endpoints),
+            ..self
         }
     }
 
@@ -242,12 +340,19 @@ where
                 .team_id
                 .as_ref()
                 .map(|team_id| team_id.value().as_str())
+                .unwrap_or_else(|| "-"),
+            "/slack/enterprise_id" = token
+                .enterprise_id
+                .as_ref()
+                .map(|enterprise_id| enterprise_id.value().as_str())
                 .unwrap_or_else(|| "-")
         );
 
         let http_session_api = SlackClientHttpSessionApi {
             client: self,
             token,
+            team_id: None,
+            message_defaults: None,
             span: http_session_span,
         };
 
@@ -264,10 +369,98 @@ where
     }
 }
 
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    pub fn endpoints(&self) -> &SlackApiEndpoints {
+        self.endpoints
+            .as_ref()
+            .unwrap_or(&SLACK_DEFAULT_API_ENDPOINTS)
+    }
+
+    /// Web API method URL using the configured endpoints or the connector otherwise
+    pub fn create_method_uri_path(&self, method_relative_uri: &str) -> ClientResult<Url> {
+        match self.endpoints.as_ref() {
+            Some(endpoints) => {
+                Ok(format!("{}/{}", endpoints.api_url, method_relative_uri).parse()?)
+            }
+            None => self
+                .http_api
+                .connector
+                .create_method_uri_path(method_relative_uri),
+        }
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// A session working in a workspace context of an Enterprise Grid organization.
+    /// The `team_id` parameter is added to every Web API request (unless it is specified explicitly),
+    /// so org-wide tokens can call workspace-level methods without specifying it for each call.
+    pub fn for_team(&self, team_id: SlackTeamId) -> SlackClientSession<'a, SCHC> {
+        let http_session_span = span!(
+            parent: &self.http_session_api.span,
+            Level::DEBUG,
+            "Slack API team session",
+            "/slack/team_id" = team_id.value().as_str()
+        );
+
+        SlackClientSession {
+            http_session_api: SlackClientHttpSessionApi {
+                client: self.http_session_api.client,
+                token: self.http_session_api.token,
+                team_id: Some(team_id),
+                message_defaults: self.http_session_api.message_defaults.clone(),
+                span: http_session_span,
+            },
+        }
+    }
+
+    /// Workspace context `team_id` of this session, if any
+    pub fn team_id(&self) -> Option<&SlackTeamId> {
+        self.http_session_api.team_context()
+    }
+
+    /// A session applying default parameters to chat messages (unless they are specified explicitly),
+    /// such as a default channel for notification services that always post to the same place.
+    pub fn with_message_defaults(
+        &self,
+        defaults: SlackMessageDefaults,
+    ) -> SlackClientSession<'a, SCHC> {
+        SlackClientSession {
+            http_session_api: SlackClientHttpSessionApi {
+                client: self.http_session_api.client,
+                token: self.http_session_api.token,
+                team_id: self.http_session_api.team_id.clone(),
+                message_defaults: Some(defaults),
+                span: self.http_session_api.span.clone(),
+            },
+        }
+    }
+
+    /// Default chat message parameters of this session, if any
+    pub fn message_defaults(&self) -> Option<&SlackMessageDefaults> {
+        self.http_session_api.message_defaults.as_ref()
+    }
+}
+
 impl<'a, SCHC> SlackClientHttpSessionApi<'a, SCHC>
 where
     SCHC: SlackClientHttpConnector + Send,
 {
+    pub(crate) fn token(&self) -> &'a SlackApiToken {
+        self.token
+    }
+
+    fn team_context(&self) -> Option<&SlackTeamId> {
+        self.team_id
+            .as_ref()
+            .or_else(|| self.token.org_team_context())
+    }
+
     pub async fn http_get_uri<RS, PT, TS>(
         &self,
         full_uri: Url,
@@ -283,6 +476,14 @@ where
             is_sensitive_url: false,
         };
 
+        // The session team context is added only to Web API methods, not to other Slack URLs
+        let full_uri = match self.client.create_method_uri_path("") {
+            Ok(api_uri) if full_uri.as_str().starts_with(api_uri.as_str()) => {
+                self.with_team_id_query_param(full_uri)
+            }
+            _ => full_uri,
+        };
+
         self.client
             .http_api
             .connector
@@ -301,6 +502,35 @@ where
         PT: std::iter::IntoIterator<Item = (&'p str, Option<TS>)> + Clone,
         TS: AsRef<str> + 'p + Send,
     {
+        if let Some(sandbox) = self.sandbox_for(method_relative_uri) {
+            if sandbox.is_dropped(method_relative_uri) {
+                return sandbox.dropped_response(method_relative_uri);
+            }
+            debug!(
+                "Routing {} to the sandbox channel {}",
+                method_relative_uri, sandbox.channel
+            );
+            let sandbox_context = SlackClientApiCallContext {
+                rate_control_params,
+                token: Some(sandbox.token.as_ref().unwrap_or(self.token)),
+                tracing_span: &self.span,
+                is_sensitive_url: false,
+            };
+            let full_uri = SlackClientHttpApiUri::create_url_with_params(
+                self.client.create_method_uri_path(method_relative_uri)?,
+                &sandbox.route_params(params),
+            )?;
+            return self
+                .client
+                .http_api
+                .connector
+                .http_get_uri(
+                    self.with_sandbox_team_id_query(full_uri, sandbox),
+                    sandbox_context,
+                )
+                .await;
+        }
+
         let context = SlackClientApiCallContext {
             rate_control_params,
             token: Some(self.token),
@@ -308,13 +538,44 @@ where
             is_sensitive_url: false,
         };
 
+        let full_uri = SlackClientHttpApiUri::create_url_with_params(
+            self.client.create_method_uri_path(method_relative_uri)?,
+            params,
+        )?;
+
         self.client
             .http_api
             .connector
-            .http_get(method_relative_uri, params, context)
+            .http_get_uri(self.with_team_id_query_param(full_uri), context)
             .await
     }
 
+    // Sandbox for all methods except read-only ones, if configured
+    fn sandbox_for(&self, method_relative_uri: &str) -> Option<&'a SlackSandboxConfig> {
+        self.client.sandbox.as_deref().filter(|sandbox| {
+            sandbox.is_routed(method_relative_uri) || sandbox.is_dropped(method_relative_uri)
+        })
+    }
+
+    // The session team context doesn't belong to a sandbox workspace token
+    fn with_sandbox_team_id_query(&self, full_uri: Url, sandbox: &SlackSandboxConfig) -> Url {
+        match sandbox.token.as_ref() {
+            Some(_) => full_uri,
+            None => self.with_team_id_query_param(full_uri),
+        }
+    }
+
+    fn with_team_id_query_param(&self, mut full_uri: Url) -> Url {
+        if let Some(team_id) = self.team_context() {
+            if !full_uri.query_pairs().any(|(k, _)| k == "team_id") {
+                full_uri
+                    .query_pairs_mut()
+                    .append_pair("team_id", team_id.value());
+            }
+        }
+        full_uri
+    }
+
     pub async fn http_post<RQ, RS>(
         &self,
         method_relative_uri: &str,
@@ -332,11 +593,84 @@ where
             is_sensitive_url: false,
         };
 
-        self.client
-            .http_api
-            .connector
-            .http_post(method_relative_uri, &request, context)
-            .await
+        let full_uri = self.client.create_method_uri_path(method_relative_uri)?;
+
+        if let Some(sandbox) = self.sandbox_for(method_relative_uri) {
+            if sandbox.is_dropped(method_relative_uri) {
+                return sandbox.dropped_response(method_relative_uri);
+            }
+            // The session team context doesn't belong to a sandbox workspace token
+            let request_json = match sandbox.token.as_ref() {
+                Some(_) => {
+                    serde_json::to_value(request).map_err(|err| map_serde_error(err, None))?
+                }
+                None => self.request_payload(request)?,
+            };
+            debug!(
+                "Routing {} to the sandbox channel {}",
+                method_relative_uri, sandbox.channel
+            );
+            let sandbox_context = SlackClientApiCallContext {
+                token: Some(sandbox.token.as_ref().unwrap_or(self.token)),
+                ..context
+            };
+            return self
+                .client
+                .http_api
+                .connector
+                .http_post_uri(
+                    full_uri,
+                    &sandbox.route_request(request_json),
+                    sandbox_context,
+                )
+                .await;
+        }
+
+        match self.team_context() {
+            Some(team_id) => {
+                let request_with_team = Self::with_team_id_param(request, team_id)?;
+                self.client
+                    .http_api
+                    .connector
+                    .http_post_uri(full_uri, &request_with_team, context)
+                    .await
+            }
+            None => {
+                self.client
+                    .http_api
+                    .connector
+                    .http_post_uri(full_uri, &request, context)
+                    .await
+            }
+        }
+    }
+
+    /// Request body as it would be sent by `http_post` (with the `team_id` of the session)
+    pub(crate) fn request_payload<RQ>(&self, request: &RQ) -> ClientResult<serde_json::Value>
+    where
+        RQ: serde::ser::Serialize,
+    {
+        match self.team_context() {
+            Some(team_id) => Self::with_team_id_param(request, team_id),
+            None => serde_json::to_value(request).map_err(|err| map_serde_error(err, None)),
+        }
+    }
+
+    fn with_team_id_param<RQ>(
+        request: &RQ,
+        team_id: &SlackTeamId,
+    ) -> ClientResult<serde_json::Value>
+    where
+        RQ: serde::ser::Serialize,
+    {
+        let mut request_json =
+            serde_json::to_value(request).map_err(|err| map_serde_error(err, None))?;
+        if let Some(fields) = request_json.as_object_mut() {
+            fields
+                .entry("team_id")
+                .or_insert_with(|| serde_json::Value::String(team_id.value().clone()));
+        }
+        Ok(request_json)
     }
 
     pub async fn http_post_uri<RQ, RS>(
@@ -375,6 +709,31 @@ where
         PT: std::iter::IntoIterator<Item = (&'p str, Option<TS>)> + Clone,
         TS: AsRef<str> + 'p + Send,
     {
+        let full_uri = self.client.create_method_uri_path(method_relative_uri)?;
+
+        if let Some(sandbox) = self.sandbox_for(method_relative_uri) {
+            if sandbox.is_dropped(method_relative_uri) {
+                return sandbox.dropped_response(method_relative_uri);
+            }
+            debug!(
+                "Routing {} to the sandbox channel {}",
+                method_relative_uri, sandbox.channel
+            );
+            let sandbox_context = SlackClientApiCallContext {
+                rate_control_params,
+                token: Some(sandbox.token.as_ref().unwrap_or(self.token)),
+                tracing_span: &self.span,
+                is_sensitive_url: false,
+            };
+            let routed_params = sandbox.route_params(params);
+            return self
+                .client
+                .http_api
+                .connector
+                .http_post_uri_multipart_form(full_uri, file, &routed_params, sandbox_context)
+                .await;
+        }
+
         let context = SlackClientApiCallContext {
             rate_control_params,
             token: Some(self.token),
@@ -385,7 +744,7 @@ where
         self.client
             .http_api
             .connector
-            .http_post_multipart_form(method_relative_uri, file, params, context)
+            .http_post_uri_multipart_form(full_uri, file, params, context)
             .await
     }
 
@@ -438,4 +797,182 @@ where
             .http_post_uri_binary(full_uri, content_type, data, context)
             .await
     }
+
+    pub async fn http_post_uri_stream<RS>(
+        &self,
+        full_uri: Url,
+        content_type: String,
+        content_length: u64,
+        content: BoxStream<'static, std::io::Result<bytes::Bytes>>,
+        rate_control_params: Option<&'a SlackApiMethodRateControlConfig>,
+    ) -> ClientResult<RS>
+    where
+        RS: for<'de> serde::de::Deserialize<'de> + Send,
+    {
+        let context = SlackClientApiCallContext {
+            rate_control_params,
+            token: Some(self.token),
+            tracing_span: &self.span,
+            is_sensitive_url: true,
+        };
+
+        self.client
+            .http_api
+            .connector
+            .http_post_uri_stream(full_uri, content_type, content_length, content, context)
+            .await
+    }
+
+    pub async fn http_get_uri_binary(&self, full_uri: Url) -> ClientResult<bytes::Bytes> {
+        let context = SlackClientApiCallContext {
+            rate_control_params: None,
+            token: Some(self.token),
+            tracing_span: &self.span,
+            is_sensitive_url: true,
+        };
+
+        self.client
+            .http_api
+            .connector
+            .http_get_uri_binary(full_uri, context)
+            .await
+    }
+
+    pub async fn http_get_uri_binary_stream(
+        &self,
+        full_uri: Url,
+    ) -> ClientResult<BoxStream<'_, ClientResult<bytes::Bytes>>> {
+        let context = SlackClientApiCallContext {
+            rate_control_params: None,
+            token: Some(self.token),
+            tracing_span: &self.span,
+            is_sensitive_url: true,
+        };
+
+        self.client
+            .http_api
+            .connector
+            .http_get_uri_binary_stream(full_uri, context)
+            .await
+    }
+}
+
+#[test]
+fn check_envelope_message_warnings() {
+    let slack_message: SlackEnvelopeMessage = serde_json::from_str(
+        r#"{ "ok": false, "error": "invalid_blocks", "warning": "superfluous_charset,missing_charset", "warnings": ["deprecated_method"] }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        slack_message.all_warnings(),
+        Some(vec![
+            "superfluous_charset".to_string(),
+            "missing_charset".to_string(),
+            "deprecated_method".to_string()
+        ])
+    );
+}
+
+#[test]
+fn check_org_token_team_id_injection() {
+    use crate::test_connector::*;
+    use serde_json::json;
+
+    let connector = SlackTestConnector::new(|_| Ok(json!({})));
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into())
+        .with_team_id("T1".into())
+        .with_enterprise_id("E1".into());
+    let session = client.open_session(&token);
+
+    futures::executor::block_on(async {
+        let api = &session.http_session_api;
+        api.http_post::<_, serde_json::Value>(
+            "chat.postMessage",
+            &json!({ "channel": "C1" }),
+            None,
+        )
+        .await
+        .unwrap();
+        api.http_post::<_, serde_json::Value>(
+            "usergroups.users.update",
+            &json!({ "usergroup": "S1", "team_id": "T2" }),
+            None,
+        )
+        .await
+        .unwrap();
+        api.http_get::<serde_json::Value, _, _>("files.list", &vec![("count", Some("10"))], None)
+            .await
+            .unwrap();
+        api.http_get::<serde_json::Value, _, _>("files.list", &vec![("team_id", Some("T2"))], None)
+            .await
+            .unwrap();
+    });
+
+    let calls = connector.calls();
+    assert_eq!(
+        calls[0].body,
+        Some(json!({ "channel": "C1", "team_id": "T1" }))
+    );
+    assert_eq!(calls[1].param("team_id"), Some("T2".into()));
+    assert_eq!(calls[2].query_param("team_id"), Some("T1".into()));
+    assert_eq!(calls[2].query_param("count"), Some("10".into()));
+    assert_eq!(
+        calls[3]
+            .url
+            .query_pairs()
+            .filter(|(key, _)| key == "team_id")
+            .count(),
+        1
+    );
+    assert_eq!(calls[3].query_param("team_id"), Some("T2".into()));
+}
+
+#[test]
+fn check_team_session_team_id_injection() {
+    use crate::test_connector::*;
+    use serde_json::json;
+
+    let connector = SlackTestConnector::new(|_| Ok(json!({})));
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let team_session = session.for_team("T1".into());
+    assert_eq!(session.team_id(), None);
+    assert_eq!(team_session.team_id(), Some(&"T1".into()));
+
+    futures::executor::block_on(async {
+        let api = &team_session.http_session_api;
+        api.http_get_uri::<serde_json::Value, (), ()>(
+            "https://slack.com/api/conversations.list?limit=10"
+                .parse()
+                .unwrap(),
+            None,
+        )
+        .await
+        .unwrap();
+        api.http_get_uri::<serde_json::Value, (), ()>(
+            "https://files.slack.com/files-pri/T1-F1/a.txt"
+                .parse()
+                .unwrap(),
+            None,
+        )
+        .await
+        .unwrap();
+        api.http_post::<_, serde_json::Value>("chat.postMessage", &json!({}), None)
+            .await
+            .unwrap();
+        session
+            .http_session_api
+            .http_post::<_, serde_json::Value>("chat.postMessage", &json!({}), None)
+            .await
+            .unwrap();
+    });
+
+    let calls = connector.calls();
+    assert_eq!(calls[0].query_param("team_id"), Some("T1".into()));
+    assert_eq!(calls[0].query_param("limit"), Some("10".into()));
+    assert_eq!(calls[1].query_param("team_id"), None);
+    assert_eq!(calls[2].body, Some(json!({ "team_id": "T1" })));
+    assert_eq!(calls[3].body, Some(json!({})));
 }
@@ -0,0 +1,318 @@
+//!
+//! Dry-run mode for chat, views and files calls: requests are validated locally
+//! and returned as would-be payloads instead of being sent to Slack
+//! (e.g. for payload snapshot tests and safe CI runs).
+//! The validation is shallow: it checks required fields and the documented size limits only,
+//! so a request passing it can still be rejected by Slack (e.g. for unknown channels,
+//! missing scopes or invalid block contents).
+//!
+
+use crate::api::*;
+use crate::blocks::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rvstruct::ValueStruct;
+use serde::Serialize;
+
+/// The request a method would send
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct SlackDryRunRequest {
+    pub method: String,
+    pub payload: serde_json::Value,
+}
+
+const SLACK_VIEW_MAX_BLOCKS: usize = 100;
+const SLACK_VIEW_MAX_PRIVATE_METADATA_LENGTH: usize = 3000;
+const SLACK_VIEW_MAX_CALLBACK_ID_LENGTH: usize = 255;
+
+/// Session calls in the dry-run mode, created with `session.dry_run()`
+pub struct SlackClientDryRunSession<'s, 'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    session: &'s SlackClientSession<'a, SCHC>,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    pub fn dry_run<'s>(&'s self) -> SlackClientDryRunSession<'s, 'a, SCHC> {
+        SlackClientDryRunSession { session: self }
+    }
+}
+
+impl<SCHC> SlackClientDryRunSession<'_, '_, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    pub fn chat_post_message(
+        &self,
+        req: &SlackApiChatPostMessageRequest,
+    ) -> ClientResult<SlackDryRunRequest> {
+        let req = self
+            .session
+            .message_defaults()
+            .map(|defaults| defaults.apply_to_post_message(req))
+            .unwrap_or_else(|| req.clone());
+        let mut violations = Vec::new();
+        check_channel(&req.channel, &mut violations);
+        check_message_content(&req.content, &mut violations);
+        self.request("chat.postMessage", &req, violations)
+    }
+
+    pub fn chat_post_ephemeral(
+        &self,
+        req: &SlackApiChatPostEphemeralRequest,
+    ) -> ClientResult<SlackDryRunRequest> {
+        let req = self
+            .session
+            .message_defaults()
+            .map(|defaults| defaults.apply_to_post_ephemeral(req))
+            .unwrap_or_else(|| req.clone());
+        let mut violations = Vec::new();
+        check_channel(&req.channel, &mut violations);
+        if req.user.value().is_empty() {
+            violations.push("user is empty".to_string());
+        }
+        check_message_content(&req.content, &mut violations);
+        self.request("chat.postEphemeral", &req, violations)
+    }
+
+    pub fn chat_schedule_message(
+        &self,
+        req: &SlackApiChatScheduleMessageRequest,
+    ) -> ClientResult<SlackDryRunRequest> {
+        let req = self
+            .session
+            .message_defaults()
+            .map(|defaults| defaults.apply_to_schedule_message(req))
+            .unwrap_or_else(|| req.clone());
+        let mut violations = Vec::new();
+        check_channel(&req.channel, &mut violations);
+        check_message_content(&req.content, &mut violations);
+        self.request("chat.scheduleMessage", &req, violations)
+    }
+
+    pub fn chat_update(&self, req: &SlackApiChatUpdateRequest) -> ClientResult<SlackDryRunRequest> {
+        let mut violations = Vec::new();
+        check_channel(&req.channel, &mut violations);
+        if req.ts.value().is_empty() {
+            violations.push("ts is empty".to_string());
+        }
+        check_message_content(&req.content, &mut violations);
+        self.request("chat.update", req, violations)
+    }
+
+    pub fn views_open(&self, req: &SlackApiViewsOpenRequest) -> ClientResult<SlackDryRunRequest> {
+        let mut violations = Vec::new();
+        check_view(&req.view, &mut violations);
+        self.request("views.open", req, violations)
+    }
+
+    pub fn views_publish(
+        &self,
+        req: &SlackApiViewsPublishRequest,
+    ) -> ClientResult<SlackDryRunRequest> {
+        let mut violations = Vec::new();
+        check_view(&req.view, &mut violations);
+        self.request("views.publish", req, violations)
+    }
+
+    pub fn views_push(&self, req: &SlackApiViewsPushRequest) -> ClientResult<SlackDryRunRequest> {
+        let mut violations = Vec::new();
+        check_view(&req.view, &mut violations);
+        self.request("views.push", req, violations)
+    }
+
+    pub fn views_update(
+        &self,
+        req: &SlackApiViewsUpdateRequest,
+    ) -> ClientResult<SlackDryRunRequest> {
+        let mut violations = Vec::new();
+        if req.view_id.is_none() && req.external_id.is_none() {
+            violations.push("view_id or external_id is required".to_string());
+        }
+        check_view(&req.view, &mut violations);
+        self.request("views.update", req, violations)
+    }
+
+    pub fn files_get_upload_url_external(
+        &self,
+        req: &SlackApiFilesGetUploadUrlExternalRequest,
+    ) -> ClientResult<SlackDryRunRequest> {
+        let mut violations = Vec::new();
+        if req.filename.is_empty() {
+            violations.push("filename is empty".to_string());
+        }
+        if req.length == 0 {
+            violations.push("length is 0".to_string());
+        }
+        self.request("files.getUploadURLExternal", req, violations)
+    }
+
+    pub fn files_complete_upload_external(
+        &self,
+        req: &SlackApiFilesCompleteUploadExternalRequest,
+    ) -> ClientResult<SlackDryRunRequest> {
+        let mut violations = Vec::new();
+        if req.files.is_empty() {
+            violations.push("files are empty".to_string());
+        }
+        if req.thread_ts.is_some() && req.channel_id.is_none() {
+            violations.push("thread_ts requires channel_id".to_string());
+        }
+        self.request("files.completeUploadExternal", req, violations)
+    }
+
+    fn request<RQ>(
+        &self,
+        method: &str,
+        req: &RQ,
+        violations: Vec<String>,
+    ) -> ClientResult<SlackDryRunRequest>
+    where
+        RQ: Serialize,
+    {
+        if !violations.is_empty() {
+            return Err(SlackClientError::SystemError(
+                SlackClientSystemError::new().with_message(format!(
+                    "Dry run of {method} failed validation: {}",
+                    violations.join("; ")
+                )),
+            ));
+        }
+
+        Ok(SlackDryRunRequest {
+            method: method.to_string(),
+            payload: self.session.http_session_api.request_payload(req)?,
+        })
+    }
+}
+
+fn check_channel(channel: &SlackChannelId, violations: &mut Vec<String>) {
+    if channel.value().is_empty() {
+        violations.push("channel is empty".to_string());
+    }
+}
+
+fn check_message_content(content: &SlackMessageContent, violations: &mut Vec<String>) {
+    if content.text.is_none() && content.blocks.is_none() && content.attachments.is_none() {
+        violations.push("text, blocks or attachments are required".to_string());
+    }
+    if let Some(text_length) = content.text.as_ref().map(|text| text.chars().count()) {
+        let max_text_length = SlackMessageSplitConfig::DEFAULT_MAX_TEXT_LENGTH;
+        if text_length > max_text_length {
+            violations.push(format!(
+                "text is too long: {text_length} > {max_text_length}"
+            ));
+        }
+    }
+    check_blocks(
+        content.blocks.as_deref().unwrap_or_default(),
+        SlackMessageSplitConfig::DEFAULT_MAX_BLOCKS,
+        violations,
+    );
+}
+
+fn check_view(view: &SlackView, violations: &mut Vec<String>) {
+    let (blocks, private_metadata, callback_id) = match view {
+        SlackView::Home(home) => (&home.blocks, &home.private_metadata, &home.callback_id),
+        SlackView::Modal(modal) => (&modal.blocks, &modal.private_metadata, &modal.callback_id),
+    };
+    check_blocks(blocks, SLACK_VIEW_MAX_BLOCKS, violations);
+    if let Some(length) = private_metadata.as_ref().map(|metadata| metadata.len()) {
+        if length > SLACK_VIEW_MAX_PRIVATE_METADATA_LENGTH {
+            violations.push(format!(
+                "private_metadata is too long: {length} > {SLACK_VIEW_MAX_PRIVATE_METADATA_LENGTH}"
+            ));
+        }
+    }
+    if let Some(length) = callback_id.as_ref().map(|id| id.value().len()) {
+        if length > SLACK_VIEW_MAX_CALLBACK_ID_LENGTH {
+            violations.push(format!(
+                "callback_id is too long: {length} > {SLACK_VIEW_MAX_CALLBACK_ID_LENGTH}"
+            ));
+        }
+    }
+}
+
+fn check_blocks(blocks: &[SlackBlock], max_blocks: usize, violations: &mut Vec<String>) {
+    if blocks.len() > max_blocks {
+        violations.push(format!("too many blocks: {} > {max_blocks}", blocks.len()));
+    }
+}
+
+#[test]
+fn check_dry_run_validation() {
+    let mut violations = Vec::new();
+    check_message_content(
+        &SlackMessageContent::new().with_blocks(vec![SlackDividerBlock::new().into(); 51]),
+        &mut violations,
+    );
+    check_channel(&"".into(), &mut violations);
+    assert_eq!(
+        violations,
+        vec!["too many blocks: 51 > 50", "channel is empty"]
+    );
+
+    let mut text_violations = Vec::new();
+    check_message_content(
+        &SlackMessageContent::new().with_text("a".repeat(4001)),
+        &mut text_violations,
+    );
+    assert_eq!(text_violations, vec!["text is too long: 4001 > 4000"]);
+
+    let mut valid_violations = Vec::new();
+    check_message_content(
+        &SlackMessageContent::new().with_text("Hey".into()),
+        &mut valid_violations,
+    );
+    assert!(valid_violations.is_empty());
+}
+
+#[test]
+fn check_dry_run_chat_post_message() {
+    use crate::message_defaults::*;
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|_| Ok(serde_json::json!({})));
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client
+        .open_session(&token)
+        .for_team("T1".into())
+        .with_message_defaults(
+            SlackMessageDefaults::new()
+                .with_channel("C1".into())
+                .with_username("Notifier".into()),
+        );
+
+    let dry_run = session
+        .dry_run()
+        .chat_post_message(&SlackApiChatPostMessageRequest::new(
+            "".into(),
+            SlackMessageContent::new().with_text("Hey".into()),
+        ))
+        .unwrap();
+
+    assert_eq!(dry_run.method, "chat.postMessage");
+    assert_eq!(
+        dry_run.payload,
+        serde_json::json!({
+            "channel": "C1",
+            "text": "Hey",
+            "username": "Notifier",
+            "team_id": "T1"
+        })
+    );
+    assert!(session
+        .dry_run()
+        .chat_post_message(&SlackApiChatPostMessageRequest::new(
+            "C1".into(),
+            SlackMessageContent::new()
+        ))
+        .is_err());
+    assert!(connector.calls().is_empty());
+}
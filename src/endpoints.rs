@@ -0,0 +1,69 @@
+//!
+//! Slack endpoints configuration for region-specific deployments (such as GovSlack).
+//!
+
+use lazy_static::*;
+use rsb_derive::Builder;
+
+/// Base URLs used by the client for Web API, OAuth and OpenID Connect calls.
+///
+/// Socket Mode WebSocket URLs aren't configured here, because Slack returns them
+/// from `apps.connections.open`, which follows the configured `api_url`.
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackApiEndpoints {
+    pub api_url: String,
+    pub oauth_authorize_url: String,
+    pub openid_authorize_url: String,
+    pub openid_keys_url: String,
+    pub openid_issuer: String,
+    pub audit_logs_url: String,
+}
+
+impl SlackApiEndpoints {
+    pub const SLACK_COM_DOMAIN: &'static str = "slack.com";
+    pub const GOV_SLACK_DOMAIN: &'static str = "slack-gov.com";
+
+    /// Endpoints for a Slack deployment domain (`slack.com`, `slack-gov.com`, etc.)
+    pub fn for_domain(domain: &str) -> Self {
+        Self::new(
+            format!("https://{domain}/api"),
+            format!("https://{domain}/oauth/v2/authorize"),
+            format!("https://{domain}/openid/connect/authorize"),
+            format!("https://{domain}/openid/connect/keys"),
+            format!("https://{domain}"),
+            format!("https://api.{domain}/audit/v1/logs"),
+        )
+    }
+
+    pub fn slack_com() -> Self {
+        Self::for_domain(Self::SLACK_COM_DOMAIN)
+    }
+
+    pub fn gov_slack() -> Self {
+        Self::for_domain(Self::GOV_SLACK_DOMAIN)
+    }
+}
+
+impl Default for SlackApiEndpoints {
+    fn default() -> Self {
+        Self::slack_com()
+    }
+}
+
+lazy_static! {
+    pub static ref SLACK_DEFAULT_API_ENDPOINTS: SlackApiEndpoints = SlackApiEndpoints::slack_com();
+}
+
+#[test]
+fn check_gov_slack_endpoints() {
+    let endpoints = SlackApiEndpoints::gov_slack();
+    assert_eq!(endpoints.api_url, "https://slack-gov.com/api");
+    assert_eq!(
+        endpoints.audit_logs_url,
+        "https://api.slack-gov.com/audit/v1/logs"
+    );
+    assert_eq!(
+        SlackApiEndpoints::default().oauth_authorize_url,
+        "https://slack.com/oauth/v2/authorize"
+    );
+}
@@ -0,0 +1,140 @@
+//!
+//! Ephemeral messages with a fallback to direct messages
+//! when `chat.postEphemeral` can't deliver a message to a user.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SlackEphemeralOrDmPath {
+    Ephemeral,
+    DirectMessage,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackEphemeralOrDmResponse {
+    pub path: SlackEphemeralOrDmPath,
+    /// The channel the message was posted to (the DM channel for the fallback path)
+    pub channel: SlackChannelId,
+    pub ts: Option<SlackTs>,
+    /// The API error code of `chat.postEphemeral` caused the fallback
+    pub ephemeral_error_code: Option<String>,
+}
+
+// Errors meaning the user can't see the ephemeral message in the channel.
+// Content errors (such as `msg_too_long`) would fail for DMs as well, so they aren't included.
+const SLACK_EPHEMERAL_FALLBACK_ERROR_CODES: &[&str] = &[
+    "user_not_in_channel",
+    "not_in_channel",
+    "channel_not_found",
+    "is_archived",
+    "messages_tab_disabled",
+];
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Posts an ephemeral message and, if the user isn't able to receive it in the channel,
+    /// opens a DM with the user
+    /// via `conversations.open` and posts the same content there.
+    /// `thread_ts` is not used for DMs.
+    pub async fn chat_post_ephemeral_or_dm(
+        &self,
+        req: &SlackApiChatPostEphemeralRequest,
+    ) -> ClientResult<SlackEphemeralOrDmResponse> {
+        match self.chat_post_ephemeral(req).await {
+            Ok(resp) => Ok(SlackEphemeralOrDmResponse {
+                path: SlackEphemeralOrDmPath::Ephemeral,
+                channel: req.channel.clone(),
+                ts: resp.message_ts,
+                ephemeral_error_code: None,
+            }),
+            Err(SlackClientError::ApiError(api_err))
+                if SLACK_EPHEMERAL_FALLBACK_ERROR_CODES.contains(&api_err.code.as_str()) =>
+            {
+                debug!(
+                    "Unable to post ephemeral message to {} in {}: {}. Falling back to DM",
+                    req.user, req.channel, api_err.code
+                );
+                let dm = self
+                    .conversations_open(
+                        &SlackApiConversationsOpenRequest::new().with_users(vec![req.user.clone()]),
+                    )
+                    .await?;
+
+                let resp = self
+                    .chat_post_message(
+                        &SlackApiChatPostMessageRequest::new(dm.channel.id, req.content.clone())
+                            .opt_as_user(req.as_user)
+                            .opt_icon_emoji(req.icon_emoji.clone())
+                            .opt_icon_url(req.icon_url.clone())
+                            .opt_link_names(req.link_names)
+                            .opt_parse(req.parse.clone())
+                            .opt_username(req.username.clone()),
+                    )
+                    .await?;
+
+                Ok(SlackEphemeralOrDmResponse {
+                    path: SlackEphemeralOrDmPath::DirectMessage,
+                    channel: resp.channel,
+                    ts: Some(resp.ts),
+                    ephemeral_error_code: Some(api_err.code),
+                })
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[test]
+fn check_ephemeral_or_dm_fallback() {
+    use crate::test_connector::*;
+    use serde_json::json;
+
+    let connector = SlackTestConnector::new(|call| match call.method.as_str() {
+        "chat.postEphemeral" => Err(SlackClientError::ApiError(SlackClientApiError::new(
+            call.param("text").unwrap_or_default(),
+        ))),
+        "conversations.open" => Ok(json!({ "channel": { "id": "D1" } })),
+        _ => Ok(json!({ "channel": "D1", "ts": "1.000001", "message": { "ts": "1.000001" } })),
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    // The test connector fails ephemeral messages with their text as the error code
+    let ephemeral = |code: &str| {
+        SlackApiChatPostEphemeralRequest::new(
+            "C1".into(),
+            "U1".into(),
+            SlackMessageContent::new().with_text(code.into()),
+        )
+    };
+
+    futures::executor::block_on(async {
+        let resp = session
+            .chat_post_ephemeral_or_dm(&ephemeral("user_not_in_channel"))
+            .await
+            .unwrap();
+        assert_eq!(resp.path, SlackEphemeralOrDmPath::DirectMessage);
+        assert_eq!(resp.channel, "D1".into());
+        assert_eq!(
+            resp.ephemeral_error_code,
+            Some("user_not_in_channel".into())
+        );
+
+        match session
+            .chat_post_ephemeral_or_dm(&ephemeral("msg_too_long"))
+            .await
+        {
+            Err(SlackClientError::ApiError(api_err)) => assert_eq!(api_err.code, "msg_too_long"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    });
+
+    assert_eq!(connector.calls_of("chat.postMessage").len(), 1);
+}
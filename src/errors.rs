@@ -15,9 +15,22 @@ pub enum SlackClientError {
     ProtocolError(SlackClientProtocolError),
     SocketModeProtocolError(SlackClientSocketModeProtocolError),
     RateLimitError(SlackRateLimitError),
+    CircuitBreakerOpenError(SlackClientCircuitBreakerOpenError),
 }
 
 impl SlackClientError {
+    /// Errors that may disappear when the same request is repeated later
+    /// (rate limits, network issues, Slack server errors and open circuit breakers).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            SlackClientError::RateLimitError(_)
+            | SlackClientError::HttpProtocolError(_)
+            | SlackClientError::CircuitBreakerOpenError(_) => true,
+            SlackClientError::HttpError(http_err) => http_err.status_code.is_server_error(),
+            _ => false,
+        }
+    }
+
     fn option_to_string<T: ToString>(value: &Option<T>) -> String {
         value
             .as_ref()
@@ -36,6 +49,7 @@ impl Display for SlackClientError {
             SlackClientError::SocketModeProtocolError(ref err) => err.fmt(f),
             SlackClientError::SystemError(ref err) => err.fmt(f),
             SlackClientError::RateLimitError(ref err) => err.fmt(f),
+            SlackClientError::CircuitBreakerOpenError(ref err) => err.fmt(f),
         }
     }
 }
@@ -51,6 +65,7 @@ impl Error for SlackClientError {
             SlackClientError::SocketModeProtocolError(ref err) => Some(err),
             SlackClientError::SystemError(ref err) => Some(err),
             SlackClientError::RateLimitError(ref err) => Some(err),
+            SlackClientError::CircuitBreakerOpenError(ref err) => Some(err),
         }
     }
 }
@@ -192,6 +207,24 @@ impl Display for SlackRateLimitError {
 
 impl Error for SlackRateLimitError {}
 
+/// The request wasn't sent, because the circuit breaker is open
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackClientCircuitBreakerOpenError {
+    pub uri_path: String,
+}
+
+impl Display for SlackClientCircuitBreakerOpenError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Slack API circuit breaker is open. Request isn't sent: {}",
+            self.uri_path
+        )
+    }
+}
+
+impl Error for SlackClientCircuitBreakerOpenError {}
+
 impl From<url::ParseError> for SlackClientError {
     fn from(url_parse_error: ParseError) -> Self {
         SlackClientError::HttpProtocolError(
@@ -211,3 +244,11 @@ pub fn map_serde_error(err: serde_json::Error, tried_to_parse: Option<&str>) ->
         SlackClientProtocolError::new(err).opt_json_body(tried_to_parse.map(|s| s.to_string())),
     )
 }
+
+/// Decodes Slack API JSON bodies (with serde_json)
+pub fn decode_json_body<T>(body: &str) -> Result<T, SlackClientError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_str(body).map_err(|err| map_serde_error(err, Some(body)))
+}
@@ -0,0 +1,360 @@
+//!
+//! Conversation export: walks channel history and threads for a date range
+//! and streams messages (and optionally files) into a sink.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use futures::StreamExt;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+use tracing::*;
+
+pub trait SlackConversationExportSink: Send {
+    fn write_message(&mut self, message: &SlackExportedMessage) -> AnyStdResult<()>;
+
+    /// Receives downloaded file content in chunks, so files aren't loaded in memory
+    fn write_file_chunk(&mut self, _file: &SlackFile, _chunk: &[u8]) -> AnyStdResult<()> {
+        Ok(())
+    }
+
+    /// All chunks of the file are written
+    fn finish_file(&mut self, _file: &SlackFile) -> AnyStdResult<()> {
+        Ok(())
+    }
+
+    /// The file download failed, so the chunks already written should be discarded
+    fn discard_file(&mut self, _file: &SlackFile) -> AnyStdResult<()> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> AnyStdResult<()> {
+        Ok(())
+    }
+}
+
+/// Writes exported messages as newline-delimited JSON.
+/// Files content is ignored, so use your own sink to store files.
+pub struct SlackNdJsonExportSink<W>
+where
+    W: std::io::Write + Send,
+{
+    writer: W,
+}
+
+impl<W> SlackNdJsonExportSink<W>
+where
+    W: std::io::Write + Send,
+{
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> SlackConversationExportSink for SlackNdJsonExportSink<W>
+where
+    W: std::io::Write + Send,
+{
+    fn write_message(&mut self, message: &SlackExportedMessage) -> AnyStdResult<()> {
+        serde_json::to_writer(&mut self.writer, message)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> AnyStdResult<()> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackExportedMessage {
+    pub channel: SlackChannelId,
+    #[serde(flatten)]
+    pub message: SlackHistoryMessage,
+    pub user_name: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackConversationExportRequest {
+    pub channel: SlackChannelId,
+    pub oldest: Option<SlackDateTime>,
+    pub latest: Option<SlackDateTime>,
+    #[default = "true"]
+    pub include_replies: bool,
+    #[default = "true"]
+    pub resolve_user_names: bool,
+    #[default = "false"]
+    pub download_files: bool,
+    #[default = "SlackConversationExportRequest::DEFAULT_PAGE_LIMIT"]
+    pub page_limit: u16,
+}
+
+impl SlackConversationExportRequest {
+    pub const DEFAULT_PAGE_LIMIT: u16 = 200;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SlackConversationExportSummary {
+    pub messages: usize,
+    pub replies: usize,
+    pub files: usize,
+    pub failed_files: Vec<SlackFileId>,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Exports channel messages (newest first, each followed by its thread replies).
+    /// Pagination is handled internally, to follow rate limits enable rate control in the connector.
+    pub async fn export_conversation<S>(
+        &self,
+        req: &SlackConversationExportRequest,
+        sink: &mut S,
+    ) -> ClientResult<SlackConversationExportSummary>
+    where
+        S: SlackConversationExportSink,
+    {
+        let mut summary = SlackConversationExportSummary::default();
+        let mut user_names: HashMap<SlackUserId, Option<String>> = HashMap::new();
+        let mut cursor: Option<SlackCursorId> = None;
+
+        loop {
+            let history = self
+                .conversations_history(
+                    &SlackApiConversationsHistoryRequest::new()
+                        .with_channel(req.channel.clone())
+                        .with_limit(req.page_limit)
+                        .opt_oldest(req.oldest.as_ref().map(|dt| SlackTs::from_date_time(&dt.0)))
+                        .opt_latest(req.latest.as_ref().map(|dt| SlackTs::from_date_time(&dt.0)))
+                        .opt_cursor(cursor),
+                )
+                .await?;
+
+            for message in history.messages {
+                let thread_ts = message
+                    .origin
+                    .thread_ts
+                    .as_ref()
+                    .filter(|thread_ts| {
+                        **thread_ts == message.origin.ts
+                            && message.parent.reply_count.unwrap_or(0) > 0
+                    })
+                    .cloned();
+
+                self.export_message(req, message, sink, &mut user_names, &mut summary)
+                    .await?;
+                summary.messages += 1;
+
+                if let Some(thread_ts) = thread_ts.filter(|_| req.include_replies) {
+                    let thread = self.fetch_full_thread(&req.channel, &thread_ts).await?;
+                    for reply in thread.replies {
+                        self.export_message(req, reply, sink, &mut user_names, &mut summary)
+                            .await?;
+                        summary.replies += 1;
+                    }
+                }
+            }
+
+            cursor = history
+                .response_metadata
+                .and_then(|rm| rm.next_cursor)
+                .filter(|next_cursor| !next_cursor.value().is_empty());
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        sink.flush().map_err(map_export_sink_error)?;
+        Ok(summary)
+    }
+
+    async fn export_message<S>(
+        &self,
+        req: &SlackConversationExportRequest,
+        message: SlackHistoryMessage,
+        sink: &mut S,
+        user_names: &mut HashMap<SlackUserId, Option<String>>,
+        summary: &mut SlackConversationExportSummary,
+    ) -> ClientResult<()>
+    where
+        S: SlackConversationExportSink,
+    {
+        let user_name = match message.sender.user.as_ref() {
+            Some(user_id) if req.resolve_user_names => {
+                if !user_names.contains_key(user_id) {
+                    let user_name = match self
+                        .cached()
+                        .users_info(&SlackApiUsersInfoRequest::new(user_id.clone()))
+                        .await
+                    {
+                        Ok(resp) => resp.user.visible_name().map(|name| name.to_string()),
+                        Err(err) => {
+                            warn!("Unable to resolve Slack user name {}: {}", user_id, err);
+                            None
+                        }
+                    };
+                    user_names.insert(user_id.clone(), user_name);
+                }
+                user_names.get(user_id).cloned().flatten()
+            }
+            _ => None,
+        };
+
+        if req.download_files {
+            for file in message.content.files.iter().flatten() {
+                match file
+                    .url_private_download
+                    .as_ref()
+                    .or(file.url_private.as_ref())
+                {
+                    Some(file_url) => match self.export_file(file, file_url, sink).await? {
+                        Ok(()) => {
+                            sink.finish_file(file).map_err(map_export_sink_error)?;
+                            summary.files += 1;
+                        }
+                        Err(err) => {
+                            warn!("Unable to download Slack file {}: {}", file.id, err);
+                            sink.discard_file(file).map_err(map_export_sink_error)?;
+                            summary.failed_files.push(file.id.clone());
+                        }
+                    },
+                    None => summary.failed_files.push(file.id.clone()),
+                }
+            }
+        }
+
+        sink.write_message(
+            &SlackExportedMessage::new(req.channel.clone(), message).opt_user_name(user_name),
+        )
+        .map_err(map_export_sink_error)
+    }
+
+    /// Streams file content into the sink.
+    /// Sink errors stop the export, while download errors are returned to record failed files.
+    async fn export_file<S>(
+        &self,
+        file: &SlackFile,
+        file_url: &url::Url,
+        sink: &mut S,
+    ) -> ClientResult<ClientResult<()>>
+    where
+        S: SlackConversationExportSink,
+    {
+        let mut content = match self.files_download_stream(file_url).await {
+            Ok(content) => content,
+            Err(err) => return Ok(Err(err)),
+        };
+        while let Some(chunk) = content.next().await {
+            match chunk {
+                Ok(chunk) => sink
+                    .write_file_chunk(file, &chunk)
+                    .map_err(map_export_sink_error)?,
+                Err(err) => return Ok(Err(err)),
+            }
+        }
+        Ok(Ok(()))
+    }
+}
+
+fn map_export_sink_error(err: BoxError) -> SlackClientError {
+    SlackClientError::SystemError(
+        SlackClientSystemError::new()
+            .with_message("Conversation export sink error".into())
+            .with_cause(err),
+    )
+}
+
+#[test]
+fn check_export_conversation_into_sink() {
+    use crate::test_connector::*;
+    use serde_json::json;
+
+    #[derive(Default)]
+    struct TestExportSink {
+        messages: Vec<SlackExportedMessage>,
+        files: HashMap<SlackFileId, Vec<u8>>,
+        discarded_files: Vec<SlackFileId>,
+    }
+
+    impl SlackConversationExportSink for TestExportSink {
+        fn write_message(&mut self, message: &SlackExportedMessage) -> AnyStdResult<()> {
+            self.messages.push(message.clone());
+            Ok(())
+        }
+
+        fn write_file_chunk(&mut self, file: &SlackFile, chunk: &[u8]) -> AnyStdResult<()> {
+            self.files
+                .entry(file.id.clone())
+                .or_default()
+                .extend_from_slice(chunk);
+            Ok(())
+        }
+
+        fn discard_file(&mut self, file: &SlackFile) -> AnyStdResult<()> {
+            self.files.remove(&file.id);
+            self.discarded_files.push(file.id.clone());
+            Ok(())
+        }
+    }
+
+    let connector = SlackTestConnector::new(|call| match call.method.as_str() {
+        "conversations.history" => Ok(json!({
+            "messages": [
+                {
+                    "ts": "2.000000",
+                    "user": "U1",
+                    "text": "with files",
+                    "files": [
+                        { "id": "F1", "url_private": "https://files.slack.com/files-pri/T1-F1/a.txt" },
+                        { "id": "F2", "url_private": "https://files.slack.com/files-pri/T1-F2/b.txt" }
+                    ]
+                },
+                { "ts": "1.000000", "user": "U2", "text": "from a deleted user" }
+            ]
+        })),
+        "users.info" if call.param("user").as_deref() == Some("U1") => {
+            Ok(json!({ "user": { "id": "U1", "name": "alice" } }))
+        }
+        "/files-pri/T1-F1/a.txt" => Ok(json!("file content")),
+        _ => Err(SlackClientError::ApiError(SlackClientApiError::new(
+            "not_found".into(),
+        ))),
+    });
+    let client = SlackClient::new(connector);
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let mut sink = TestExportSink::default();
+
+    let summary = futures::executor::block_on(session.export_conversation(
+        &SlackConversationExportRequest::new("C1".into()).with_download_files(true),
+        &mut sink,
+    ))
+    .unwrap();
+
+    assert_eq!(summary.messages, 2);
+    assert_eq!(summary.files, 1);
+    assert_eq!(summary.failed_files, vec![SlackFileId::new("F2".into())]);
+    assert_eq!(
+        sink.messages
+            .iter()
+            .map(|message| message.user_name.as_deref())
+            .collect::<Vec<_>>(),
+        vec![Some("alice"), None]
+    );
+    assert_eq!(
+        sink.files.get(&SlackFileId::new("F1".into())),
+        Some(&b"file content".to_vec())
+    );
+    assert_eq!(sink.discarded_files, vec![SlackFileId::new("F2".into())]);
+}
@@ -0,0 +1,193 @@
+//!
+//! Fault injection for resilience testing: latency, rate limits, server errors,
+//! truncated bodies and connection resets injected into outgoing requests by connectors
+//! (see `SlackClientHyperConnector::with_fault_injection`), to verify retry and backoff configurations.
+//!
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rsb_derive::Builder;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackFaultInjectionConfig {
+    /// Probabilities are in the range from 0.0 to 1.0. At most one failure is injected per request.
+    #[default = "0.0"]
+    pub rate_limit_probability: f64,
+    #[default = "SlackFaultInjectionConfig::DEFAULT_RETRY_AFTER"]
+    pub rate_limit_retry_after: Duration,
+    #[default = "0.0"]
+    pub server_error_probability: f64,
+    #[default = "SlackFaultInjectionConfig::DEFAULT_SERVER_ERROR_STATUS"]
+    pub server_error_status: u16,
+    /// Real responses with bodies cut in half
+    #[default = "0.0"]
+    pub truncated_body_probability: f64,
+    #[default = "0.0"]
+    pub connection_reset_probability: f64,
+    /// Latency added to requests (independently of failures)
+    #[default = "0.0"]
+    pub latency_probability: f64,
+    #[default = "SlackFaultInjectionConfig::DEFAULT_LATENCY"]
+    pub latency: Duration,
+    /// Seed to reproduce the same faults sequence
+    pub seed: Option<u64>,
+}
+
+impl SlackFaultInjectionConfig {
+    pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+    pub const DEFAULT_SERVER_ERROR_STATUS: u16 = 503;
+    pub const DEFAULT_LATENCY: Duration = Duration::from_millis(500);
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SlackInjectedFault {
+    RateLimited { retry_after: Duration },
+    ServerError { status: u16 },
+    TruncatedBody,
+    ConnectionReset,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SlackInjectedFaultsStats {
+    pub requests: usize,
+    pub delayed: usize,
+    pub rate_limited: usize,
+    pub server_errors: usize,
+    pub truncated_bodies: usize,
+    pub connection_resets: usize,
+}
+
+#[derive(Debug)]
+pub struct SlackFaultInjector {
+    config: SlackFaultInjectionConfig,
+    rng: Mutex<StdRng>,
+    requests: AtomicUsize,
+    delayed: AtomicUsize,
+    rate_limited: AtomicUsize,
+    server_errors: AtomicUsize,
+    truncated_bodies: AtomicUsize,
+    connection_resets: AtomicUsize,
+}
+
+impl SlackFaultInjector {
+    pub fn new(config: SlackFaultInjectionConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            config,
+            rng: Mutex::new(rng),
+            requests: AtomicUsize::new(0),
+            delayed: AtomicUsize::new(0),
+            rate_limited: AtomicUsize::new(0),
+            server_errors: AtomicUsize::new(0),
+            truncated_bodies: AtomicUsize::new(0),
+            connection_resets: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &SlackFaultInjectionConfig {
+        &self.config
+    }
+
+    /// Latency to add to the next request and a failure to inject, if any
+    pub fn next_faults(&self) -> (Option<Duration>, Option<SlackInjectedFault>) {
+        let (latency_draw, fault_draw) = {
+            let mut rng = self.rng.lock().unwrap();
+            (rng.gen::<f64>(), rng.gen::<f64>())
+        };
+        self.requests.fetch_add(1, Ordering::Relaxed);
+
+        let latency = if latency_draw < self.config.latency_probability {
+            self.delayed.fetch_add(1, Ordering::Relaxed);
+            Some(self.config.latency)
+        } else {
+            None
+        };
+
+        let faults = [
+            (
+                self.config.rate_limit_probability,
+                SlackInjectedFault::RateLimited {
+                    retry_after: self.config.rate_limit_retry_after,
+                },
+                &self.rate_limited,
+            ),
+            (
+                self.config.server_error_probability,
+                SlackInjectedFault::ServerError {
+                    status: self.config.server_error_status,
+                },
+                &self.server_errors,
+            ),
+            (
+                self.config.truncated_body_probability,
+                SlackInjectedFault::TruncatedBody,
+                &self.truncated_bodies,
+            ),
+            (
+                self.config.connection_reset_probability,
+                SlackInjectedFault::ConnectionReset,
+                &self.connection_resets,
+            ),
+        ];
+
+        let mut threshold = 0.0;
+        for (probability, fault, counter) in faults {
+            threshold += probability;
+            if fault_draw < threshold {
+                counter.fetch_add(1, Ordering::Relaxed);
+                return (latency, Some(fault));
+            }
+        }
+        (latency, None)
+    }
+
+    pub fn stats(&self) -> SlackInjectedFaultsStats {
+        SlackInjectedFaultsStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            delayed: self.delayed.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            server_errors: self.server_errors.load(Ordering::Relaxed),
+            truncated_bodies: self.truncated_bodies.load(Ordering::Relaxed),
+            connection_resets: self.connection_resets.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Body of a response with an injected truncation
+    pub fn truncate_body(body: &str) -> String {
+        let mut cut = body.len() / 2;
+        while !body.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        body[..cut].to_string()
+    }
+}
+
+#[test]
+fn check_fault_injector() {
+    let injector = SlackFaultInjector::new(
+        SlackFaultInjectionConfig::new()
+            .with_rate_limit_probability(0.5)
+            .with_connection_reset_probability(0.5)
+            .with_seed(42),
+    );
+    let faults: Vec<_> = (0..100).map(|_| injector.next_faults()).collect();
+    assert!(faults
+        .iter()
+        .all(|(latency, fault)| latency.is_none() && fault.is_some()));
+
+    let stats = injector.stats();
+    assert_eq!(stats.requests, 100);
+    assert_eq!(stats.rate_limited + stats.connection_resets, 100);
+    assert!(stats.rate_limited > 0 && stats.connection_resets > 0);
+
+    assert_eq!(
+        SlackFaultInjector::truncate_body(r#"{"ok":true}"#),
+        r#"{"ok""#
+    );
+}
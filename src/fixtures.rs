@@ -0,0 +1,131 @@
+//!
+//! Captured real Slack payloads and a test harness to check that models still parse them.
+//! Apps can check their own captured payloads the same way, to find model mismatches early.
+//!
+
+use crate::api::*;
+use crate::events::*;
+use crate::lenient::*;
+use serde::de::DeserializeOwned;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SlackPayloadFixture {
+    pub name: &'static str,
+    pub payload: &'static str,
+}
+
+impl SlackPayloadFixture {
+    pub const fn new(name: &'static str, payload: &'static str) -> Self {
+        Self { name, payload }
+    }
+}
+
+pub const SLACK_PUSH_EVENT_FIXTURES: &[SlackPayloadFixture] = &[
+    SlackPayloadFixture::new(
+        "app_mention_org_install",
+        include_str!("./models/events/fixtures/app_mention_org_install.json"),
+    ),
+    SlackPayloadFixture::new(
+        "message_changed",
+        include_str!("./models/events/fixtures/message_changed.json"),
+    ),
+    SlackPayloadFixture::new(
+        "message_changed_by_bot",
+        include_str!("./models/events/fixtures/message_changed_by_bot.json"),
+    ),
+    SlackPayloadFixture::new(
+        "reaction_added",
+        include_str!("./models/events/fixtures/reaction_added.json"),
+    ),
+];
+
+pub const SLACK_INTERACTION_EVENT_FIXTURES: &[SlackPayloadFixture] = &[SlackPayloadFixture::new(
+    "block_actions",
+    include_str!("./models/events/fixtures/block_actions.json"),
+)];
+
+pub const SLACK_CONVERSATIONS_HISTORY_FIXTURES: &[SlackPayloadFixture] =
+    &[SlackPayloadFixture::new(
+        "conversations_history",
+        include_str!("./api/fixtures/slack_api_conversations_history_response.json"),
+    )];
+
+#[derive(Debug, Clone)]
+pub struct SlackPayloadFixtureFailure {
+    pub name: &'static str,
+    /// Values that had to be skipped to parse the payload. Empty for invalid JSON.
+    pub errors: Vec<SlackLenientFieldError>,
+    /// The payload couldn't be parsed even after skipping values
+    pub unparsable: bool,
+}
+
+impl std::fmt::Display for SlackPayloadFixtureFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.unparsable {
+            write!(f, " (unparsable)")?;
+        }
+        for err in self.errors.iter() {
+            write!(f, "\n  {err}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Fixtures that don't parse strictly into the model, with the values failed to parse
+pub fn check_payload_fixtures<T>(
+    fixtures: &[SlackPayloadFixture],
+) -> Vec<SlackPayloadFixtureFailure>
+where
+    T: DeserializeOwned,
+{
+    fixtures
+        .iter()
+        .filter_map(|fixture| match parse_leniently::<T>(fixture.payload) {
+            Ok(parsed) if parsed.is_complete() => None,
+            Ok(parsed) => Some(SlackPayloadFixtureFailure {
+                name: fixture.name,
+                unparsable: parsed.value.is_none(),
+                errors: parsed.errors,
+            }),
+            Err(err) => Some(SlackPayloadFixtureFailure {
+                name: fixture.name,
+                unparsable: true,
+                errors: vec![SlackLenientFieldError {
+                    path: Vec::new(),
+                    message: format!("Invalid JSON: {err}"),
+                }],
+            }),
+        })
+        .collect()
+}
+
+/// Panics with a report of all fields failed to parse, to use in tests
+pub fn assert_payload_fixtures<T>(fixtures: &[SlackPayloadFixture])
+where
+    T: DeserializeOwned,
+{
+    let failures = check_payload_fixtures::<T>(fixtures);
+    if !failures.is_empty() {
+        panic!(
+            "{} of {} fixtures failed to parse as {}:\n{}",
+            failures.len(),
+            fixtures.len(),
+            std::any::type_name::<T>(),
+            failures
+                .iter()
+                .map(|failure| failure.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
+
+#[test]
+fn check_slack_payload_fixtures() {
+    assert_payload_fixtures::<SlackPushEvent>(SLACK_PUSH_EVENT_FIXTURES);
+    assert_payload_fixtures::<SlackInteractionEvent>(SLACK_INTERACTION_EVENT_FIXTURES);
+    assert_payload_fixtures::<SlackApiConversationsHistoryResponse>(
+        SLACK_CONVERSATIONS_HISTORY_FIXTURES,
+    );
+}
@@ -0,0 +1,263 @@
+//!
+//! Channel files harvester: collects files shared in a channel (using `files.list`
+//! and channel history), and downloads them concurrently into a sink.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::StreamExt;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use std::collections::HashSet;
+use tracing::*;
+
+/// Storage for harvested files.
+/// Each file is downloaded entirely in memory before it's written to the sink,
+/// so a harvest holds up to `SlackChannelFilesHarvestRequest::concurrency` whole files in memory.
+/// Sinks run on the async runtime and shouldn't block it (use async file APIs
+/// or `spawn_blocking` for filesystem work, as `SlackDirectoryFileSink` does with the `hyper` feature).
+pub trait SlackFileHarvestSink: Send {
+    /// Files already stored (e.g. by a previous interrupted run) aren't downloaded again
+    fn contains<'a>(&'a self, _file: &'a SlackFile) -> BoxFuture<'a, bool> {
+        futures::future::ready(false).boxed()
+    }
+
+    fn write_file<'a>(
+        &'a mut self,
+        file: &'a SlackFile,
+        content: bytes::Bytes,
+    ) -> BoxFuture<'a, AnyStdResult<()>>;
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackChannelFilesHarvestRequest {
+    pub channel: SlackChannelId,
+    pub oldest: Option<SlackDateTime>,
+    pub latest: Option<SlackDateTime>,
+    /// Also scan channel history (files.list doesn't return some of the files, e.g. shared from other channels)
+    #[default = "true"]
+    pub scan_history: bool,
+    #[default = "SlackChannelFilesHarvestRequest::DEFAULT_CONCURRENCY"]
+    pub concurrency: usize,
+}
+
+impl SlackChannelFilesHarvestRequest {
+    pub const DEFAULT_CONCURRENCY: usize = 4;
+    const PAGE_LIMIT: u16 = 200;
+}
+
+#[derive(Debug, Default)]
+pub struct SlackChannelFilesHarvestSummary {
+    pub downloaded: Vec<SlackFileId>,
+    /// Files already stored in the sink
+    pub skipped: Vec<SlackFileId>,
+    pub failed: Vec<(SlackFileId, SlackClientError)>,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Lists all files shared in a channel without duplicates.
+    pub async fn list_channel_files(
+        &self,
+        req: &SlackChannelFilesHarvestRequest,
+    ) -> ClientResult<Vec<SlackFile>> {
+        let mut seen: HashSet<SlackFileId> = HashSet::new();
+        let mut files: Vec<SlackFile> = Vec::new();
+        let mut add_file = |file: SlackFile| {
+            if seen.insert(file.id.clone()) {
+                files.push(file);
+            }
+        };
+
+        let mut page = 1;
+        loop {
+            let resp = self
+                .files_list(
+                    &SlackApiFilesListRequest::new()
+                        .with_channel(req.channel.clone())
+                        .with_count(SlackChannelFilesHarvestRequest::PAGE_LIMIT)
+                        .with_page(page)
+                        .opt_ts_from(req.oldest.clone())
+                        .opt_ts_to(req.latest.clone()),
+                )
+                .await?;
+            resp.files.into_iter().for_each(&mut add_file);
+            match resp.paging {
+                Some(paging) if paging.page < paging.pages => page = paging.page + 1,
+                _ => break,
+            }
+        }
+
+        if req.scan_history {
+            let mut cursor: Option<SlackCursorId> = None;
+            loop {
+                let history = self
+                    .conversations_history(
+                        &SlackApiConversationsHistoryRequest::new()
+                            .with_channel(req.channel.clone())
+                            .with_limit(SlackChannelFilesHarvestRequest::PAGE_LIMIT)
+                            .opt_oldest(
+                                req.oldest.as_ref().map(|dt| SlackTs::from_date_time(&dt.0)),
+                            )
+                            .opt_latest(
+                                req.latest.as_ref().map(|dt| SlackTs::from_date_time(&dt.0)),
+                            )
+                            .opt_cursor(cursor),
+                    )
+                    .await?;
+                history
+                    .messages
+                    .into_iter()
+                    .flat_map(|message| message.content.files.unwrap_or_default())
+                    .for_each(&mut add_file);
+                cursor = history
+                    .response_metadata
+                    .and_then(|rm| rm.next_cursor)
+                    .filter(|next_cursor| !next_cursor.value().is_empty());
+                if cursor.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Downloads all files shared in a channel into the sink.
+    /// Files already stored by the sink are skipped, so an interrupted harvest can be resumed
+    /// by running it again with the same sink.
+    pub async fn harvest_channel_files<S>(
+        &self,
+        req: &SlackChannelFilesHarvestRequest,
+        sink: &mut S,
+    ) -> ClientResult<SlackChannelFilesHarvestSummary>
+    where
+        S: SlackFileHarvestSink,
+    {
+        let mut summary = SlackChannelFilesHarvestSummary::default();
+
+        let mut files: Vec<SlackFile> = Vec::new();
+        for file in self.list_channel_files(req).await? {
+            if sink.contains(&file).await {
+                summary.skipped.push(file.id);
+            } else {
+                files.push(file);
+            }
+        }
+
+        let mut downloads = futures::stream::iter(files)
+            .map(|file| async move {
+                let result = match file
+                    .url_private_download
+                    .as_ref()
+                    .or(file.url_private.as_ref())
+                {
+                    Some(file_url) => self.files_download(file_url).await,
+                    None => Err(SlackClientError::SystemError(
+                        SlackClientSystemError::new()
+                            .with_message(format!("Slack file {} has no download URL", file.id)),
+                    )),
+                };
+                (file, result)
+            })
+            .buffer_unordered(req.concurrency.max(1));
+
+        while let Some((file, result)) = downloads.next().await {
+            let result = match result {
+                Ok(content) => sink.write_file(&file, content).await.map_err(|err| {
+                    SlackClientError::SystemError(
+                        SlackClientSystemError::new()
+                            .with_message("Files harvest sink error".into())
+                            .with_cause(err),
+                    )
+                }),
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(()) => summary.downloaded.push(file.id),
+                Err(err) => {
+                    warn!("Unable to harvest Slack file {}: {}", file.id, err);
+                    summary.failed.push((file.id, err));
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[test]
+fn check_harvest_channel_files() {
+    use crate::test_connector::*;
+    use std::collections::HashMap;
+
+    struct MemorySink(HashMap<SlackFileId, Vec<u8>>);
+
+    impl SlackFileHarvestSink for MemorySink {
+        fn contains<'a>(&'a self, file: &'a SlackFile) -> BoxFuture<'a, bool> {
+            futures::future::ready(self.0.contains_key(&file.id)).boxed()
+        }
+
+        fn write_file<'a>(
+            &'a mut self,
+            file: &'a SlackFile,
+            content: bytes::Bytes,
+        ) -> BoxFuture<'a, AnyStdResult<()>> {
+            self.0.insert(file.id.clone(), content.to_vec());
+            futures::future::ready(Ok(())).boxed()
+        }
+    }
+
+    let file = |id: &str| {
+        serde_json::json!({
+            "id": id,
+            "url_private": format!("https://files.slack.com/files-pri/T1-{id}/a.txt")
+        })
+    };
+    let connector =
+        SlackTestConnector::new(move |call: &SlackTestCall| match call.method.as_str() {
+            "files.list" => Ok(serde_json::json!({
+                "ok": true,
+                "files": [file("F1"), file("F2"), { "id": "F4" }]
+            })),
+            "conversations.history" => Ok(serde_json::json!({
+                "ok": true,
+                "messages": [
+                    { "ts": "1.1", "files": [file("F2"), file("F3")] }
+                ]
+            })),
+            method => Ok(serde_json::Value::String(format!("content of {method}"))),
+        });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let mut sink = MemorySink(HashMap::from([("F1".into(), b"stored".to_vec())]));
+    let summary = futures::executor::block_on(session.harvest_channel_files(
+        &SlackChannelFilesHarvestRequest::new("C1".into()),
+        &mut sink,
+    ))
+    .unwrap();
+
+    let mut downloaded = summary.downloaded.clone();
+    downloaded.sort_by(|a, b| a.value().cmp(b.value()));
+    assert_eq!(downloaded, vec!["F2".into(), "F3".into()]);
+    assert_eq!(summary.skipped, vec!["F1".into()]);
+    assert_eq!(
+        summary
+            .failed
+            .iter()
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>(),
+        vec!["F4".into()]
+    );
+    assert_eq!(
+        sink.0.get(&"F3".into()).map(|content| content.as_slice()),
+        Some(b"content of /files-pri/T1-F3/a.txt".as_slice())
+    );
+}
@@ -0,0 +1,108 @@
+//!
+//! Connection prewarming and health checks using `api.test`,
+//! so the first real API call doesn't pay for DNS resolution and TLS handshake.
+//!
+
+use crate::api::*;
+use crate::models::*;
+use crate::ratectl::*;
+use crate::*;
+use std::time::{Duration, Instant};
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SlackClientHealthStatus {
+    pub checked_at: SlackDateTime,
+    /// Round-trip time of the `api.test` call
+    pub latency: Duration,
+    /// Error of the failed check
+    pub error: Option<String>,
+}
+
+impl SlackClientHealthStatus {
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Opens a connection to Slack Web API (DNS resolution, TLS handshake and an `api.test` ping).
+    /// Connectors with connection pools (such as Hyper) keep it for the next API calls.
+    /// Returns the round-trip time of the ping.
+    pub async fn warm_up(&self) -> ClientResult<Duration> {
+        let started = Instant::now();
+        let _: SlackApiTestResponse = self.ping_api().await?;
+        Ok(started.elapsed())
+    }
+
+    /// Checks Slack Web API availability with an `api.test` ping
+    pub async fn health_check(&self) -> SlackClientHealthStatus {
+        let checked_at = SlackDateTime(chrono::Utc::now());
+        let started = Instant::now();
+        let result: ClientResult<SlackApiTestResponse> = self.ping_api().await;
+        SlackClientHealthStatus {
+            checked_at,
+            latency: started.elapsed(),
+            error: result.err().map(|err| err.to_string()),
+        }
+    }
+
+    async fn ping_api(&self) -> ClientResult<SlackApiTestResponse> {
+        let http_health_span = span!(Level::DEBUG, "Slack API health check");
+
+        let context = SlackClientApiCallContext {
+            rate_control_params: Some(&SLACK_TIER4_METHOD_CONFIG),
+            token: None,
+            tracing_span: &http_health_span,
+            is_sensitive_url: false,
+        };
+
+        self.http_api
+            .connector
+            .http_post_uri(
+                self.create_method_uri_path("api.test")?,
+                &SlackApiTestRequest::new(),
+                context,
+            )
+            .await
+    }
+}
+
+#[test]
+fn check_client_health_status() {
+    use crate::errors::*;
+    use crate::test_connector::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let available = Arc::new(AtomicBool::new(true));
+    let connector = {
+        let available = available.clone();
+        SlackTestConnector::new(move |_| {
+            if available.load(Ordering::SeqCst) {
+                Ok(serde_json::json!({ "ok": true }))
+            } else {
+                Err(SlackClientError::HttpError(SlackClientHttpError::new(
+                    http::StatusCode::SERVICE_UNAVAILABLE,
+                )))
+            }
+        })
+    };
+    let client = SlackClient::new(connector.clone());
+
+    futures::executor::block_on(async {
+        assert!(client.warm_up().await.is_ok());
+        assert!(client.health_check().await.is_healthy());
+
+        available.store(false, Ordering::SeqCst);
+        assert!(client.warm_up().await.is_err());
+        let status = client.health_check().await;
+        assert!(!status.is_healthy());
+        assert!(status.error.unwrap().contains("503"));
+    });
+
+    assert_eq!(connector.calls_of("api.test").len(), 4);
+}
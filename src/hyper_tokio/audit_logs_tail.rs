@@ -0,0 +1,256 @@
+use crate::api::*;
+use crate::models::*;
+use crate::*;
+use futures::stream::{BoxStream, StreamExt};
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackAuditLogsTailConfig {
+    #[default = "SlackAuditLogsTailConfig::DEFAULT_POLL_INTERVAL"]
+    pub poll_interval: Duration,
+    /// Time range requested at once when catching up from an old checkpoint,
+    /// so only entries of one window are kept in memory to return them in chronological order
+    #[default = "SlackAuditLogsTailConfig::DEFAULT_CATCH_UP_WINDOW"]
+    pub catch_up_window: Duration,
+}
+
+impl SlackAuditLogsTailConfig {
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+    pub const DEFAULT_CATCH_UP_WINDOW: Duration = Duration::from_secs(60 * 60);
+}
+
+/// The position to resume tailing from.
+/// Audit Logs API filters entries with seconds precision,
+/// so already seen entries from the last second are tracked too.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SlackAuditLogsCheckpoint {
+    pub oldest: SlackDateTime,
+    pub seen_ids: Vec<SlackAuditLogEntryId>,
+}
+
+impl SlackAuditLogsCheckpoint {
+    pub fn new(oldest: SlackDateTime) -> Self {
+        Self {
+            oldest,
+            seen_ids: Vec::new(),
+        }
+    }
+
+    fn advance(&mut self, entry: &SlackAuditLogEntry) {
+        if entry.date_create == self.oldest {
+            self.seen_ids.push(entry.id.clone());
+        } else {
+            self.oldest = entry.date_create.clone();
+            self.seen_ids = vec![entry.id.clone()];
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackAuditLogsTailEvent {
+    pub entry: SlackAuditLogEntry,
+    /// Store it to resume tailing after this entry
+    pub checkpoint: SlackAuditLogsCheckpoint,
+}
+
+// A poll of the time window from the checkpoint, with pages fetched so far
+struct SlackAuditLogsTailPoll {
+    req: SlackApiAuditLogsRequest,
+    latest: SlackDateTime,
+    pages: Vec<Vec<SlackAuditLogEntry>>,
+}
+
+struct SlackAuditLogsTailState {
+    checkpoint: SlackAuditLogsCheckpoint,
+    buffer: VecDeque<SlackAuditLogEntry>,
+    poll: Option<SlackAuditLogsTailPoll>,
+    // The end of the last completed poll window, and if it has reached the current time
+    polled_until: Option<(SlackDateTime, bool)>,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Endless stream of audit log entries in chronological order: first all entries
+    /// since the checkpoint (or `req.oldest`, or the current time if none of them are specified),
+    /// and then new entries polled with the configured interval. `req.latest` is ignored.
+    /// Errors are returned as stream items, and the failed page is requested again on the next poll.
+    pub fn audit_logs_tail<'s>(
+        &'s self,
+        req: &SlackApiAuditLogsRequest,
+        checkpoint: Option<SlackAuditLogsCheckpoint>,
+        config: &SlackAuditLogsTailConfig,
+    ) -> BoxStream<'s, ClientResult<SlackAuditLogsTailEvent>> {
+        let base_req = req.clone().without_cursor().without_latest();
+        let config = config.clone();
+        let initial_state = SlackAuditLogsTailState {
+            checkpoint: checkpoint.unwrap_or_else(|| {
+                SlackAuditLogsCheckpoint::new(
+                    req.oldest
+                        .clone()
+                        .unwrap_or_else(|| SlackDateTime(chrono::Utc::now())),
+                )
+            }),
+            buffer: VecDeque::new(),
+            poll: None,
+            polled_until: None,
+        };
+
+        futures::stream::unfold(initial_state, move |mut state| {
+            let base_req = base_req.clone();
+            let config = config.clone();
+            async move {
+                loop {
+                    if let Some(entry) = state.buffer.pop_front() {
+                        state.checkpoint.advance(&entry);
+                        let checkpoint = state.checkpoint.clone();
+                        return Some((Ok(SlackAuditLogsTailEvent { entry, checkpoint }), state));
+                    }
+
+                    let mut poll = match state.poll.take() {
+                        Some(poll) => poll,
+                        None => Self::start_audit_logs_poll(&mut state, &base_req, &config).await,
+                    };
+
+                    match self.audit_logs(&poll.req).await {
+                        Ok(resp) => {
+                            poll.pages.push(resp.entries);
+                            match resp
+                                .response_metadata
+                                .and_then(|rm| rm.next_cursor)
+                                .filter(|next_cursor| !next_cursor.value().is_empty())
+                            {
+                                Some(next_cursor) => {
+                                    poll.req = poll.req.with_cursor(next_cursor);
+                                    state.poll = Some(poll);
+                                }
+                                None => {
+                                    // Pages are returned newest first
+                                    let seen_ids = &state.checkpoint.seen_ids;
+                                    state.buffer.extend(
+                                        poll.pages
+                                            .into_iter()
+                                            .rev()
+                                            .flat_map(|page| page.into_iter().rev())
+                                            .filter(|entry| !seen_ids.contains(&entry.id)),
+                                    );
+                                    let caught_up = poll.req.latest.is_none();
+                                    state.polled_until = Some((poll.latest, caught_up));
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            state.poll = Some(poll);
+                            return Some((Err(err), state));
+                        }
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+
+    async fn start_audit_logs_poll(
+        state: &mut SlackAuditLogsTailState,
+        base_req: &SlackApiAuditLogsRequest,
+        config: &SlackAuditLogsTailConfig,
+    ) -> SlackAuditLogsTailPoll {
+        if let Some((polled_until, caught_up)) = state.polled_until.take() {
+            if state.checkpoint.oldest.0 < polled_until.0 {
+                // Nothing newer has been seen in the polled window
+                state.checkpoint = SlackAuditLogsCheckpoint::new(polled_until);
+            }
+            if caught_up {
+                tokio::time::sleep(config.poll_interval).await;
+            }
+        }
+
+        let now = chrono::Utc::now();
+        let window_end = chrono::Duration::from_std(config.catch_up_window)
+            .ok()
+            .and_then(|window| state.checkpoint.oldest.0.checked_add_signed(window))
+            .filter(|window_end| *window_end < now);
+
+        SlackAuditLogsTailPoll {
+            req: base_req
+                .clone()
+                .with_oldest(state.checkpoint.oldest.clone())
+                .opt_latest(window_end.map(SlackDateTime)),
+            latest: SlackDateTime(window_end.unwrap_or(now)),
+            pages: Vec::new(),
+        }
+    }
+}
+
+#[test]
+fn check_audit_logs_tail_pages() {
+    use crate::errors::*;
+    use crate::test_connector::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let started = chrono::Utc::now().timestamp() - 600;
+    let entry = move |id: &str, offset: i64| {
+        serde_json::json!({
+            "id": id,
+            "date_create": started + offset,
+            "action": "user_login",
+            "actor": { "type": "user" },
+            "entity": { "type": "user" }
+        })
+    };
+    let failed_once = AtomicBool::new(false);
+    let connector = SlackTestConnector::new(move |call| match call.query_param("cursor") {
+        None => Ok(serde_json::json!({
+            "entries": [entry("e3", 30), entry("e2", 20)],
+            "response_metadata": { "next_cursor": "page2" }
+        })),
+        Some(_) if !failed_once.swap(true, Ordering::SeqCst) => Err(SlackClientError::HttpError(
+            SlackClientHttpError::new(http::StatusCode::BAD_GATEWAY),
+        )),
+        Some(_) => Ok(serde_json::json!({ "entries": [entry("e1", 10), entry("e0", 0)] })),
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxp-test".into());
+    let session = client.open_session(&token);
+    let checkpoint = SlackAuditLogsCheckpoint {
+        oldest: SlackDateTime(chrono::DateTime::from_timestamp(started, 0).unwrap()),
+        seen_ids: vec!["e0".into()],
+    };
+
+    let items: Vec<_> = futures::executor::block_on(
+        session
+            .audit_logs_tail(
+                &SlackApiAuditLogsRequest::new(),
+                Some(checkpoint),
+                &SlackAuditLogsTailConfig::new(),
+            )
+            .take(4)
+            .collect(),
+    );
+    assert!(items[0].is_err());
+    let ids: Vec<String> = items[1..]
+        .iter()
+        .map(|item| item.as_ref().unwrap().entry.id.to_string())
+        .collect();
+    assert_eq!(ids, vec!["e1", "e2", "e3"]);
+    assert_eq!(
+        items[3].as_ref().unwrap().checkpoint.seen_ids,
+        vec!["e3".into()]
+    );
+
+    // The failed page is requested again without restarting from the first page
+    let cursors: Vec<Option<String>> = connector
+        .calls()
+        .iter()
+        .map(|call| call.query_param("cursor"))
+        .collect();
+    assert_eq!(
+        cursors,
+        vec![None, Some("page2".into()), Some("page2".into())]
+    );
+}
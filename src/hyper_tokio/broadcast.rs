@@ -0,0 +1,174 @@
+use crate::api::*;
+use crate::errors::*;
+use crate::hyper_tokio::paced_calls::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use std::time::Duration;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackBroadcastConfig {
+    /// Delay between posting to channels
+    #[default = "SlackBroadcastConfig::DEFAULT_PACING"]
+    pub pacing: Duration,
+    /// How many times to retry a channel after rate limit errors
+    #[default = "SlackBroadcastConfig::DEFAULT_MAX_RATE_LIMIT_RETRIES"]
+    pub max_rate_limit_retries: usize,
+}
+
+impl SlackBroadcastConfig {
+    pub const DEFAULT_PACING: Duration = Duration::from_secs(1);
+    pub const DEFAULT_MAX_RATE_LIMIT_RETRIES: usize = 3;
+}
+
+#[derive(Debug)]
+pub struct SlackBroadcastReport {
+    pub delivered: Vec<SlackApiChatPostMessageResponse>,
+    pub failed: Vec<(SlackChannelId, SlackClientError)>,
+    /// Channels not processed because the broadcast was stopped by a fatal error
+    pub pending: Vec<SlackChannelId>,
+}
+
+impl SlackBroadcastReport {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && self.pending.is_empty()
+    }
+
+    /// Channels to resume the broadcast with (failed and pending ones)
+    pub fn remaining_channels(&self) -> Vec<SlackChannelId> {
+        self.failed
+            .iter()
+            .map(|(channel, _)| channel.clone())
+            .chain(self.pending.iter().cloned())
+            .collect()
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Posts the same message to many channels sequentially with pacing between posts.
+    /// Errors are collected per channel, and the broadcast can be resumed
+    /// using `SlackBroadcastReport::remaining_channels`.
+    pub async fn broadcast_message<T>(
+        &self,
+        channels: &[SlackChannelId],
+        template: &T,
+        config: &SlackBroadcastConfig,
+    ) -> SlackBroadcastReport
+    where
+        T: SlackMessageTemplate,
+    {
+        let content = template.render_template();
+        let mut report = SlackBroadcastReport {
+            delivered: Vec::with_capacity(channels.len()),
+            failed: Vec::new(),
+            pending: Vec::new(),
+        };
+
+        for (idx, channel) in channels.iter().enumerate() {
+            if idx > 0 && !config.pacing.is_zero() {
+                tokio::time::sleep(config.pacing).await;
+            }
+
+            match self.broadcast_post_message(channel, &content, config).await {
+                Ok(resp) => report.delivered.push(resp),
+                Err(err) if fatal_error_code(&err).is_some() => {
+                    error!(
+                        "Broadcast stopped at channel {} with a fatal error: {}",
+                        channel, err
+                    );
+                    report.pending.extend(channels[idx..].iter().cloned());
+                    break;
+                }
+                Err(err) => {
+                    warn!("Broadcast to channel {} failed: {}", channel, err);
+                    report.failed.push((channel.clone(), err));
+                }
+            }
+        }
+
+        report
+    }
+
+    async fn broadcast_post_message(
+        &self,
+        channel: &SlackChannelId,
+        content: &SlackMessageContent,
+        config: &SlackBroadcastConfig,
+    ) -> ClientResult<SlackApiChatPostMessageResponse> {
+        let req = SlackApiChatPostMessageRequest::new(channel.clone(), content.clone());
+        retry_rate_limited(
+            &format!("Broadcast to channel {channel}"),
+            config.max_rate_limit_retries,
+            config.pacing,
+            || self.chat_post_message(&req),
+        )
+        .await
+    }
+}
+
+#[tokio::test]
+async fn check_broadcast_report() {
+    use crate::test_connector::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Greeting;
+
+    impl SlackMessageTemplate for Greeting {
+        fn render_template(&self) -> SlackMessageContent {
+            SlackMessageContent::new().with_text("Hello".into())
+        }
+    }
+
+    let rate_limited = AtomicUsize::new(0);
+    let connector = SlackTestConnector::new(move |call: &SlackTestCall| {
+        let channel = call.param("channel").unwrap_or_default();
+        match channel.as_str() {
+            "C1" if rate_limited.fetch_add(1, Ordering::SeqCst) == 0 => {
+                Err(SlackClientError::RateLimitError(
+                    SlackRateLimitError::new().with_retry_after(Duration::from_millis(1)),
+                ))
+            }
+            "C2" => Err(SlackClientError::ApiError(SlackClientApiError::new(
+                "channel_not_found".into(),
+            ))),
+            "C3" => Err(SlackClientError::ApiError(SlackClientApiError::new(
+                "token_revoked".into(),
+            ))),
+            _ => Ok(serde_json::json!({
+                "ok": true,
+                "channel": channel,
+                "ts": "1.1",
+                "message": { "ts": "1.1", "text": "Hello" }
+            })),
+        }
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let channels: Vec<SlackChannelId> = vec!["C1".into(), "C2".into(), "C3".into(), "C4".into()];
+    let report = session
+        .broadcast_message(
+            &channels,
+            &Greeting,
+            &SlackBroadcastConfig::new().with_pacing(Duration::ZERO),
+        )
+        .await;
+
+    assert_eq!(
+        report
+            .delivered
+            .iter()
+            .map(|resp| resp.channel.clone())
+            .collect::<Vec<_>>(),
+        vec!["C1".into()]
+    );
+    assert_eq!(report.pending, channels[2..].to_vec());
+    assert!(!report.is_complete());
+    assert_eq!(report.remaining_channels(), channels[1..].to_vec());
+    assert_eq!(connector.calls_of("chat.postMessage").len(), 4);
+}
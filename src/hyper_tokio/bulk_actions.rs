@@ -0,0 +1,325 @@
+use crate::api::*;
+use crate::errors::*;
+use crate::hyper_tokio::paced_calls::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use std::time::Duration;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Builder)]
+pub struct SlackMessageRef {
+    pub channel: SlackChannelId,
+    pub ts: SlackTs,
+}
+
+impl From<&SlackSearchMessageMatch> for SlackMessageRef {
+    fn from(search_match: &SlackSearchMessageMatch) -> Self {
+        SlackMessageRef::new(search_match.channel.id.clone(), search_match.ts.clone())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SlackBulkMessageAction {
+    AddReaction(SlackReactionName),
+    RemoveReaction(SlackReactionName),
+    Pin,
+    Unpin,
+}
+
+impl SlackBulkMessageAction {
+    // Errors meaning that the action has been already applied to a message
+    fn already_applied_error_code(&self) -> &'static str {
+        match self {
+            SlackBulkMessageAction::AddReaction(_) => "already_reacted",
+            SlackBulkMessageAction::RemoveReaction(_) => "no_reaction",
+            SlackBulkMessageAction::Pin => "already_pinned",
+            SlackBulkMessageAction::Unpin => "no_pin",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackBulkMessageActionConfig {
+    /// Delay between actions
+    #[default = "SlackBulkMessageActionConfig::DEFAULT_PACING"]
+    pub pacing: Duration,
+    /// How many times to retry a message after rate limit errors
+    #[default = "SlackBulkMessageActionConfig::DEFAULT_MAX_RATE_LIMIT_RETRIES"]
+    pub max_rate_limit_retries: usize,
+}
+
+impl SlackBulkMessageActionConfig {
+    pub const DEFAULT_PACING: Duration = Duration::from_millis(1200);
+    pub const DEFAULT_MAX_RATE_LIMIT_RETRIES: usize = 3;
+}
+
+#[derive(Debug)]
+pub struct SlackBulkMessageActionReport {
+    pub completed: Vec<SlackMessageRef>,
+    /// Messages where the action had been already applied
+    pub skipped: Vec<SlackMessageRef>,
+    pub failed: Vec<(SlackMessageRef, SlackClientError)>,
+    /// Messages not processed because of a fatal error
+    pub pending: Vec<SlackMessageRef>,
+}
+
+impl SlackBulkMessageActionReport {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && self.pending.is_empty()
+    }
+
+    /// Messages to resume the action with (failed and pending ones)
+    pub fn remaining_messages(&self) -> Vec<SlackMessageRef> {
+        self.failed
+            .iter()
+            .map(|(message, _)| message.clone())
+            .chain(self.pending.iter().cloned())
+            .collect()
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Applies a reaction or pin action to many messages (e.g. all matches of a search)
+    /// sequentially with pacing. Errors are collected per message.
+    pub async fn bulk_message_action(
+        &self,
+        messages: &[SlackMessageRef],
+        action: &SlackBulkMessageAction,
+        config: &SlackBulkMessageActionConfig,
+    ) -> SlackBulkMessageActionReport {
+        let mut report = SlackBulkMessageActionReport {
+            completed: Vec::with_capacity(messages.len()),
+            skipped: Vec::new(),
+            failed: Vec::new(),
+            pending: Vec::new(),
+        };
+
+        for (idx, message) in messages.iter().enumerate() {
+            if idx > 0 && !config.pacing.is_zero() {
+                tokio::time::sleep(config.pacing).await;
+            }
+
+            match self.bulk_apply_action(message, action, config).await {
+                Ok(()) => report.completed.push(message.clone()),
+                Err(SlackClientError::ApiError(ref api_err))
+                    if api_err.code == action.already_applied_error_code() =>
+                {
+                    report.skipped.push(message.clone())
+                }
+                Err(err) if fatal_error_code(&err).is_some() => {
+                    error!(
+                        "Bulk action stopped at message {}/{} with a fatal error: {}",
+                        message.channel, message.ts, err
+                    );
+                    report.pending.extend(messages[idx..].iter().cloned());
+                    break;
+                }
+                Err(err) => {
+                    warn!(
+                        "Bulk action for message {}/{} failed: {}",
+                        message.channel, message.ts, err
+                    );
+                    report.failed.push((message.clone(), err));
+                }
+            }
+        }
+
+        report
+    }
+
+    pub async fn add_reaction_to_messages(
+        &self,
+        messages: &[SlackMessageRef],
+        reaction: &SlackReactionName,
+        config: &SlackBulkMessageActionConfig,
+    ) -> SlackBulkMessageActionReport {
+        self.bulk_message_action(
+            messages,
+            &SlackBulkMessageAction::AddReaction(reaction.clone()),
+            config,
+        )
+        .await
+    }
+
+    pub async fn remove_reaction_from_messages(
+        &self,
+        messages: &[SlackMessageRef],
+        reaction: &SlackReactionName,
+        config: &SlackBulkMessageActionConfig,
+    ) -> SlackBulkMessageActionReport {
+        self.bulk_message_action(
+            messages,
+            &SlackBulkMessageAction::RemoveReaction(reaction.clone()),
+            config,
+        )
+        .await
+    }
+
+    pub async fn pin_messages(
+        &self,
+        messages: &[SlackMessageRef],
+        config: &SlackBulkMessageActionConfig,
+    ) -> SlackBulkMessageActionReport {
+        self.bulk_message_action(messages, &SlackBulkMessageAction::Pin, config)
+            .await
+    }
+
+    pub async fn unpin_messages(
+        &self,
+        messages: &[SlackMessageRef],
+        config: &SlackBulkMessageActionConfig,
+    ) -> SlackBulkMessageActionReport {
+        self.bulk_message_action(messages, &SlackBulkMessageAction::Unpin, config)
+            .await
+    }
+
+    async fn bulk_apply_action(
+        &self,
+        message: &SlackMessageRef,
+        action: &SlackBulkMessageAction,
+        config: &SlackBulkMessageActionConfig,
+    ) -> ClientResult<()> {
+        retry_rate_limited(
+            &format!("Bulk action for message {}/{}", message.channel, message.ts),
+            config.max_rate_limit_retries,
+            config.pacing,
+            || self.bulk_call_action(message, action),
+        )
+        .await
+    }
+
+    async fn bulk_call_action(
+        &self,
+        message: &SlackMessageRef,
+        action: &SlackBulkMessageAction,
+    ) -> ClientResult<()> {
+        match action {
+            SlackBulkMessageAction::AddReaction(reaction) => self
+                .reactions_add(&SlackApiReactionsAddRequest::new(
+                    message.channel.clone(),
+                    reaction.clone(),
+                    message.ts.clone(),
+                ))
+                .await
+                .map(|_| ()),
+            SlackBulkMessageAction::RemoveReaction(reaction) => self
+                .reactions_remove(
+                    &SlackApiReactionsRemoveRequest::new(reaction.clone())
+                        .with_channel(message.channel.clone())
+                        .with_timestamp(message.ts.clone()),
+                )
+                .await
+                .map(|_| ()),
+            SlackBulkMessageAction::Pin => self
+                .pins_add(&SlackApiPinsAddRequest::new(
+                    message.channel.clone(),
+                    message.ts.clone(),
+                ))
+                .await
+                .map(|_| ()),
+            SlackBulkMessageAction::Unpin => self
+                .pins_remove(&SlackApiPinsRemoveRequest::new(
+                    message.channel.clone(),
+                    message.ts.clone(),
+                ))
+                .await
+                .map(|_| ()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn check_bulk_message_action_removals() {
+    use crate::test_connector::*;
+
+    let connector =
+        SlackTestConnector::new(
+            |call: &SlackTestCall| match call.param("timestamp").as_deref() {
+                Some("1.2") if call.method == "reactions.remove" => Err(
+                    SlackClientError::ApiError(SlackClientApiError::new("no_reaction".into())),
+                ),
+                Some("1.1") if call.method == "pins.remove" => Err(SlackClientError::ApiError(
+                    SlackClientApiError::new("no_pin".into()),
+                )),
+                _ => Ok(serde_json::json!({ "ok": true })),
+            },
+        );
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let config = SlackBulkMessageActionConfig::new().with_pacing(Duration::ZERO);
+
+    let messages = vec![
+        SlackMessageRef::new("C1".into(), "1.1".into()),
+        SlackMessageRef::new("C2".into(), "1.2".into()),
+    ];
+    let removed = session
+        .remove_reaction_from_messages(&messages, &"thumbsup".into(), &config)
+        .await;
+    let unpinned = session.unpin_messages(&messages, &config).await;
+
+    assert_eq!(removed.completed, vec![messages[0].clone()]);
+    assert_eq!(removed.skipped, vec![messages[1].clone()]);
+    assert!(removed.is_complete());
+    assert_eq!(unpinned.completed, vec![messages[1].clone()]);
+    assert_eq!(unpinned.skipped, vec![messages[0].clone()]);
+
+    let removals = connector.calls_of("reactions.remove");
+    assert_eq!(removals.len(), 2);
+    assert_eq!(removals[1].param("name").as_deref(), Some("thumbsup"));
+    assert_eq!(removals[1].param("channel").as_deref(), Some("C2"));
+    let unpins = connector.calls_of("pins.remove");
+    assert_eq!(unpins.len(), 2);
+    assert_eq!(unpins[0].param("channel").as_deref(), Some("C1"));
+    assert_eq!(unpins[0].param("timestamp").as_deref(), Some("1.1"));
+}
+
+#[tokio::test]
+async fn check_bulk_message_action_retries_and_fatal_errors() {
+    use crate::test_connector::*;
+
+    let connector =
+        SlackTestConnector::new(
+            |call: &SlackTestCall| match call.param("timestamp").as_deref() {
+                Some("1.1") => Err(SlackClientError::RateLimitError(
+                    SlackRateLimitError::new().with_retry_after(Duration::from_millis(1)),
+                )),
+                Some("1.2") => Err(SlackClientError::ApiError(SlackClientApiError::new(
+                    "missing_scope".into(),
+                ))),
+                _ => Ok(serde_json::json!({ "ok": true })),
+            },
+        );
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let messages: Vec<SlackMessageRef> = ["1.1", "1.2", "1.3"]
+        .iter()
+        .map(|ts| SlackMessageRef::new("C1".into(), (*ts).into()))
+        .collect();
+    let report = session
+        .pin_messages(
+            &messages,
+            &SlackBulkMessageActionConfig::new()
+                .with_pacing(Duration::ZERO)
+                .with_max_rate_limit_retries(1),
+        )
+        .await;
+
+    assert!(report.completed.is_empty());
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, messages[0]);
+    assert!(matches!(
+        report.failed[0].1,
+        SlackClientError::RateLimitError(_)
+    ));
+    assert_eq!(report.pending, messages[1..].to_vec());
+    assert_eq!(report.remaining_messages(), messages);
+    // One retry of the rate limited message, and nothing after the fatal error
+    assert_eq!(connector.calls_of("pins.add").len(), 3);
+}
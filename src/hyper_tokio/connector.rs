@@ -5,7 +5,9 @@ use crate::models::{SlackClientId, SlackClientSecret};
 use crate::*;
 use async_recursion::async_recursion;
 use futures::future::{BoxFuture, FutureExt};
-use http_body_util::{BodyExt, Empty, Full};
+use futures::stream::{BoxStream, Stream, StreamExt};
+use http_body_util::{BodyExt, Empty, Full, StreamBody};
+use hyper::body::Frame;
 use hyper::http::StatusCode;
 use hyper::Request;
 use hyper_rustls::HttpsConnector;
@@ -18,11 +20,17 @@ use crate::hyper_tokio::multipart_form::{
 };
 use crate::multipart_form::FileMultipartData;
 use crate::prelude::hyper_ext::HyperExtensions;
-use crate::ratectl::SlackApiRateControlConfig;
+use crate::ratectl::{
+    SlackApiCircuitBreaker, SlackApiCircuitBreakerPermit, SlackApiRateControlConfig,
+    SlackApiRetryBudget,
+};
 use bytes::BytesMut;
+use std::convert::Infallible;
 use std::hash::Hash;
 use std::hash::Hasher;
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 use std::time::Duration;
 
 use tracing::*;
@@ -32,6 +40,9 @@ use url::Url;
 pub struct SlackClientHyperConnector<H: Send + Sync + Clone + connect::Connect> {
     hyper_connector: Client<H, Body>,
     tokio_rate_controller: Option<Arc<SlackTokioRateController>>,
+    circuit_breaker: Option<Arc<SlackApiCircuitBreaker>>,
+    retry_budget: Option<Arc<SlackApiRetryBudget>>,
+    fault_injector: Option<Arc<SlackFaultInjector>>,
     slack_api_url: String,
 }
 
@@ -62,6 +73,9 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
         Self {
             hyper_connector: Client::builder(TokioExecutor::new()).build::<_, Body>(connector),
             tokio_rate_controller: None,
+            circuit_breaker: None,
+            retry_budget: None,
+            fault_injector: None,
             slack_api_url: SlackClientHttpApiUri::SLACK_API_URI_STR.to_string(),
         }
     }
@@ -75,6 +89,42 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
         }
     }
 
+    /// Rejects requests without sending them while the circuit breaker is open.
+    /// The same breaker can be shared between many connectors.
+    pub fn with_circuit_breaker(self, circuit_breaker: Arc<SlackApiCircuitBreaker>) -> Self {
+        Self {
+            circuit_breaker: Some(circuit_breaker),
+            ..self
+        }
+    }
+
+    /// Limits retries (configured with `max_retries` in rate control config)
+    /// with a budget shared across workers.
+    pub fn with_retry_budget(self, retry_budget: Arc<SlackApiRetryBudget>) -> Self {
+        Self {
+            retry_budget: Some(retry_budget),
+            ..self
+        }
+    }
+
+    /// Injects faults into requests for resilience testing (never use it in production).
+    /// Faults are injected under the rate control and retries, so they exercise their configuration.
+    pub fn with_fault_injection(self, fault_injector: Arc<SlackFaultInjector>) -> Self {
+        Self {
+            fault_injector: Some(fault_injector),
+            ..self
+        }
+    }
+
+    /// Until when API calls for the team are slowed down, because Slack throttled event deliveries
+    /// (`app_rate_limited` events). `None` if calls aren't slowed down or rate control is disabled.
+    pub async fn backpressure_until(&self, team_id: &SlackTeamId) -> Option<std::time::Instant> {
+        match self.tokio_rate_controller.as_ref() {
+            Some(rate_controller) => rate_controller.backpressure_until(team_id).await,
+            None => None,
+        }
+    }
+
     pub fn with_slack_api_url(self, slack_api_url: &str) -> Self {
         Self {
             slack_api_url: slack_api_url.to_string(),
@@ -121,11 +171,69 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
             );
         });
 
-        let http_res = self.hyper_connector.request(request).await?;
-        let http_status = http_res.status();
-        let http_headers = http_res.headers().clone();
-        let http_content_type = HyperExtensions::http_response_content_type(&http_res);
-        let http_body_str = HyperExtensions::http_body_to_string(http_res).await?;
+        let injected_fault = match self.fault_injector.as_ref() {
+            Some(fault_injector) => {
+                let (latency, fault) = fault_injector.next_faults();
+                if let Some(latency) = latency {
+                    tokio::time::sleep(latency).await;
+                }
+                if fault.is_some() {
+                    context.tracing_span.in_scope(|| {
+                        debug!(
+                            slack_uri = uri_str.as_str(),
+                            "Injecting fault {:?} to {}",
+                            fault,
+                            uri_str.as_str()
+                        );
+                    });
+                }
+                fault
+            }
+            None => None,
+        };
+
+        let (http_status, http_headers, http_content_type, http_body_str) = match injected_fault {
+            Some(SlackInjectedFault::ConnectionReset) => {
+                return Err(SlackClientError::HttpProtocolError(
+                    SlackClientHttpProtocolError::new().with_cause(Box::new(std::io::Error::new(
+                        std::io::ErrorKind::ConnectionReset,
+                        "Injected connection reset",
+                    ))),
+                ))
+            }
+            Some(SlackInjectedFault::RateLimited { retry_after }) => {
+                let mut http_headers = hyper::HeaderMap::new();
+                http_headers.insert(hyper::header::RETRY_AFTER, retry_after.as_secs().into());
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    http_headers,
+                    Some(mime::APPLICATION_JSON),
+                    r#"{"ok":false,"error":"ratelimited"}"#.to_string(),
+                )
+            }
+            Some(SlackInjectedFault::ServerError { status }) => (
+                StatusCode::from_u16(status).unwrap_or(StatusCode::SERVICE_UNAVAILABLE),
+                hyper::HeaderMap::new(),
+                None,
+                "Injected server error".to_string(),
+            ),
+            injected_fault => {
+                let http_res = self.hyper_connector.request(request).await?;
+                let http_status = http_res.status();
+                let http_headers = http_res.headers().clone();
+                let http_content_type = HyperExtensions::http_response_content_type(&http_res);
+                let http_body_str = HyperExtensions::http_body_to_string(http_res).await?;
+                match injected_fault {
+                    Some(SlackInjectedFault::TruncatedBody) => (
+                        http_status,
+                        http_headers,
+                        http_content_type,
+                        SlackFaultInjector::truncate_body(&http_body_str),
+                    ),
+                    _ => (http_status, http_headers, http_content_type, http_body_str),
+                }
+            }
+        };
         let http_content_is_json = http_content_type.iter().all(|response_mime| {
             response_mime.type_() == mime::APPLICATION && response_mime.subtype() == mime::JSON
         });
@@ -141,30 +249,25 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
 
         match http_status {
             StatusCode::OK if http_content_is_json => {
-                let slack_message: SlackEnvelopeMessage =
-                    serde_json::from_str(http_body_str.as_str())
-                        .map_err(|err| map_serde_error(err, Some(http_body_str.as_str())))?;
+                let slack_message: SlackEnvelopeMessage = decode_json_body(http_body_str.as_str())?;
+                let slack_warnings = slack_message.all_warnings();
                 match slack_message.error {
                     None => {
-                        let decoded_body = serde_json::from_str(http_body_str.as_str())
-                            .map_err(|err| map_serde_error(err, Some(http_body_str.as_str())))?;
+                        let decoded_body = decode_json_body(http_body_str.as_str())?;
                         Ok(decoded_body)
                     }
                     Some(slack_error) => Err(SlackClientError::ApiError(
                         SlackClientApiError::new(slack_error)
                             .opt_errors(slack_message.errors)
-                            .opt_warnings(slack_message.warnings)
+                            .opt_warnings(slack_warnings)
                             .with_http_response_body(http_body_str),
                     )),
                 }
             }
-            StatusCode::OK | StatusCode::NO_CONTENT => {
-                serde_json::from_str("{}").map_err(|err| map_serde_error(err, Some("{}")))
-            }
+            StatusCode::OK | StatusCode::NO_CONTENT => decode_json_body("{}"),
             StatusCode::TOO_MANY_REQUESTS if http_content_is_json => {
-                let slack_message: SlackEnvelopeMessage =
-                    serde_json::from_str(http_body_str.as_str())
-                        .map_err(|err| map_serde_error(err, Some(http_body_str.as_str())))?;
+                let slack_message: SlackEnvelopeMessage = decode_json_body(http_body_str.as_str())?;
+                let slack_warnings = slack_message.all_warnings();
 
                 Err(SlackClientError::RateLimitError(
                     SlackRateLimitError::new()
@@ -175,7 +278,7 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
                                 .map(Duration::from_secs),
                         )
                         .opt_code(slack_message.error)
-                        .opt_warnings(slack_message.warnings)
+                        .opt_warnings(slack_warnings)
                         .with_http_response_body(http_body_str),
                 ))
             }
@@ -195,6 +298,136 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
         }
     }
 
+    async fn send_guarded_http_request<'a, RS>(
+        &'a self,
+        request: Request<Body>,
+        context: SlackClientApiCallContext<'a>,
+    ) -> ClientResult<RS>
+    where
+        RS: for<'de> serde::de::Deserialize<'de>,
+    {
+        let permit = self.acquire_circuit_breaker_permit(request.uri().path())?;
+        let result = self.send_http_request(request, context).await;
+        if let Some(permit) = permit {
+            permit.record(!result.as_ref().is_err_and(Self::is_unavailable_error));
+        }
+        result
+    }
+
+    fn acquire_circuit_breaker_permit(
+        &self,
+        uri_path: &str,
+    ) -> ClientResult<Option<SlackApiCircuitBreakerPermit<'_>>> {
+        match self.circuit_breaker.as_ref() {
+            Some(circuit_breaker) => match circuit_breaker.try_acquire() {
+                Some(permit) => Ok(Some(permit)),
+                None => Err(SlackClientError::CircuitBreakerOpenError(
+                    SlackClientCircuitBreakerOpenError::new(uri_path.to_string()),
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
+    async fn send_binary_request<'a>(
+        &'a self,
+        full_uri: Url,
+        context_token: Option<&'a SlackApiToken>,
+        context: SlackClientApiCallContext<'a>,
+    ) -> ClientResult<hyper::Response<hyper::body::Incoming>> {
+        let http_request = HyperExtensions::setup_token_auth_header(
+            HyperExtensions::create_http_request(full_uri, hyper::http::Method::GET),
+            context_token,
+        )
+        .body(Empty::new().boxed())?;
+
+        context.tracing_span.in_scope(|| {
+            debug!("Sending HTTP request to download binary content");
+        });
+
+        let http_res = self.hyper_connector.request(http_request).await?;
+        let http_status = http_res.status();
+
+        context.tracing_span.in_scope(|| {
+            debug!(
+                slack_http_status = http_status.as_u16(),
+                "Received HTTP response {}", http_status
+            );
+        });
+
+        match http_status {
+            StatusCode::OK => Ok(http_res),
+            StatusCode::TOO_MANY_REQUESTS => Err(SlackClientError::RateLimitError(
+                SlackRateLimitError::new().opt_retry_after(
+                    http_res
+                        .headers()
+                        .get(hyper::header::RETRY_AFTER)
+                        .and_then(|ra| ra.to_str().ok().and_then(|s| s.parse().ok()))
+                        .map(Duration::from_secs),
+                ),
+            )),
+            _ => Err(SlackClientError::HttpError(SlackClientHttpError::new(
+                http_status,
+            ))),
+        }
+    }
+
+    async fn download_binary_content<'a>(
+        &'a self,
+        full_uri: Url,
+        context_token: Option<&'a SlackApiToken>,
+        context: SlackClientApiCallContext<'a>,
+    ) -> ClientResult<bytes::Bytes> {
+        Ok(self
+            .send_binary_request(full_uri, context_token, context)
+            .await?
+            .into_body()
+            .collect()
+            .await
+            .map_err(Self::map_body_error)?
+            .to_bytes())
+    }
+
+    fn map_body_error(err: hyper::Error) -> SlackClientError {
+        SlackClientError::HttpProtocolError(
+            SlackClientHttpProtocolError::new().with_cause(Box::new(err)),
+        )
+    }
+
+    fn is_unavailable_error(err: &SlackClientError) -> bool {
+        match err {
+            SlackClientError::HttpProtocolError(_) => true,
+            SlackClientError::HttpError(http_err) => http_err.status_code.is_server_error(),
+            _ => false,
+        }
+    }
+
+    fn retry_delay(
+        config: &SlackApiRateControlConfig,
+        err: &SlackClientError,
+        idempotent: bool,
+        retried: usize,
+    ) -> Option<Duration> {
+        let backoff = &config.retry_backoff;
+        match err {
+            SlackClientError::RateLimitError(rate_error) => Some(
+                rate_error
+                    .retry_after
+                    .unwrap_or_else(|| backoff.delay_for(retried)),
+            ),
+            _ if idempotent && backoff.retry_server_errors && Self::is_unavailable_error(err) => {
+                Some(backoff.delay_for(retried))
+            }
+            _ => None,
+        }
+        .filter(|delay| {
+            config
+                .max_delay_timeout
+                .iter()
+                .all(|max_delay| delay <= max_delay)
+        })
+    }
+
     #[async_recursion]
     async fn send_rate_controlled_request<'a, R, RS>(
         &'a self,
@@ -207,6 +440,12 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
         R: Fn() -> ClientResult<Request<Body>> + Send + Sync,
         RS: for<'de> serde::de::Deserialize<'de> + Send,
     {
+        if retried == 0 {
+            if let Some(retry_budget) = self.retry_budget.as_ref() {
+                retry_budget.deposit();
+            }
+        }
+
         match (
             self.tokio_rate_controller.as_ref(),
             context.rate_control_params,
@@ -220,16 +459,21 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
                     )
                     .await;
 
+                let http_request = request()?;
+                // Only requests without side effects are safe to repeat after server errors
+                let idempotent = http_request.method() == hyper::http::Method::GET;
                 self.retry_request_if_needed(
                     rate_controller.clone(),
-                    self.send_http_request(request()?, context.clone()).await,
+                    self.send_guarded_http_request(http_request, context.clone())
+                        .await,
+                    idempotent,
                     retried,
                     request,
                     context,
                 )
                 .await
             }
-            (None, _) => self.send_http_request(request()?, context).await,
+            (None, _) => self.send_guarded_http_request(request()?, context).await,
         }
     }
 
@@ -237,6 +481,7 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
         &'a self,
         rate_controller: Arc<SlackTokioRateController>,
         result: ClientResult<RS>,
+        idempotent: bool,
         retried: usize,
         request: R,
         context: SlackClientApiCallContext<'a>,
@@ -247,27 +492,36 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHyperConnec
     {
         match result {
             Err(err) => match rate_controller.config.max_retries {
-                Some(max_retries) if max_retries > retried => match err {
-                    SlackClientError::RateLimitError(ref rate_error) => {
-                        context.tracing_span.in_scope(|| {
-                            debug!(
-                                "Rate limit error received: {}. Retrying: {}/{}",
-                                rate_error,
+                Some(max_retries) if max_retries > retried => {
+                    match Self::retry_delay(&rate_controller.config, &err, idempotent, retried) {
+                        Some(retry_delay)
+                            if self
+                                .retry_budget
+                                .as_ref()
+                                .map(|retry_budget| retry_budget.try_withdraw())
+                                .unwrap_or(true) =>
+                        {
+                            context.tracing_span.in_scope(|| {
+                                debug!(
+                                    "Slack API error received: {}. Retrying in {:?}: {}/{}",
+                                    err,
+                                    retry_delay,
+                                    retried + 1,
+                                    max_retries
+                                );
+                            });
+
+                            self.send_rate_controlled_request(
+                                request,
+                                context,
+                                Some(retry_delay),
                                 retried + 1,
-                                max_retries
-                            );
-                        });
-
-                        self.send_rate_controlled_request(
-                            request,
-                            context,
-                            rate_error.retry_after,
-                            retried + 1,
-                        )
-                        .await
+                            )
+                            .await
+                        }
+                        _ => Err(err),
                     }
-                    _ => Err(err),
-                },
+                }
                 _ => Err(err),
             },
             Ok(result) => Ok(result),
@@ -492,4 +746,189 @@ impl<H: 'static + Send + Sync + Clone + connect::Connect> SlackClientHttpConnect
         }
         .boxed()
     }
+
+    fn http_post_uri_stream<'a, RS>(
+        &'a self,
+        full_uri: Url,
+        content_type: String,
+        content_length: u64,
+        content: BoxStream<'static, std::io::Result<bytes::Bytes>>,
+        context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<RS>>
+    where
+        RS: for<'de> serde::de::Deserialize<'de> + Send + 'a,
+    {
+        let context_token = context.token;
+
+        async move {
+            // Hyper bodies can't fail here, so read errors stop the body and are reported after the request
+            let read_error: Arc<Mutex<Option<std::io::Error>>> = Arc::new(Mutex::new(None));
+            let body_read_error = read_error.clone();
+            let body_stream = content
+                .scan(body_read_error, |read_error, chunk| {
+                    std::future::ready(match chunk {
+                        Ok(bytes) => Some(Ok::<_, Infallible>(Frame::data(bytes))),
+                        Err(err) => {
+                            *read_error.lock().unwrap() = Some(err);
+                            None
+                        }
+                    })
+                })
+                .boxed();
+
+            let http_base_request =
+                HyperExtensions::create_http_request(full_uri, hyper::http::Method::POST)
+                    .header("content-type", content_type.as_str())
+                    .header("content-length", content_length.to_string());
+
+            let http_request =
+                HyperExtensions::setup_token_auth_header(http_base_request, context_token).body(
+                    BodyExt::boxed(StreamBody::new(SyncBodyStream::new(body_stream))),
+                )?;
+
+            if let Some(rate_controller) = self.tokio_rate_controller.as_ref() {
+                rate_controller
+                    .throttle_delay(
+                        context.rate_control_params,
+                        context_token.and_then(|t| t.team_id.clone()),
+                        None,
+                    )
+                    .await;
+            }
+
+            let result = self.send_guarded_http_request(http_request, context).await;
+
+            let maybe_read_error = read_error.lock().unwrap().take();
+            match maybe_read_error {
+                Some(err) => Err(SlackClientError::SystemError(
+                    SlackClientSystemError::new()
+                        .with_message("Unable to read streamed content".into())
+                        .with_cause(Box::new(err)),
+                )),
+                None => result,
+            }
+        }
+        .boxed()
+    }
+
+    fn http_get_uri_binary<'a>(
+        &'a self,
+        full_uri: Url,
+        context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<bytes::Bytes>> {
+        let context_token = context.token;
+
+        async move {
+            let permit = self.acquire_circuit_breaker_permit(full_uri.path())?;
+            let result = self
+                .download_binary_content(full_uri, context_token, context)
+                .await;
+            if let Some(permit) = permit {
+                permit.record(!result.as_ref().is_err_and(Self::is_unavailable_error));
+            }
+            result
+        }
+        .boxed()
+    }
+
+    fn http_get_uri_binary_stream<'a>(
+        &'a self,
+        full_uri: Url,
+        context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<BoxStream<'a, ClientResult<bytes::Bytes>>>> {
+        let context_token = context.token;
+
+        async move {
+            let permit = self.acquire_circuit_breaker_permit(full_uri.path())?;
+            let result = self
+                .send_binary_request(full_uri, context_token, context)
+                .await;
+            if let Some(permit) = permit {
+                permit.record(!result.as_ref().is_err_and(Self::is_unavailable_error));
+            }
+            Ok(http_body_util::BodyDataStream::new(result?.into_body())
+                .map(|chunk| chunk.map_err(Self::map_body_error))
+                .boxed())
+        }
+        .boxed()
+    }
+
+    fn app_rate_limited<'a>(
+        &'a self,
+        event: &'a crate::events::SlackAppRateLimitedEvent,
+    ) -> BoxFuture<'a, ()> {
+        async move {
+            if let Some(rate_controller) = self.tokio_rate_controller.as_ref() {
+                rate_controller.signal_app_rate_limited(event).await;
+            }
+        }
+        .boxed()
+    }
+}
+
+// Hyper client bodies must be `Sync`, which boxed streams are not
+struct SyncBodyStream<S> {
+    inner: Mutex<S>,
+}
+
+impl<S> SyncBodyStream<S> {
+    fn new(inner: S) -> Self {
+        Self {
+            inner: Mutex::new(inner),
+        }
+    }
+}
+
+impl<S> Stream for SyncBodyStream<S>
+where
+    S: Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut().inner.get_mut() {
+            Ok(inner) => inner.poll_next_unpin(cx),
+            Err(poisoned) => poisoned.into_inner().poll_next_unpin(cx),
+        }
+    }
+}
+
+#[test]
+fn check_retry_delay_decision() {
+    let config = SlackApiRateControlConfig::new().with_retry_backoff(
+        crate::ratectl::SlackApiRateControlRetryBackoffConfig::new()
+            .with_initial_delay(Duration::from_secs(1))
+            .with_retry_server_errors(true),
+    );
+    let server_error =
+        SlackClientError::HttpError(SlackClientHttpError::new(StatusCode::SERVICE_UNAVAILABLE));
+    let rate_limit_error = SlackClientError::RateLimitError(
+        SlackRateLimitError::new().with_retry_after(Duration::from_secs(5)),
+    );
+    let retry_delay = SlackClientHyperHttpsConnector::retry_delay;
+
+    assert_eq!(
+        retry_delay(&config, &server_error, true, 1),
+        Some(Duration::from_secs(2))
+    );
+    assert_eq!(retry_delay(&config, &server_error, false, 1), None);
+    assert_eq!(
+        retry_delay(&config, &rate_limit_error, false, 0),
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(
+        retry_delay(
+            &config
+                .clone()
+                .with_max_delay_timeout(Duration::from_secs(1)),
+            &rate_limit_error,
+            true,
+            0
+        ),
+        None
+    );
+    assert_eq!(
+        retry_delay(&SlackApiRateControlConfig::new(), &server_error, true, 0),
+        None
+    );
 }
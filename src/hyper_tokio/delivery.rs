@@ -0,0 +1,409 @@
+use crate::api::*;
+use crate::events::*;
+use crate::models::*;
+use crate::*;
+use futures::future::{BoxFuture, FutureExt};
+use rsb_derive::Builder;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackDeliveryTrackerConfig {
+    /// How long to wait for a message event before checking history
+    #[default = "SlackDeliveryTrackerConfig::DEFAULT_EVENT_TIMEOUT"]
+    pub event_timeout: Duration,
+    /// Observed messages kept to correlate events received before the post response
+    #[default = "SlackDeliveryTrackerConfig::DEFAULT_OBSERVED_CAPACITY"]
+    pub observed_capacity: usize,
+}
+
+impl SlackDeliveryTrackerConfig {
+    pub const DEFAULT_EVENT_TIMEOUT: Duration = Duration::from_secs(30);
+    pub const DEFAULT_OBSERVED_CAPACITY: usize = 1000;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SlackDeliveryStatus {
+    /// A message event has been received for the message
+    ConfirmedByEvent,
+    /// No message event in time, but the message has been found in history
+    ConfirmedByHistory,
+    /// The message has been posted, but is neither delivered as an event nor found in history
+    Dropped,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SlackDeliveryReceipt {
+    pub channel: SlackChannelId,
+    pub ts: SlackTs,
+    pub status: SlackDeliveryStatus,
+}
+
+impl SlackDeliveryReceipt {
+    pub fn is_delivered(&self) -> bool {
+        self.status != SlackDeliveryStatus::Dropped
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+enum SlackDeliveryKey {
+    Ts(SlackChannelId, SlackTs),
+    ClientMsgId(SlackClientMessageId),
+}
+
+#[derive(Default)]
+struct SlackDeliveryTrackerState {
+    pending: HashMap<SlackDeliveryKey, usize>,
+    senders: HashMap<usize, oneshot::Sender<()>>,
+    next_sender_id: usize,
+    observed: HashSet<SlackDeliveryKey>,
+    observed_order: VecDeque<SlackDeliveryKey>,
+}
+
+/// Pending delivery keys of a message, removed from the tracker when dropped
+struct SlackDeliveryRegistration<'t> {
+    tracker: &'t SlackDeliveryTracker,
+    keys: Vec<SlackDeliveryKey>,
+    // None if a message event has been already observed
+    pending: Option<(usize, oneshot::Receiver<()>)>,
+}
+
+impl Drop for SlackDeliveryRegistration<'_> {
+    fn drop(&mut self) {
+        if let Some((sender_id, _)) = self.pending.as_ref() {
+            let mut state = self.tracker.state.lock().unwrap();
+            for key in self.keys.iter() {
+                if state.pending.get(key) == Some(sender_id) {
+                    state.pending.remove(key);
+                }
+            }
+            state.senders.remove(sender_id);
+        }
+    }
+}
+
+/// Correlates posted messages with message events (by channel/ts or `client_msg_id`)
+/// and falls back to history reads, to confirm delivery and detect silent drops.
+/// Message events have to be passed to `observe_push_event` (or `observe_message_event`).
+pub struct SlackDeliveryTracker {
+    config: SlackDeliveryTrackerConfig,
+    state: Mutex<SlackDeliveryTrackerState>,
+}
+
+impl SlackDeliveryTracker {
+    pub fn new(config: SlackDeliveryTrackerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(SlackDeliveryTrackerState::default()),
+        }
+    }
+
+    pub fn observe_push_event(&self, event: &SlackPushEventCallback) {
+        if let SlackEventCallbackBody::Message(message_event) = &event.event {
+            self.observe_message_event(message_event);
+        }
+    }
+
+    pub fn observe_message_event(&self, event: &SlackMessageEvent) {
+        let keys = event
+            .origin
+            .channel
+            .as_ref()
+            .map(|channel| SlackDeliveryKey::Ts(channel.clone(), event.origin.ts.clone()))
+            .into_iter()
+            .chain(
+                event
+                    .origin
+                    .client_msg_id
+                    .clone()
+                    .map(SlackDeliveryKey::ClientMsgId),
+            );
+
+        let mut state = self.state.lock().unwrap();
+        for key in keys {
+            match state.pending.remove(&key) {
+                Some(sender_id) => {
+                    state
+                        .pending
+                        .retain(|_, pending_id| *pending_id != sender_id);
+                    if let Some(sender) = state.senders.remove(&sender_id) {
+                        let _ = sender.send(());
+                    }
+                }
+                None => {
+                    if state.observed.insert(key.clone()) {
+                        state.observed_order.push_back(key);
+                    }
+                    while state.observed_order.len() > self.config.observed_capacity {
+                        if let Some(oldest) = state.observed_order.pop_front() {
+                            state.observed.remove(&oldest);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Waits for a message posted by other means to be delivered
+    pub async fn confirm_delivery<SCHC>(
+        &self,
+        session: &SlackClientSession<'_, SCHC>,
+        channel: SlackChannelId,
+        ts: SlackTs,
+        thread_ts: Option<SlackTs>,
+        client_msg_id: Option<SlackClientMessageId>,
+    ) -> ClientResult<SlackDeliveryReceipt>
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        let keys: Vec<SlackDeliveryKey> =
+            std::iter::once(SlackDeliveryKey::Ts(channel.clone(), ts.clone()))
+                .chain(client_msg_id.map(SlackDeliveryKey::ClientMsgId))
+                .collect();
+        let registration = self.register(keys);
+        self.wait_for_delivery(session, registration, channel, ts, thread_ts)
+            .await
+    }
+
+    fn register(&self, keys: Vec<SlackDeliveryKey>) -> SlackDeliveryRegistration<'_> {
+        let mut state = self.state.lock().unwrap();
+        if keys.iter().any(|key| state.observed.contains(key)) {
+            return SlackDeliveryRegistration {
+                tracker: self,
+                keys,
+                pending: None,
+            };
+        }
+        let (sender, receiver) = oneshot::channel();
+        let sender_id = state.next_sender_id;
+        state.next_sender_id += 1;
+        state.senders.insert(sender_id, sender);
+        for key in keys.iter() {
+            state.pending.insert(key.clone(), sender_id);
+        }
+        SlackDeliveryRegistration {
+            tracker: self,
+            keys,
+            pending: Some((sender_id, receiver)),
+        }
+    }
+
+    async fn wait_for_delivery<SCHC>(
+        &self,
+        session: &SlackClientSession<'_, SCHC>,
+        mut registration: SlackDeliveryRegistration<'_>,
+        channel: SlackChannelId,
+        ts: SlackTs,
+        thread_ts: Option<SlackTs>,
+    ) -> ClientResult<SlackDeliveryReceipt>
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        let confirmed_by_event = match registration.pending.as_mut() {
+            None => true,
+            Some((_, receiver)) => matches!(
+                tokio::time::timeout(self.config.event_timeout, receiver).await,
+                Ok(Ok(()))
+            ),
+        };
+        drop(registration);
+
+        let status = if confirmed_by_event {
+            SlackDeliveryStatus::ConfirmedByEvent
+        } else if Self::find_in_history(session, &channel, &ts, thread_ts.as_ref()).await? {
+            SlackDeliveryStatus::ConfirmedByHistory
+        } else {
+            warn!("Message {}/{} has not been delivered", channel, ts);
+            SlackDeliveryStatus::Dropped
+        };
+
+        Ok(SlackDeliveryReceipt {
+            channel,
+            ts,
+            status,
+        })
+    }
+
+    async fn find_in_history<SCHC>(
+        session: &SlackClientSession<'_, SCHC>,
+        channel: &SlackChannelId,
+        ts: &SlackTs,
+        thread_ts: Option<&SlackTs>,
+    ) -> ClientResult<bool>
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        let messages = match thread_ts.filter(|thread_ts| *thread_ts != ts) {
+            Some(thread_ts) => {
+                session
+                    .conversations_replies(
+                        &SlackApiConversationsRepliesRequest::new(
+                            channel.clone(),
+                            thread_ts.clone(),
+                        )
+                        .with_oldest(ts.clone())
+                        .with_latest(ts.clone())
+                        .with_inclusive(true),
+                    )
+                    .await?
+                    .messages
+            }
+            None => {
+                session
+                    .conversations_history(
+                        &SlackApiConversationsHistoryRequest::new()
+                            .with_channel(channel.clone())
+                            .with_oldest(ts.clone())
+                            .with_latest(ts.clone())
+                            .with_inclusive(true)
+                            .with_limit(1),
+                    )
+                    .await?
+                    .messages
+            }
+        };
+        Ok(messages.iter().any(|message| message.origin.ts == *ts))
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Posts a message and returns a receipt future resolved when the delivery is confirmed
+    /// by a message event or history, or the message is detected as dropped.
+    pub async fn chat_post_message_with_receipt<'s>(
+        &'s self,
+        tracker: &'s SlackDeliveryTracker,
+        req: &SlackApiChatPostMessageRequest,
+    ) -> ClientResult<(
+        SlackApiChatPostMessageResponse,
+        BoxFuture<'s, ClientResult<SlackDeliveryReceipt>>,
+    )> {
+        let resp = self.chat_post_message(req).await?;
+        let keys = std::iter::once(SlackDeliveryKey::Ts(resp.channel.clone(), resp.ts.clone()))
+            .chain(
+                resp.message
+                    .origin
+                    .client_msg_id
+                    .clone()
+                    .map(SlackDeliveryKey::ClientMsgId),
+            )
+            .collect();
+        // Registered before returning to not miss events received while the receipt isn't awaited.
+        // Dropping the receipt future drops the registration as well.
+        let registration = tracker.register(keys);
+        let receipt = tracker
+            .wait_for_delivery(
+                self,
+                registration,
+                resp.channel.clone(),
+                resp.ts.clone(),
+                req.thread_ts.clone(),
+            )
+            .boxed();
+        Ok((resp, receipt))
+    }
+}
+
+#[test]
+fn check_delivery_correlation() {
+    let tracker = SlackDeliveryTracker::new(SlackDeliveryTrackerConfig::new());
+    let message_event = |channel: &str, ts: &str| {
+        SlackMessageEvent::new(
+            SlackMessageOrigin::new(ts.into()).with_channel(channel.into()),
+            SlackMessageSender::new(),
+        )
+    };
+
+    let mut registration = tracker.register(vec![SlackDeliveryKey::Ts("C1".into(), "1.1".into())]);
+    let (_, receiver) = registration.pending.as_mut().unwrap();
+    tracker.observe_message_event(&message_event("C1", "1.2"));
+    assert!(receiver.try_recv().is_err());
+    tracker.observe_message_event(&message_event("C1", "1.1"));
+    assert!(receiver.try_recv().is_ok());
+
+    // Events received before the post response are correlated as well
+    assert!(tracker
+        .register(vec![SlackDeliveryKey::Ts("C1".into(), "1.2".into())])
+        .pending
+        .is_none());
+
+    let mut registration = tracker.register(vec![
+        SlackDeliveryKey::Ts("C1".into(), "1.3".into()),
+        SlackDeliveryKey::ClientMsgId("client-msg-1".into()),
+    ]);
+    let (_, receiver) = registration.pending.as_mut().unwrap();
+    tracker.observe_message_event(&SlackMessageEvent::new(
+        SlackMessageOrigin::new("1.4".into()).with_client_msg_id("client-msg-1".into()),
+        SlackMessageSender::new(),
+    ));
+    assert!(receiver.try_recv().is_ok());
+}
+
+#[test]
+fn check_dropped_delivery_registration() {
+    let tracker = SlackDeliveryTracker::new(SlackDeliveryTrackerConfig::new());
+    let registration = tracker.register(vec![
+        SlackDeliveryKey::Ts("C1".into(), "1.1".into()),
+        SlackDeliveryKey::ClientMsgId("client-msg-1".into()),
+    ]);
+    drop(registration);
+
+    let state = tracker.state.lock().unwrap();
+    assert!(state.pending.is_empty());
+    assert!(state.senders.is_empty());
+}
+
+#[tokio::test]
+async fn check_delivery_history_fallback() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|call: &SlackTestCall| match call.method.as_str() {
+        "conversations.replies" => Ok(serde_json::json!({
+            "messages": [{ "ts": "1.1" }, { "ts": "1.2" }]
+        })),
+        _ => Ok(serde_json::json!({ "messages": [] })),
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let tracker = SlackDeliveryTracker::new(
+        SlackDeliveryTrackerConfig::new().with_event_timeout(Duration::from_millis(10)),
+    );
+
+    let in_thread = tracker
+        .confirm_delivery(
+            &session,
+            "C1".into(),
+            "1.2".into(),
+            Some("1.1".into()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(in_thread.status, SlackDeliveryStatus::ConfirmedByHistory);
+
+    let in_channel = tracker
+        .confirm_delivery(&session, "C1".into(), "1.3".into(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(in_channel.status, SlackDeliveryStatus::Dropped);
+    assert!(!in_channel.is_delivered());
+
+    let replies = connector.calls_of("conversations.replies");
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0].param("channel").as_deref(), Some("C1"));
+    assert_eq!(replies[0].param("ts").as_deref(), Some("1.1"));
+    assert_eq!(replies[0].param("oldest").as_deref(), Some("1.2"));
+    assert_eq!(replies[0].param("latest").as_deref(), Some("1.2"));
+
+    let history = connector.calls_of("conversations.history");
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].param("channel").as_deref(), Some("C1"));
+    assert_eq!(history[0].param("oldest").as_deref(), Some("1.3"));
+    assert_eq!(history[0].param("inclusive").as_deref(), Some("true"));
+}
@@ -0,0 +1,240 @@
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use bytes::BytesMut;
+use futures::stream::StreamExt;
+use rsb_derive::Builder;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SlackFileUploadProgress {
+    pub uploaded: u64,
+    pub total: u64,
+}
+
+pub type SlackFileUploadProgressFn = Arc<dyn Fn(SlackFileUploadProgress) + Send + Sync>;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackFileStreamUploadRequest {
+    /// Defaults to the file name for path uploads
+    pub filename: Option<String>,
+    /// Defaults to the MIME type guessed from the file name
+    pub content_type: Option<String>,
+    pub title: Option<String>,
+    pub alt_txt: Option<String>,
+    pub snippet_type: Option<SlackFileSnippetType>,
+    pub channel_id: Option<SlackChannelId>,
+    pub initial_comment: Option<String>,
+    pub thread_ts: Option<SlackTs>,
+    #[default = "SlackFileStreamUploadRequest::DEFAULT_CHUNK_SIZE"]
+    pub chunk_size: usize,
+}
+
+impl SlackFileStreamUploadRequest {
+    pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Uploads a file from disk streaming its content in chunks,
+    /// using `files.getUploadURLExternal` and `files.completeUploadExternal`.
+    pub async fn files_upload_from_path<P>(
+        &self,
+        path: P,
+        req: &SlackFileStreamUploadRequest,
+        progress: Option<SlackFileUploadProgressFn>,
+    ) -> ClientResult<SlackApiFilesCompleteUploadExternalResponse>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = tokio::fs::File::open(path).await.map_err(map_read_error)?;
+        let length = file.metadata().await.map_err(map_read_error)?.len();
+        let req = match (req.filename.as_ref(), path.file_name()) {
+            (None, Some(filename)) => req
+                .clone()
+                .with_filename(filename.to_string_lossy().to_string()),
+            _ => req.clone(),
+        };
+        self.files_upload_from_reader(file, length, &req, progress)
+            .await
+    }
+
+    /// Uploads `length` bytes read from `reader` streaming them in chunks,
+    /// so memory usage is bounded by `req.chunk_size`.
+    /// The upload fails if the reader ends before `length` bytes.
+    pub async fn files_upload_from_reader<R>(
+        &self,
+        reader: R,
+        length: u64,
+        req: &SlackFileStreamUploadRequest,
+        progress: Option<SlackFileUploadProgressFn>,
+    ) -> ClientResult<SlackApiFilesCompleteUploadExternalResponse>
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let filename = req.filename.clone().unwrap_or_else(|| "file".to_string());
+        let content_type = req.content_type.clone().unwrap_or_else(|| {
+            mime_guess::MimeGuess::from_path(&filename)
+                .first_or_octet_stream()
+                .to_string()
+        });
+
+        let upload_url_resp = self
+            .get_upload_url_external(
+                &SlackApiFilesGetUploadUrlExternalRequest::new(filename, length as usize)
+                    .opt_alt_txt(req.alt_txt.clone())
+                    .opt_snippet_type(req.snippet_type.clone()),
+            )
+            .await?;
+
+        self.files_upload_via_url_stream(
+            &upload_url_resp.upload_url,
+            content_type,
+            length,
+            read_chunks(reader, length, req.chunk_size.max(1), progress),
+        )
+        .await?;
+
+        self.files_complete_upload_external(
+            &SlackApiFilesCompleteUploadExternalRequest::new(vec![SlackApiFilesComplete::new(
+                upload_url_resp.file_id,
+            )
+            .opt_title(req.title.clone())])
+            .opt_channel_id(req.channel_id.clone())
+            .opt_initial_comment(req.initial_comment.clone())
+            .opt_thread_ts(req.thread_ts.clone()),
+        )
+        .await
+    }
+}
+
+fn read_chunks<R>(
+    reader: R,
+    length: u64,
+    chunk_size: usize,
+    progress: Option<SlackFileUploadProgressFn>,
+) -> futures::stream::BoxStream<'static, std::io::Result<bytes::Bytes>>
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    let uploaded = Arc::new(AtomicU64::new(0));
+    // Reading no more than declared length, since it has been already sent to Slack
+    futures::stream::try_unfold(reader.take(length), move |mut reader| async move {
+        let mut buf = BytesMut::with_capacity(chunk_size);
+        while buf.len() < chunk_size {
+            if reader.read_buf(&mut buf).await? == 0 {
+                break;
+            }
+        }
+        if !buf.is_empty() {
+            Ok(Some((buf.freeze(), reader)))
+        } else if reader.limit() > 0 {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "File content is shorter than its declared length: {} bytes are missing",
+                    reader.limit()
+                ),
+            ))
+        } else {
+            Ok(None)
+        }
+    })
+    .inspect(move |chunk| {
+        if let (Ok(chunk), Some(progress)) = (chunk, progress.as_ref()) {
+            let uploaded =
+                uploaded.fetch_add(chunk.len() as u64, Ordering::Relaxed) + chunk.len() as u64;
+            progress(SlackFileUploadProgress {
+                uploaded,
+                total: length,
+            });
+        }
+    })
+    .boxed()
+}
+
+fn map_read_error(err: std::io::Error) -> SlackClientError {
+    SlackClientError::SystemError(
+        SlackClientSystemError::new()
+            .with_message("Unable to read the file to upload".into())
+            .with_cause(Box::new(err)),
+    )
+}
+
+#[tokio::test]
+async fn check_read_chunks() {
+    use futures::stream::TryStreamExt;
+    use std::sync::Mutex;
+
+    let progress_updates = Arc::new(Mutex::new(Vec::new()));
+    let progress: SlackFileUploadProgressFn = {
+        let progress_updates = progress_updates.clone();
+        Arc::new(move |progress| progress_updates.lock().unwrap().push(progress.uploaded))
+    };
+    let chunks: Vec<bytes::Bytes> = read_chunks(
+        std::io::Cursor::new(b"0123456789extra".to_vec()),
+        10,
+        4,
+        Some(progress),
+    )
+    .try_collect()
+    .await
+    .unwrap();
+    assert_eq!(chunks, vec!["0123", "4567", "89"]);
+    assert_eq!(*progress_updates.lock().unwrap(), vec![4, 8, 10]);
+
+    let short_result: std::io::Result<Vec<bytes::Bytes>> =
+        read_chunks(std::io::Cursor::new(b"0123".to_vec()), 10, 4, None)
+            .try_collect()
+            .await;
+    assert_eq!(
+        short_result.unwrap_err().kind(),
+        std::io::ErrorKind::UnexpectedEof
+    );
+}
+
+#[tokio::test]
+async fn check_files_upload_from_reader() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|call: &SlackTestCall| match call.method.as_str() {
+        "files.getUploadURLExternal" => Ok(serde_json::json!({
+            "ok": true,
+            "upload_url": "https://files.slack.com/upload/v1/F1",
+            "file_id": "F1"
+        })),
+        "files.completeUploadExternal" => Ok(serde_json::json!({ "ok": true, "files": [] })),
+        _ => Ok(serde_json::json!({})),
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    session
+        .files_upload_from_reader(
+            std::io::Cursor::new(b"hello world".to_vec()),
+            11,
+            &SlackFileStreamUploadRequest::new()
+                .with_filename("hello.txt".into())
+                .with_chunk_size(4),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let get_url_call = &connector.calls_of("files.getUploadURLExternal")[0];
+    assert_eq!(get_url_call.param("filename").as_deref(), Some("hello.txt"));
+    assert_eq!(get_url_call.param("length").as_deref(), Some("11"));
+    assert_eq!(
+        connector.calls_of("/upload/v1/F1")[0].body,
+        Some(serde_json::Value::String("hello world".into()))
+    );
+    assert_eq!(connector.calls_of("files.completeUploadExternal").len(), 1);
+}
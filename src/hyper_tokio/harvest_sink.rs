@@ -0,0 +1,88 @@
+use crate::models::*;
+use crate::*;
+use futures::future::{BoxFuture, FutureExt};
+use std::path::{Path, PathBuf};
+
+/// Stores files in a directory as `<file id>-<file name>`.
+/// Files are written to a temporary `.part` file first,
+/// so partially downloaded files are never treated as harvested.
+pub struct SlackDirectoryFileSink {
+    dir: PathBuf,
+}
+
+impl SlackDirectoryFileSink {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn file_path(&self, file: &SlackFile) -> PathBuf {
+        let name: String = file
+            .name
+            .as_deref()
+            .unwrap_or("file")
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                _ => c,
+            })
+            .collect();
+        self.dir.join(format!("{}-{}", file.id, name))
+    }
+
+    // Appending the extension to keep the whole file name (e.g. `a.tar.gz.part`)
+    fn part_path(path: &Path) -> PathBuf {
+        let mut part_name = path.file_name().unwrap_or_default().to_os_string();
+        part_name.push(".part");
+        path.with_file_name(part_name)
+    }
+}
+
+impl SlackFileHarvestSink for SlackDirectoryFileSink {
+    fn contains<'a>(&'a self, file: &'a SlackFile) -> BoxFuture<'a, bool> {
+        async move {
+            tokio::fs::try_exists(self.file_path(file))
+                .await
+                .unwrap_or(false)
+        }
+        .boxed()
+    }
+
+    fn write_file<'a>(
+        &'a mut self,
+        file: &'a SlackFile,
+        content: bytes::Bytes,
+    ) -> BoxFuture<'a, AnyStdResult<()>> {
+        async move {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            let path = self.file_path(file);
+            let part_path = Self::part_path(&path);
+            tokio::fs::write(&part_path, content).await?;
+            tokio::fs::rename(&part_path, &path).await?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+#[tokio::test]
+async fn check_directory_file_sink() {
+    let dir = std::env::temp_dir().join(format!("slack-morphism-harvest-{}", std::process::id()));
+    let mut sink = SlackDirectoryFileSink::new(&dir);
+    let file = SlackFile::new("F1".into(), SlackFileFlags::new()).with_name("logs/a.tar.gz".into());
+
+    let path = sink.file_path(&file);
+    assert_eq!(path, dir.join("F1-logs_a.tar.gz"));
+    assert_eq!(
+        SlackDirectoryFileSink::part_path(&path),
+        dir.join("F1-logs_a.tar.gz.part")
+    );
+
+    assert!(!sink.contains(&file).await);
+    sink.write_file(&file, bytes::Bytes::from_static(b"content"))
+        .await
+        .unwrap();
+    assert!(sink.contains(&file).await);
+    assert_eq!(std::fs::read(&path).unwrap(), b"content");
+    assert!(!SlackDirectoryFileSink::part_path(&path).exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,106 @@
+use crate::*;
+use rsb_derive::Builder;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackHealthCheckerConfig {
+    #[default = "SlackHealthCheckerConfig::DEFAULT_INTERVAL"]
+    pub interval: Duration,
+    /// Prewarm connections with the first check right after start
+    #[default = "true"]
+    pub check_on_start: bool,
+}
+
+impl SlackHealthCheckerConfig {
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+}
+
+/// Hook to export health statuses (e.g. to metrics)
+pub type SlackHealthStatusHook = Arc<dyn Fn(&SlackClientHealthStatus) + Send + Sync>;
+
+/// Periodically checks Slack Web API with `health_check` and reports statuses to the hook.
+/// Checks also keep pooled connections warm between real API calls.
+/// Checks stop when the checker is dropped.
+pub struct SlackHealthChecker {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl SlackHealthChecker {
+    pub fn start<SCHC>(
+        client: Arc<SlackClient<SCHC>>,
+        config: SlackHealthCheckerConfig,
+        hook: SlackHealthStatusHook,
+    ) -> Self
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+    {
+        let task = tokio::spawn(Self::run_checks(client, config, hook));
+        Self { task }
+    }
+
+    /// Stops checks (the same as dropping the checker)
+    pub fn stop(self) {}
+
+    async fn run_checks<SCHC>(
+        client: Arc<SlackClient<SCHC>>,
+        config: SlackHealthCheckerConfig,
+        hook: SlackHealthStatusHook,
+    ) where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        if !config.check_on_start {
+            tokio::time::sleep(config.interval).await;
+        }
+
+        loop {
+            let status = client.health_check().await;
+            match status.error.as_ref() {
+                None => debug!("Slack API is healthy. Latency: {:?}", status.latency),
+                Some(err) => warn!("Slack API health check failed: {}", err),
+            }
+            hook(&status);
+
+            tokio::time::sleep(config.interval).await;
+        }
+    }
+}
+
+impl Drop for SlackHealthChecker {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[tokio::test]
+async fn check_health_checker_reports() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|_| Ok(serde_json::json!({ "ok": true })));
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let checker = SlackHealthChecker::start(
+        Arc::new(SlackClient::new(connector.clone())),
+        SlackHealthCheckerConfig::new().with_interval(Duration::from_millis(1)),
+        Arc::new(move |status: &SlackClientHealthStatus| {
+            let _ = sender.send(status.clone());
+        }),
+    );
+
+    for _ in 0..2 {
+        let status = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(status.is_healthy());
+    }
+
+    checker.stop();
+    // The hook (and its sender) is dropped with the aborted checks task
+    while tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .unwrap()
+        .is_some()
+    {}
+    assert!(connector.calls_of("api.test").len() >= 2);
+}
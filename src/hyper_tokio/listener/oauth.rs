@@ -5,7 +5,7 @@ use crate::hyper_tokio::{Body, SlackClientEventsHyperListener};
 use crate::api::*;
 use crate::errors::*;
 use crate::listener::*;
-use crate::{AnyStdResult, SlackClient, SlackClientHttpApiUri};
+use crate::{AnyStdResult, SlackApiEndpoints, SlackClient, SlackClientHttpApiUri};
 
 use futures::future::{BoxFuture, FutureExt};
 use hyper::body::Incoming;
@@ -20,18 +20,9 @@ impl<H: 'static + Send + Sync + Connect + Clone> SlackClientEventsHyperListener<
     pub(crate) async fn slack_oauth_install_service(
         _: Request<Incoming>,
         config: &SlackOAuthListenerConfig,
+        endpoints: &SlackApiEndpoints,
     ) -> AnyStdResult<Response<Body>> {
-        let full_uri = SlackClientHttpApiUri::create_url_with_params(
-            SlackOAuthListenerConfig::OAUTH_AUTHORIZE_URL_VALUE.parse()?,
-            &vec![
-                ("client_id", Some(config.client_id.value())),
-                ("scope", Some(&config.bot_scope)),
-                (
-                    "redirect_uri",
-                    Some(config.to_redirect_url()?.as_str().to_string()).as_ref(),
-                ),
-            ],
-        )?;
+        let full_uri = config.to_authorize_url_with(endpoints, None)?;
         debug!("Redirecting to Slack OAuth authorize: {}", &full_uri);
         HyperExtensions::hyper_redirect_to(full_uri.as_ref())
     }
@@ -67,13 +58,18 @@ impl<H: 'static + Send + Sync + Connect + Clone> SlackClientEventsHyperListener<
                 match oauth_access_resp {
                     Ok(oauth_resp) => {
                         info!(
-                            "Received slack OAuth access resp for: {} / {} / {}",
-                            &oauth_resp.team.id,
+                            "Received slack OAuth access resp for: {:?} / {} / {}",
+                            oauth_resp.installation_key(),
                             &oauth_resp
                                 .team
                                 .name
-                                .as_ref()
-                                .cloned()
+                                .clone()
+                                .or_else(|| {
+                                    oauth_resp
+                                        .enterprise
+                                        .as_ref()
+                                        .and_then(|enterprise| enterprise.name.clone())
+                                })
                                 .unwrap_or_else(|| "".into()),
                             &oauth_resp.authed_user.id
                         );
@@ -143,7 +139,7 @@ impl<H: 'static + Send + Sync + Connect + Clone> SlackClientEventsHyperListener<
             async move {
                 match (req.method(), req.uri().path()) {
                     (&Method::GET, url) if url == cfg.install_path => {
-                        Self::slack_oauth_install_service(req, &cfg).await
+                        Self::slack_oauth_install_service(req, &cfg, sc.endpoints()).await
                     }
                     (&Method::GET, url) if url == cfg.redirect_callback_path => {
                         Self::slack_oauth_callback_service(
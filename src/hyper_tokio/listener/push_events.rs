@@ -85,6 +85,18 @@ impl<H: 'static + Send + Sync + Connect + Clone> SlackClientEventsHyperListener<
                                     }
                                     other => match other {
                                         Ok(push_event) => {
+                                            if let Some(api_cache) = sc.api_cache.as_ref() {
+                                                api_cache.invalidate_by_push_event(&push_event);
+                                            }
+                                            if let SlackPushEvent::AppRateLimited(
+                                                ref rate_limited_event,
+                                            ) = push_event
+                                            {
+                                                sc.http_api
+                                                    .connector
+                                                    .app_rate_limited(rate_limited_event)
+                                                    .await;
+                                            }
                                             match push_service_fn(
                                                 push_event,
                                                 sc.clone(),
@@ -11,19 +11,38 @@ use std::convert::Infallible;
 
 use crate::*;
 
+mod audit_logs_tail;
+mod broadcast;
+mod bulk_actions;
 pub mod connector;
+mod delivery;
+mod file_upload;
+mod harvest_sink;
+mod health_checker;
 pub mod hyper_errors;
 pub(crate) mod hyper_ext;
 pub mod listener;
+mod paced_calls;
+mod presence;
 mod ratectl;
 pub mod scroller_ext;
 mod socket_mode;
+mod users_by_emails;
 
 use crate::listener::SlackClientEventsListenerEnvironment;
+pub use audit_logs_tail::*;
+pub use broadcast::*;
+pub use bulk_actions::*;
+pub use delivery::*;
+pub use file_upload::*;
+pub use harvest_sink::*;
+pub use health_checker::*;
 pub use listener::chain_service_routes_fn;
 pub use listener::SlackClientEventsHyperListener;
+pub use presence::*;
 pub use scroller_ext::SlackApiResponseScrollerExt;
 pub use socket_mode::*;
+pub use users_by_emails::*;
 
 pub type SlackHyperClient = SlackClient<SlackClientHyperHttpsConnector>;
 
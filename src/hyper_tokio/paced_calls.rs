@@ -0,0 +1,56 @@
+use crate::errors::*;
+use crate::ClientResult;
+use std::future::Future;
+use std::time::Duration;
+use tracing::*;
+
+// Errors meaning that nothing can be done with this token anymore
+const FATAL_ERROR_CODES: &[&str] = &[
+    "invalid_auth",
+    "not_authed",
+    "account_inactive",
+    "token_revoked",
+    "token_expired",
+    "team_access_not_granted",
+    "missing_scope",
+];
+
+/// Error code of an error that stops a paced run over many targets
+pub(crate) fn fatal_error_code(err: &SlackClientError) -> Option<&str> {
+    match err {
+        SlackClientError::ApiError(api_err)
+            if FATAL_ERROR_CODES.contains(&api_err.code.as_str()) =>
+        {
+            Some(api_err.code.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Repeats a call after rate limit errors, waiting for `retry_after` (or `default_delay`)
+pub(crate) async fn retry_rate_limited<T, F, FR>(
+    target: &str,
+    max_retries: usize,
+    default_delay: Duration,
+    mut call: F,
+) -> ClientResult<T>
+where
+    F: FnMut() -> FR,
+    FR: Future<Output = ClientResult<T>>,
+{
+    let mut retried = 0;
+    loop {
+        match call().await {
+            Err(SlackClientError::RateLimitError(rate_limit_err)) if retried < max_retries => {
+                retried += 1;
+                let delay = rate_limit_err.retry_after.unwrap_or(default_delay);
+                debug!(
+                    "{} is rate limited. Retrying {}/{} in {:?}",
+                    target, retried, max_retries, delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackPresenceWatcherConfig {
+    /// Delay between polling rounds over all watched users
+    #[default = "SlackPresenceWatcherConfig::DEFAULT_POLL_INTERVAL"]
+    pub poll_interval: Duration,
+    /// Delay between `users.getPresence` calls inside a round
+    #[default = "SlackPresenceWatcherConfig::DEFAULT_REQUEST_PACING"]
+    pub request_pacing: Duration,
+}
+
+impl SlackPresenceWatcherConfig {
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+    // users.getPresence is a Tier 3 method (50+ per minute)
+    pub const DEFAULT_REQUEST_PACING: Duration = Duration::from_millis(1200);
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackPresenceChange {
+    pub user: SlackUserId,
+    /// None for the first observed presence of a user
+    pub previous: Option<String>,
+    pub presence: String,
+}
+
+/// Tracks presence of watched users polling `users.getPresence`
+/// and sends changes to the receiver returned from `SlackPresenceWatcher::start`.
+/// Slack RTM API isn't supported by the library, so `presence_sub` isn't available.
+/// Polling stops when the watcher or the receiver is dropped.
+pub struct SlackPresenceWatcher {
+    users: Arc<RwLock<Vec<SlackUserId>>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl SlackPresenceWatcher {
+    pub fn start<SCHC>(
+        client: Arc<SlackClient<SCHC>>,
+        token: SlackApiToken,
+        users: Vec<SlackUserId>,
+        config: SlackPresenceWatcherConfig,
+    ) -> (Self, UnboundedReceiver<SlackPresenceChange>)
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+    {
+        let users = Arc::new(RwLock::new(users));
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(Self::poll_presence(
+            client,
+            token,
+            users.clone(),
+            config,
+            sender,
+        ));
+        (Self { users, task }, receiver)
+    }
+
+    pub fn watch(&self, user: SlackUserId) {
+        let mut users = self.users.write().unwrap();
+        if !users.contains(&user) {
+            users.push(user);
+        }
+    }
+
+    pub fn unwatch(&self, user: &SlackUserId) {
+        self.users
+            .write()
+            .unwrap()
+            .retain(|watched| watched != user);
+    }
+
+    pub fn watched_users(&self) -> Vec<SlackUserId> {
+        self.users.read().unwrap().clone()
+    }
+
+    /// Stops polling (the same as dropping the watcher)
+    pub fn stop(self) {}
+
+    async fn poll_presence<SCHC>(
+        client: Arc<SlackClient<SCHC>>,
+        token: SlackApiToken,
+        users: Arc<RwLock<Vec<SlackUserId>>>,
+        config: SlackPresenceWatcherConfig,
+        sender: UnboundedSender<SlackPresenceChange>,
+    ) where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        let session = client.open_session(&token);
+        let mut known: HashMap<SlackUserId, String> = HashMap::new();
+
+        loop {
+            let round_users = users.read().unwrap().clone();
+            known.retain(|user, _| round_users.contains(user));
+
+            for user in round_users {
+                if sender.is_closed() {
+                    return;
+                }
+
+                match session
+                    .users_get_presence(&SlackApiUsersGetPresenceRequest::new(user.clone()))
+                    .await
+                {
+                    Ok(resp) => {
+                        let previous = known.insert(user.clone(), resp.presence.clone());
+                        if previous.as_ref() != Some(&resp.presence)
+                            && sender
+                                .send(SlackPresenceChange {
+                                    user,
+                                    previous,
+                                    presence: resp.presence,
+                                })
+                                .is_err()
+                        {
+                            return;
+                        }
+                    }
+                    Err(SlackClientError::RateLimitError(rate_limit_err)) => {
+                        let delay = rate_limit_err.retry_after.unwrap_or(config.poll_interval);
+                        debug!("Presence polling is rate limited. Waiting {:?}", delay);
+                        tokio::time::sleep(delay).await;
+                    }
+                    Err(err) => {
+                        warn!("Unable to get presence for user {}: {}", user, err);
+                    }
+                }
+
+                tokio::time::sleep(config.request_pacing).await;
+            }
+
+            tokio::time::sleep(config.poll_interval).await;
+        }
+    }
+}
+
+impl Drop for SlackPresenceWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[tokio::test]
+async fn check_presence_watcher_changes() {
+    use crate::test_connector::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let polls = AtomicUsize::new(0);
+    let connector =
+        SlackTestConnector::new(
+            move |call: &SlackTestCall| match call.param("user").as_deref() {
+                Some("U1") => {
+                    let presence = match polls.fetch_add(1, Ordering::SeqCst) {
+                        0 | 1 => "active",
+                        _ => "away",
+                    };
+                    Ok(serde_json::json!({ "ok": true, "presence": presence }))
+                }
+                _ => Err(SlackClientError::ApiError(SlackClientApiError::new(
+                    "user_not_found".into(),
+                ))),
+            },
+        );
+    let (watcher, mut changes) = SlackPresenceWatcher::start(
+        Arc::new(SlackClient::new(connector)),
+        SlackApiToken::new("xoxb-test".into()),
+        vec!["U1".into(), "U2".into()],
+        SlackPresenceWatcherConfig::new()
+            .with_poll_interval(Duration::from_millis(1))
+            .with_request_pacing(Duration::ZERO),
+    );
+
+    let timeout = Duration::from_secs(5);
+    assert_eq!(
+        tokio::time::timeout(timeout, changes.recv()).await.unwrap(),
+        Some(SlackPresenceChange {
+            user: "U1".into(),
+            previous: None,
+            presence: "active".into()
+        })
+    );
+    // Unchanged presence isn't reported and failing users don't stop polling
+    assert_eq!(
+        tokio::time::timeout(timeout, changes.recv()).await.unwrap(),
+        Some(SlackPresenceChange {
+            user: "U1".into(),
+            previous: Some("active".into()),
+            presence: "away".into()
+        })
+    );
+
+    watcher.watch("U1".into());
+    watcher.unwatch(&"U2".into());
+    assert_eq!(watcher.watched_users(), vec!["U1".into()]);
+
+    watcher.stop();
+    // The sender is dropped with the aborted polling task
+    assert_eq!(
+        tokio::time::timeout(timeout, changes.recv()).await.unwrap(),
+        None
+    );
+}
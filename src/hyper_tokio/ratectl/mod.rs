@@ -1,8 +1,10 @@
+use crate::models::events::*;
 use crate::models::*;
 use crate::ratectl::*;
 
+use rvstruct::ValueStruct;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tracing::*;
 
@@ -64,4 +66,19 @@ impl SlackTokioRateController {
             }
         }
     }
+
+    /// Slows down API calls for the team, because Slack throttles event deliveries to the app
+    pub async fn signal_app_rate_limited(&self, event: &SlackAppRateLimitedEvent) {
+        warn!(
+            "Slack throttles event deliveries for team {} (minute {}). Slowing down API calls",
+            event.team_id, event.minute_rate_limited.0
+        );
+        let mut throttler = self.throttler.lock().await;
+        throttler.signal_backpressure(SlackTeamId::new(event.team_id.clone()));
+    }
+
+    /// Until when API calls for the team are slowed down after `app_rate_limited` events
+    pub async fn backpressure_until(&self, team_id: &SlackTeamId) -> Option<Instant> {
+        self.throttler.lock().await.backpressure_until(team_id)
+    }
 }
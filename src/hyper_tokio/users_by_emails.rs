@@ -0,0 +1,180 @@
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::ratectl::*;
+use crate::*;
+use futures::stream::StreamExt;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use std::collections::HashMap;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackBulkResolveConfig {
+    #[default = "SlackBulkResolveConfig::DEFAULT_CONCURRENCY"]
+    pub concurrency: usize,
+    #[default = "SlackBulkResolveConfig::DEFAULT_MAX_RETRIES"]
+    pub max_retries: usize,
+    /// Delays of retries without `Retry-After`
+    #[default = "SlackApiRateControlRetryBackoffConfig::new()"]
+    pub retry_backoff: SlackApiRateControlRetryBackoffConfig,
+}
+
+impl SlackBulkResolveConfig {
+    pub const DEFAULT_CONCURRENCY: usize = 4;
+    pub const DEFAULT_MAX_RETRIES: usize = 2;
+}
+
+#[derive(Debug)]
+pub struct SlackUsersByEmailsResolution {
+    pub found: HashMap<EmailAddress, SlackUser>,
+    pub not_found: Vec<EmailAddress>,
+    pub failed: Vec<(EmailAddress, SlackClientError)>,
+}
+
+impl SlackUsersByEmailsResolution {
+    pub fn user_ids(&self) -> HashMap<EmailAddress, SlackUserId> {
+        self.found
+            .iter()
+            .map(|(email, user)| (email.clone(), user.id.clone()))
+            .collect()
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Resolves email addresses to users using `users.lookupByEmail` with limited concurrency.
+    /// Transient errors (such as rate limits or network issues) are retried up to `max_retries`
+    /// after `Retry-After` or the backoff delay.
+    pub async fn resolve_users_by_emails(
+        &self,
+        emails: &[EmailAddress],
+        config: &SlackBulkResolveConfig,
+    ) -> SlackUsersByEmailsResolution {
+        let mut unique_emails: Vec<&EmailAddress> = emails.iter().collect();
+        unique_emails.sort_by(|a, b| a.value().cmp(b.value()));
+        unique_emails.dedup();
+
+        let results: Vec<(EmailAddress, ClientResult<SlackUser>)> =
+            futures::stream::iter(unique_emails)
+                .map(|email| async move {
+                    (
+                        email.clone(),
+                        self.lookup_user_by_email_with_retries(email, config).await,
+                    )
+                })
+                .buffer_unordered(config.concurrency.max(1))
+                .collect()
+                .await;
+
+        let mut resolution = SlackUsersByEmailsResolution {
+            found: HashMap::new(),
+            not_found: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        for (email, result) in results {
+            match result {
+                Ok(user) => {
+                    resolution.found.insert(email, user);
+                }
+                Err(SlackClientError::ApiError(ref api_err))
+                    if api_err.code == SLACK_USERS_NOT_FOUND_ERROR_CODE =>
+                {
+                    resolution.not_found.push(email);
+                }
+                Err(err) => {
+                    resolution.failed.push((email, err));
+                }
+            }
+        }
+
+        resolution
+    }
+
+    async fn lookup_user_by_email_with_retries(
+        &self,
+        email: &EmailAddress,
+        config: &SlackBulkResolveConfig,
+    ) -> ClientResult<SlackUser> {
+        let mut retried = 0;
+        loop {
+            match self
+                .users_lookup_by_email(&SlackApiUsersLookupByEmailRequest::new(email.clone()))
+                .await
+            {
+                Ok(resp) => return Ok(resp.user),
+                Err(err) if retried < config.max_retries && err.is_transient() => {
+                    let delay = match err {
+                        SlackClientError::RateLimitError(ref rate_limit_err) => rate_limit_err
+                            .retry_after
+                            .unwrap_or_else(|| config.retry_backoff.delay_for(retried)),
+                        _ => config.retry_backoff.delay_for(retried),
+                    };
+                    retried += 1;
+                    debug!(
+                        "Retrying users.lookupByEmail ({}/{}) in {:?} after: {}",
+                        retried, config.max_retries, delay, err
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+const SLACK_USERS_NOT_FOUND_ERROR_CODE: &str = "users_not_found";
+
+#[tokio::test]
+async fn check_users_by_emails_retries() {
+    use crate::test_connector::*;
+    use std::time::Duration;
+
+    let connector =
+        SlackTestConnector::new(
+            |call: &SlackTestCall| match call.param("email").as_deref() {
+                Some("flaky@example.com") => Err(SlackClientError::RateLimitError(
+                    SlackRateLimitError::new().with_retry_after(Duration::from_millis(1)),
+                )),
+                Some("missing@example.com") => Err(SlackClientError::ApiError(
+                    SlackClientApiError::new("users_not_found".into()),
+                )),
+                _ => Ok(serde_json::json!({
+                    "ok": true,
+                    "user": { "id": "U1" }
+                })),
+            },
+        );
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let resolution = session
+        .resolve_users_by_emails(
+            &[
+                "user@example.com".into(),
+                "flaky@example.com".into(),
+                "missing@example.com".into(),
+            ],
+            &SlackBulkResolveConfig::new().with_max_retries(2),
+        )
+        .await;
+
+    assert_eq!(
+        resolution.user_ids().get(&"user@example.com".into()),
+        Some(&"U1".into())
+    );
+    assert_eq!(resolution.not_found, vec!["missing@example.com".into()]);
+    assert_eq!(resolution.failed.len(), 1);
+    assert_eq!(
+        connector
+            .calls_of("users.lookupByEmail")
+            .iter()
+            .filter(|call| call.param("email").as_deref() == Some("flaky@example.com"))
+            .count(),
+        3
+    );
+}
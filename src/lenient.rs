@@ -0,0 +1,296 @@
+//!
+//! Tolerant deserialization: parses a payload skipping the fields that don't match the models
+//! and reports them, instead of failing the whole payload when Slack changes shapes.
+//!
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SlackLenientPathSegment {
+    Field(String),
+    Index(usize),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SlackLenientFieldError {
+    /// Path of the skipped value (as in the original payload), empty for the payload itself
+    pub path: Vec<SlackLenientPathSegment>,
+    pub message: String,
+}
+
+impl SlackLenientFieldError {
+    /// Path formatted like `event.blocks[2].text`
+    pub fn path_string(&self) -> String {
+        format_path(&self.path)
+    }
+}
+
+impl std::fmt::Display for SlackLenientFieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "<payload>: {}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path_string(), self.message)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SlackLenientParsed<T> {
+    /// Parsed model without the skipped values, or `None` if the payload can't be parsed at all
+    pub value: Option<T>,
+    /// Skipped values in the order they were found
+    pub errors: Vec<SlackLenientFieldError>,
+}
+
+impl<T> SlackLenientParsed<T> {
+    /// Parsed without skipping anything
+    pub fn is_complete(&self) -> bool {
+        self.value.is_some() && self.errors.is_empty()
+    }
+}
+
+const MAX_LENIENT_ERRORS: usize = 100;
+
+/// Parses JSON into a model skipping values that fail to deserialize.
+/// Values are skipped until the rest of the payload matches the model.
+/// If a skipped value is required, the object containing it is skipped as well.
+/// Returns an error only for invalid JSON.
+pub fn parse_leniently<T>(json: &str) -> serde_json::Result<SlackLenientParsed<T>>
+where
+    T: DeserializeOwned,
+{
+    Ok(parse_value_leniently(serde_json::from_str(json)?))
+}
+
+pub fn parse_value_leniently<T>(mut value: Value) -> SlackLenientParsed<T>
+where
+    T: DeserializeOwned,
+{
+    let mut errors = Vec::new();
+    let mut removed_paths: Vec<Vec<SlackLenientPathSegment>> = Vec::new();
+
+    loop {
+        // Rendering values one per line allows to find failed values by error line numbers
+        let rendered = LenientRenderedValue::render(&value);
+        let err = match serde_json::from_str::<T>(&rendered.text) {
+            Ok(parsed) => {
+                return SlackLenientParsed {
+                    value: Some(parsed),
+                    errors,
+                }
+            }
+            Err(err) => err,
+        };
+
+        let failed_path = rendered.path_at_line(err.line()).unwrap_or_default();
+        let original_path = to_original_path(&failed_path, &removed_paths);
+
+        // Skipping a required value makes its parent fail, which is reported with the value itself
+        let is_parent_of_last_error = errors.last().is_some_and(|last: &SlackLenientFieldError| {
+            last.path.len() > original_path.len() && last.path.starts_with(&original_path)
+        });
+        if !is_parent_of_last_error {
+            errors.push(SlackLenientFieldError {
+                path: original_path.clone(),
+                message: error_message(&err),
+            });
+        }
+
+        if failed_path.is_empty() || errors.len() >= MAX_LENIENT_ERRORS {
+            return SlackLenientParsed {
+                value: None,
+                errors,
+            };
+        }
+
+        remove_path(&mut value, &failed_path);
+        removed_paths.push(original_path);
+    }
+}
+
+fn error_message(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    // Line and column refer to the internally rendered payload
+    match message.rfind(" at line ") {
+        Some(pos) => message[..pos].to_string(),
+        None => message,
+    }
+}
+
+fn format_path(path: &[SlackLenientPathSegment]) -> String {
+    let mut result = String::new();
+    for segment in path {
+        match segment {
+            SlackLenientPathSegment::Field(name) if result.is_empty() => result.push_str(name),
+            SlackLenientPathSegment::Field(name) => {
+                result.push('.');
+                result.push_str(name);
+            }
+            SlackLenientPathSegment::Index(idx) => result.push_str(&format!("[{idx}]")),
+        }
+    }
+    result
+}
+
+// Array items are removed, so indices of the next items have to be shifted back
+fn to_original_path(
+    path: &[SlackLenientPathSegment],
+    removed_paths: &[Vec<SlackLenientPathSegment>],
+) -> Vec<SlackLenientPathSegment> {
+    let mut original: Vec<SlackLenientPathSegment> = Vec::with_capacity(path.len());
+    for segment in path {
+        match segment {
+            SlackLenientPathSegment::Index(idx) => {
+                let mut removed_indices: Vec<usize> = removed_paths
+                    .iter()
+                    .filter(|removed| {
+                        removed.len() == original.len() + 1 && removed.starts_with(&original)
+                    })
+                    .filter_map(|removed| match removed.last() {
+                        Some(SlackLenientPathSegment::Index(removed_idx)) => Some(*removed_idx),
+                        _ => None,
+                    })
+                    .collect();
+                removed_indices.sort_unstable();
+                let mut original_idx = *idx;
+                for removed_idx in removed_indices {
+                    if removed_idx <= original_idx {
+                        original_idx += 1;
+                    }
+                }
+                original.push(SlackLenientPathSegment::Index(original_idx));
+            }
+            field => original.push(field.clone()),
+        }
+    }
+    original
+}
+
+fn remove_path(value: &mut Value, path: &[SlackLenientPathSegment]) {
+    if let Some((last, parent_path)) = path.split_last() {
+        let parent = parent_path
+            .iter()
+            .try_fold(value, |current, segment| match segment {
+                SlackLenientPathSegment::Field(name) => current.get_mut(name.as_str()),
+                SlackLenientPathSegment::Index(idx) => current.get_mut(*idx),
+            });
+        match (parent, last) {
+            (Some(Value::Object(fields)), SlackLenientPathSegment::Field(name)) => {
+                fields.remove(name);
+            }
+            (Some(Value::Array(items)), SlackLenientPathSegment::Index(idx))
+                if *idx < items.len() =>
+            {
+                items.remove(*idx);
+            }
+            _ => {}
+        }
+    }
+}
+
+struct LenientRenderedValue {
+    text: String,
+    /// Value paths with their first and last lines (1-based as in serde errors)
+    spans: Vec<(Vec<SlackLenientPathSegment>, usize, usize)>,
+}
+
+impl LenientRenderedValue {
+    fn render(value: &Value) -> Self {
+        let mut rendered = Self {
+            text: String::new(),
+            spans: Vec::new(),
+        };
+        let mut lines = 1;
+        rendered.render_value(value, &mut Vec::new(), &mut lines);
+        rendered
+    }
+
+    fn path_at_line(&self, line: usize) -> Option<Vec<SlackLenientPathSegment>> {
+        self.spans
+            .iter()
+            .filter(|(_, start, end)| *start <= line && line <= *end)
+            .min_by_key(|(_, start, end)| end - start)
+            .map(|(path, _, _)| path.clone())
+    }
+
+    fn render_value(
+        &mut self,
+        value: &Value,
+        path: &mut Vec<SlackLenientPathSegment>,
+        lines: &mut usize,
+    ) {
+        let start = *lines;
+        match value {
+            Value::Object(fields) => {
+                self.text.push('{');
+                for (idx, (name, field_value)) in fields.iter().enumerate() {
+                    self.new_line(lines);
+                    self.text
+                        .push_str(&serde_json::to_string(name).unwrap_or_default());
+                    self.text.push(':');
+                    path.push(SlackLenientPathSegment::Field(name.clone()));
+                    self.render_value(field_value, path, lines);
+                    path.pop();
+                    if idx + 1 < fields.len() {
+                        self.text.push(',');
+                    }
+                }
+                self.new_line(lines);
+                self.text.push('}');
+            }
+            Value::Array(items) => {
+                self.text.push('[');
+                for (idx, item) in items.iter().enumerate() {
+                    self.new_line(lines);
+                    path.push(SlackLenientPathSegment::Index(idx));
+                    self.render_value(item, path, lines);
+                    path.pop();
+                    if idx + 1 < items.len() {
+                        self.text.push(',');
+                    }
+                }
+                self.new_line(lines);
+                self.text.push(']');
+            }
+            scalar => self
+                .text
+                .push_str(&serde_json::to_string(scalar).unwrap_or_default()),
+        }
+        self.spans.push((path.clone(), start, *lines));
+    }
+
+    fn new_line(&mut self, lines: &mut usize) {
+        self.text.push('\n');
+        *lines += 1;
+    }
+}
+
+#[test]
+fn check_parse_leniently() {
+    use crate::models::*;
+
+    let parsed: SlackLenientParsed<SlackMessageContent> = parse_leniently(
+        r#"{ "text": "hello", "blocks": [{ "type": "unknown_block" }, { "type": "divider" }, { "type": "unknown_block" }] }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        parsed.value.and_then(|content| content.text),
+        Some("hello".into())
+    );
+    assert_eq!(
+        parsed
+            .errors
+            .iter()
+            .map(|err| err.path_string())
+            .collect::<Vec<_>>(),
+        vec!["blocks[0].type", "blocks[2].type"]
+    );
+
+    let parsed_root: SlackLenientParsed<SlackMessageOrigin> =
+        parse_leniently(r#"{ "ts": 1 }"#).unwrap();
+    assert!(parsed_root.value.is_none());
+    assert_eq!(parsed_root.errors.len(), 1);
+    assert_eq!(parsed_root.errors[0].path_string(), "ts");
+}
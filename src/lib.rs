@@ -84,26 +84,80 @@
     unused_imports
 )]
 
+pub use batch::*;
+pub use cache::*;
+pub use call_lifecycle::*;
+pub use canvas_markdown::*;
 pub use client::*;
+pub use dry_run::*;
+pub use endpoints::*;
+pub use ephemeral::*;
+pub use exporter::*;
+pub use fault_injection::*;
+pub use harvester::*;
+pub use health::*;
+pub use lenient::*;
+pub use membership::*;
+pub use message_defaults::*;
+pub use message_limits::*;
+pub use outbound_queue::*;
+pub use persona::*;
+pub use reactions_summary::*;
+pub use resolvers::*;
+pub use sandbox::*;
+pub use scheduled::*;
+pub use scopes::*;
 pub use scroller::*;
 pub use socket_mode::*;
+pub use threads::*;
 pub use token::*;
+pub use typed_session::*;
 
 mod models;
 pub use models::*;
 
 pub mod api;
+mod batch;
+mod cache;
+mod call_lifecycle;
+mod canvas_markdown;
 mod client;
+mod dry_run;
+mod endpoints;
+mod ephemeral;
 pub mod errors;
+mod exporter;
+mod fault_injection;
+pub mod fixtures;
+mod harvester;
+mod health;
+mod lenient;
 pub mod listener;
+mod membership;
+mod message_defaults;
+mod message_limits;
+mod outbound_queue;
+mod persona;
 mod ratectl;
+mod reactions_summary;
+mod resolvers;
+mod sandbox;
+mod scheduled;
+mod scopes;
 mod scroller;
 #[cfg(feature = "signature-verifier")]
 pub mod signature_verifier;
 pub mod socket_mode;
+#[cfg(test)]
+mod test_connector;
+mod threads;
+
+#[cfg(feature = "signature-verifier")]
+pub mod openid_verifier;
 
 mod multipart_form;
 mod token;
+mod typed_session;
 
 #[cfg(feature = "hyper")]
 pub mod hyper_tokio;
@@ -1,8 +1,12 @@
 use crate::models::*;
-use crate::{BoxError, ClientResult, SlackClient, SlackClientHttpConnector};
+use crate::{
+    BoxError, ClientResult, SlackApiEndpoints, SlackClient, SlackClientHttpApiUri,
+    SlackClientHttpConnector, SLACK_DEFAULT_API_ENDPOINTS,
+};
 use futures::executor::block_on;
 use futures::FutureExt;
 use rsb_derive::Builder;
+use rvstruct::ValueStruct;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -137,6 +141,8 @@ pub struct SlackOAuthListenerConfig {
     pub client_id: SlackClientId,
     pub client_secret: SlackClientSecret,
     pub bot_scope: String,
+    /// Scopes requested for the installing user token
+    pub user_scope: Option<String>,
     pub redirect_callback_host: String,
     #[default = "SlackOAuthListenerConfig::DEFAULT_INSTALL_PATH_VALUE.into()"]
     pub install_path: String,
@@ -169,7 +175,46 @@ impl SlackOAuthListenerConfig {
         )
         .map_err(|e| e.into())
     }
+
+    /// OAuth v2 authorize URL with the configured scopes and redirect URI.
+    /// The state should be verified on the redirect callback to prevent CSRF.
+    pub fn to_authorize_url(&self, state: Option<&str>) -> ClientResult<Url> {
+        self.to_authorize_url_with(&SLACK_DEFAULT_API_ENDPOINTS, state)
+    }
+
+    /// Authorize URL for region-specific endpoints (use `SlackClient::endpoints()`)
+    pub fn to_authorize_url_with(
+        &self,
+        endpoints: &SlackApiEndpoints,
+        state: Option<&str>,
+    ) -> ClientResult<Url> {
+        SlackClientHttpApiUri::create_url_with_params(
+            endpoints.oauth_authorize_url.parse()?,
+            &vec![
+                ("client_id", Some(self.client_id.value().as_str())),
+                ("scope", Some(self.bot_scope.as_str())),
+                ("user_scope", self.user_scope.as_deref()),
+                ("redirect_uri", Some(self.to_redirect_url()?.as_str())),
+                ("state", state),
+            ],
+        )
+    }
 }
 
 pub type UserCallbackFunction<E, IF, SCHC> =
     fn(E, Arc<SlackClient<SCHC>>, SlackClientEventsUserState) -> IF;
+
+#[test]
+fn check_oauth_authorize_url() {
+    let config = SlackOAuthListenerConfig::new(
+        "client-id".into(),
+        "client-secret".into(),
+        "chat:write".into(),
+        "https://example.net".into(),
+    )
+    .with_user_scope("users:read".into());
+    assert_eq!(
+        config.to_authorize_url(Some("state-1")).unwrap().as_str(),
+        "https://slack.com/oauth/v2/authorize?client_id=client-id&scope=chat%3Awrite&user_scope=users%3Aread&redirect_uri=https%3A%2F%2Fexample.net%2Fauth%2Fcallback&state=state-1"
+    );
+}
@@ -0,0 +1,347 @@
+//!
+//! Declarative membership sync helpers: compute the difference between
+//! current and desired members and apply it.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use std::collections::HashSet;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SlackMembershipDiff {
+    pub to_add: Vec<SlackUserId>,
+    pub to_remove: Vec<SlackUserId>,
+}
+
+impl SlackMembershipDiff {
+    /// Keeps the order of `desired` for added and of `current` for removed users
+    pub fn compute(current: &[SlackUserId], desired: &[SlackUserId]) -> Self {
+        let current_set: HashSet<&SlackUserId> = current.iter().collect();
+        let desired_set: HashSet<&SlackUserId> = desired.iter().collect();
+        let mut added: HashSet<&SlackUserId> = HashSet::new();
+
+        Self {
+            to_add: desired
+                .iter()
+                .filter(|user| !current_set.contains(user) && added.insert(user))
+                .cloned()
+                .collect(),
+            to_remove: current
+                .iter()
+                .filter(|user| !desired_set.contains(user))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.to_add.is_empty() && self.to_remove.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackUserGroupSyncRequest {
+    /// User group handle (with or without `@`)
+    pub handle: String,
+    pub desired_members: Vec<SlackUserId>,
+    #[default = "false"]
+    pub dry_run: bool,
+    pub team_id: Option<SlackTeamId>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackUserGroupSyncResult {
+    pub usergroup: SlackUserGroup,
+    pub diff: SlackMembershipDiff,
+    /// False for dry runs and when there is nothing to change
+    pub applied: bool,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Makes user group members equal to `desired_members` using `usergroups.users.update`.
+    /// Slack doesn't allow user groups without members, so use `usergroups.disable` for that.
+    pub async fn sync_usergroup_members(
+        &self,
+        req: &SlackUserGroupSyncRequest,
+    ) -> ClientResult<SlackUserGroupSyncResult> {
+        let handle = req.handle.trim().trim_start_matches('@');
+        let usergroup = self
+            .usergroups_list(
+                &SlackApiUserGroupsListRequest::new()
+                    .with_include_disabled(true)
+                    .opt_team_id(req.team_id.clone()),
+            )
+            .await?
+            .usergroups
+            .into_iter()
+            .find(|usergroup| usergroup.handle == handle)
+            .ok_or_else(|| {
+                SlackClientError::SystemError(
+                    SlackClientSystemError::new()
+                        .with_message(format!("User group @{handle} is not found")),
+                )
+            })?;
+
+        let current_members = self
+            .usergroups_users_list(
+                &SlackApiUserGroupsUsersListRequest::new(usergroup.id.clone())
+                    .with_include_disabled(true)
+                    .opt_team_id(req.team_id.clone()),
+            )
+            .await?
+            .users;
+
+        let diff = SlackMembershipDiff::compute(&current_members, &req.desired_members);
+
+        if req.dry_run || diff.is_empty() {
+            info!(
+                "User group @{} sync{}: {} to add {:?}, {} to remove {:?}",
+                handle,
+                if req.dry_run { " (dry run)" } else { "" },
+                diff.to_add.len(),
+                diff.to_add,
+                diff.to_remove.len(),
+                diff.to_remove
+            );
+            return Ok(SlackUserGroupSyncResult {
+                usergroup,
+                diff,
+                applied: false,
+            });
+        }
+
+        if req.desired_members.is_empty() {
+            return Err(SlackClientError::SystemError(
+                SlackClientSystemError::new().with_message(format!(
+                    "User group @{handle} can't have no members, disable it instead"
+                )),
+            ));
+        }
+
+        let mut desired_members = req.desired_members.clone();
+        let mut seen: HashSet<SlackUserId> = HashSet::new();
+        desired_members.retain(|user| seen.insert(user.clone()));
+
+        let updated = self
+            .usergroups_users_update(
+                &SlackApiUserGroupsUsersUpdateRequest::new(usergroup.id.clone(), desired_members)
+                    .opt_team_id(req.team_id.clone()),
+            )
+            .await?;
+
+        Ok(SlackUserGroupSyncResult {
+            usergroup: updated.usergroup,
+            diff,
+            applied: true,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackChannelMembershipSyncRequest {
+    pub channel: SlackChannelId,
+    pub desired_members: Vec<SlackUserId>,
+    /// Kick members missing in `desired_members`, otherwise only invite missing ones
+    #[default = "false"]
+    pub remove_extra_members: bool,
+    #[default = "false"]
+    pub dry_run: bool,
+    /// How many users to invite with one `conversations.invite` call (Slack allows up to 1000)
+    #[default = "SlackChannelMembershipSyncRequest::DEFAULT_INVITE_BATCH_SIZE"]
+    pub invite_batch_size: usize,
+}
+
+impl SlackChannelMembershipSyncRequest {
+    pub const DEFAULT_INVITE_BATCH_SIZE: usize = 100;
+    const MEMBERS_PAGE_LIMIT: u16 = 1000;
+}
+
+#[derive(Debug)]
+pub struct SlackChannelMembershipSyncResult {
+    pub diff: SlackMembershipDiff,
+    pub invited: Vec<SlackUserId>,
+    pub removed: Vec<SlackUserId>,
+    pub failed_invites: Vec<(Vec<SlackUserId>, SlackClientError)>,
+    pub failed_removals: Vec<(SlackUserId, SlackClientError)>,
+}
+
+impl SlackChannelMembershipSyncResult {
+    pub fn is_complete(&self) -> bool {
+        self.failed_invites.is_empty() && self.failed_removals.is_empty()
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Reconciles channel members with `desired_members` using `conversations.invite`
+    /// (in batches) and `conversations.kick`. Errors are collected per batch/user,
+    /// so the sync can be repeated to retry failed changes.
+    pub async fn sync_channel_members(
+        &self,
+        req: &SlackChannelMembershipSyncRequest,
+    ) -> ClientResult<SlackChannelMembershipSyncResult> {
+        let current_members = self.channel_members(&req.channel).await?;
+        let mut diff = SlackMembershipDiff::compute(&current_members, &req.desired_members);
+        if !req.remove_extra_members {
+            diff.to_remove.clear();
+        }
+
+        let mut result = SlackChannelMembershipSyncResult {
+            diff,
+            invited: Vec::new(),
+            removed: Vec::new(),
+            failed_invites: Vec::new(),
+            failed_removals: Vec::new(),
+        };
+
+        if req.dry_run {
+            info!(
+                "Channel {} members sync (dry run): {} to invite {:?}, {} to remove {:?}",
+                req.channel,
+                result.diff.to_add.len(),
+                result.diff.to_add,
+                result.diff.to_remove.len(),
+                result.diff.to_remove
+            );
+            return Ok(result);
+        }
+
+        for users in result.diff.to_add.chunks(req.invite_batch_size.max(1)) {
+            match self
+                .conversations_invite(&SlackApiConversationsInviteRequest::new(
+                    req.channel.clone(),
+                    users.to_vec(),
+                ))
+                .await
+            {
+                Ok(_) => result.invited.extend(users.iter().cloned()),
+                Err(err) => {
+                    warn!("Unable to invite users to {}: {}", req.channel, err);
+                    result.failed_invites.push((users.to_vec(), err));
+                }
+            }
+        }
+
+        for user in result.diff.to_remove.iter() {
+            match self
+                .conversations_kick(&SlackApiConversationsKickRequest::new(
+                    req.channel.clone(),
+                    user.clone(),
+                ))
+                .await
+            {
+                Ok(_) => result.removed.push(user.clone()),
+                Err(err) => {
+                    warn!("Unable to remove {} from {}: {}", user, req.channel, err);
+                    result.failed_removals.push((user.clone(), err));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn channel_members(&self, channel: &SlackChannelId) -> ClientResult<Vec<SlackUserId>> {
+        let mut members = Vec::new();
+        let mut cursor: Option<SlackCursorId> = None;
+        loop {
+            let resp = self
+                .conversations_members(
+                    &SlackApiConversationsMembersRequest::new()
+                        .with_channel(channel.clone())
+                        .with_limit(SlackChannelMembershipSyncRequest::MEMBERS_PAGE_LIMIT)
+                        .opt_cursor(cursor),
+                )
+                .await?;
+            members.extend(resp.members);
+            cursor = resp
+                .response_metadata
+                .and_then(|rm| rm.next_cursor)
+                .filter(|next_cursor| !next_cursor.value().is_empty());
+            if cursor.is_none() {
+                return Ok(members);
+            }
+        }
+    }
+}
+
+#[test]
+fn check_membership_diff() {
+    let users = |ids: &[&str]| -> Vec<SlackUserId> { ids.iter().map(|id| (*id).into()).collect() };
+
+    let diff = SlackMembershipDiff::compute(
+        &users(&["U1", "U2", "U3"]),
+        &users(&["U3", "U4", "U4", "U1"]),
+    );
+    assert_eq!(diff.to_add, users(&["U4"]));
+    assert_eq!(diff.to_remove, users(&["U2"]));
+
+    assert!(SlackMembershipDiff::compute(&users(&["U1"]), &users(&["U1"])).is_empty());
+}
+
+#[test]
+fn check_channel_members_sync() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|call: &SlackTestCall| match call.method.as_str() {
+        "conversations.members" => Ok(serde_json::json!({
+            "ok": true,
+            "members": ["U1", "U2"]
+        })),
+        "conversations.invite"
+            if call.body.as_ref().map(|body| &body["users"])
+                == Some(&serde_json::json!(["U5"])) =>
+        {
+            Err(SlackClientError::ApiError(SlackClientApiError::new(
+                "user_not_found".into(),
+            )))
+        }
+        "conversations.invite" => Ok(serde_json::json!({
+            "ok": true,
+            "channel": {
+                "id": "C1",
+                "created": 0
+            }
+        })),
+        _ => Ok(serde_json::json!({ "ok": true })),
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let users = |ids: &[&str]| -> Vec<SlackUserId> { ids.iter().map(|id| (*id).into()).collect() };
+
+    let req = SlackChannelMembershipSyncRequest::new("C1".into(), users(&["U2", "U3", "U4", "U5"]))
+        .with_remove_extra_members(true)
+        .with_invite_batch_size(2);
+
+    let dry_run =
+        futures::executor::block_on(session.sync_channel_members(&req.clone().with_dry_run(true)))
+            .unwrap();
+    assert_eq!(dry_run.diff.to_add, users(&["U3", "U4", "U5"]));
+    assert_eq!(dry_run.diff.to_remove, users(&["U1"]));
+    assert!(connector.calls_of("conversations.invite").is_empty());
+
+    let result = futures::executor::block_on(session.sync_channel_members(&req)).unwrap();
+    assert_eq!(result.invited, users(&["U3", "U4"]));
+    assert_eq!(result.removed, users(&["U1"]));
+    assert_eq!(
+        result
+            .failed_invites
+            .iter()
+            .map(|(users, _)| users.clone())
+            .collect::<Vec<_>>(),
+        vec![users(&["U5"])]
+    );
+    assert!(!result.is_complete());
+    assert_eq!(connector.calls_of("conversations.invite").len(), 2);
+}
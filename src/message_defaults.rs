@@ -0,0 +1,207 @@
+//!
+//! Session-scoped default parameters for chat messages
+//! (see `SlackClientSession::with_message_defaults`).
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackMessageDefaults {
+    /// Used for requests with an empty channel
+    pub channel: Option<SlackChannelId>,
+    /// Used only for messages posted to the default channel
+    pub thread_ts: Option<SlackTs>,
+    pub icon_emoji: Option<String>,
+    pub icon_url: Option<String>,
+    pub username: Option<String>,
+    pub unfurl_links: Option<bool>,
+    pub unfurl_media: Option<bool>,
+}
+
+impl SlackMessageDefaults {
+    fn resolve_channel(&self, channel: &SlackChannelId) -> SlackChannelId {
+        match self.channel.as_ref() {
+            Some(default_channel) if channel.value().is_empty() => default_channel.clone(),
+            _ => channel.clone(),
+        }
+    }
+
+    fn thread_ts_for(
+        &self,
+        channel: &SlackChannelId,
+        thread_ts: &Option<SlackTs>,
+    ) -> Option<SlackTs> {
+        thread_ts.clone().or_else(|| {
+            self.thread_ts
+                .clone()
+                .filter(|_| self.channel.as_ref() == Some(channel))
+        })
+    }
+
+    // The icon is one setting: a request with either icon keeps its own icon only
+    fn icon_for(
+        &self,
+        icon_emoji: &Option<String>,
+        icon_url: &Option<String>,
+    ) -> (Option<String>, Option<String>) {
+        if icon_emoji.is_some() || icon_url.is_some() {
+            (icon_emoji.clone(), icon_url.clone())
+        } else {
+            (self.icon_emoji.clone(), self.icon_url.clone())
+        }
+    }
+
+    pub fn apply_to_post_message(
+        &self,
+        req: &SlackApiChatPostMessageRequest,
+    ) -> SlackApiChatPostMessageRequest {
+        let channel = self.resolve_channel(&req.channel);
+        let (icon_emoji, icon_url) = self.icon_for(&req.icon_emoji, &req.icon_url);
+        SlackApiChatPostMessageRequest {
+            thread_ts: self.thread_ts_for(&channel, &req.thread_ts),
+            channel,
+            icon_emoji,
+            icon_url,
+            username: req.username.clone().or_else(|| self.username.clone()),
+            unfurl_links: req.unfurl_links.or(self.unfurl_links),
+            unfurl_media: req.unfurl_media.or(self.unfurl_media),
+            ..req.clone()
+        }
+    }
+
+    pub fn apply_to_post_ephemeral(
+        &self,
+        req: &SlackApiChatPostEphemeralRequest,
+    ) -> SlackApiChatPostEphemeralRequest {
+        let channel = self.resolve_channel(&req.channel);
+        let (icon_emoji, icon_url) = self.icon_for(&req.icon_emoji, &req.icon_url);
+        SlackApiChatPostEphemeralRequest {
+            thread_ts: self.thread_ts_for(&channel, &req.thread_ts),
+            channel,
+            icon_emoji,
+            icon_url,
+            username: req.username.clone().or_else(|| self.username.clone()),
+            ..req.clone()
+        }
+    }
+
+    pub fn apply_to_schedule_message(
+        &self,
+        req: &SlackApiChatScheduleMessageRequest,
+    ) -> SlackApiChatScheduleMessageRequest {
+        let channel = self.resolve_channel(&req.channel);
+        let (icon_emoji, icon_url) = self.icon_for(&req.icon_emoji, &req.icon_url);
+        SlackApiChatScheduleMessageRequest {
+            thread_ts: self.thread_ts_for(&channel, &req.thread_ts),
+            channel,
+            icon_emoji,
+            icon_url,
+            username: req.username.clone().or_else(|| self.username.clone()),
+            unfurl_links: req.unfurl_links.or(self.unfurl_links),
+            unfurl_media: req.unfurl_media.or(self.unfurl_media),
+            ..req.clone()
+        }
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Posts content to the default channel of the session
+    pub async fn post_default_message(
+        &self,
+        content: SlackMessageContent,
+    ) -> ClientResult<SlackApiChatPostMessageResponse> {
+        match self
+            .message_defaults()
+            .and_then(|defaults| defaults.channel.as_ref())
+        {
+            Some(_) => {
+                self.chat_post_message(&SlackApiChatPostMessageRequest::new("".into(), content))
+                    .await
+            }
+            None => Err(SlackClientError::SystemError(
+                SlackClientSystemError::new()
+                    .with_message("No default channel configured for the session".into()),
+            )),
+        }
+    }
+}
+
+#[test]
+fn check_message_defaults() {
+    let defaults = SlackMessageDefaults::new()
+        .with_channel("C1".into())
+        .with_thread_ts("1.1".into())
+        .with_username("Notifier".into())
+        .with_unfurl_links(false);
+
+    let to_default = defaults.apply_to_post_message(&SlackApiChatPostMessageRequest::new(
+        "".into(),
+        SlackMessageContent::new().with_text("Hey".into()),
+    ));
+    assert_eq!(to_default.channel, "C1".into());
+    assert_eq!(to_default.thread_ts, Some("1.1".into()));
+    assert_eq!(to_default.username.as_deref(), Some("Notifier"));
+    assert_eq!(to_default.unfurl_links, Some(false));
+
+    let overridden = defaults.apply_to_post_message(
+        &SlackApiChatPostMessageRequest::new("C2".into(), SlackMessageContent::new())
+            .with_username("Other".into()),
+    );
+    assert_eq!(overridden.channel, "C2".into());
+    assert_eq!(overridden.thread_ts, None);
+    assert_eq!(overridden.username.as_deref(), Some("Other"));
+}
+
+#[test]
+fn check_message_defaults_icon() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|_| {
+        Ok(serde_json::json!({ "channel": "C1", "ts": "1.1", "message": { "ts": "1.1" } }))
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token).with_message_defaults(
+        SlackMessageDefaults::new()
+            .with_icon_emoji(":robot_face:".into())
+            .with_username("Notifier".into()),
+    );
+
+    futures::executor::block_on(async {
+        session
+            .chat_post_message(
+                &SlackApiChatPostMessageRequest::new("C1".into(), SlackMessageContent::new())
+                    .with_icon_url("https://example.com/persona.png".into()),
+            )
+            .await
+            .unwrap();
+        session
+            .chat_post_message(&SlackApiChatPostMessageRequest::new(
+                "C1".into(),
+                SlackMessageContent::new(),
+            ))
+            .await
+            .unwrap();
+    });
+
+    let calls = connector.calls_of("chat.postMessage");
+    assert_eq!(
+        calls[0].param("icon_url").as_deref(),
+        Some("https://example.com/persona.png")
+    );
+    assert_eq!(calls[0].param("icon_emoji"), None);
+    assert_eq!(calls[0].param("username").as_deref(), Some("Notifier"));
+    assert_eq!(
+        calls[1].param("icon_emoji").as_deref(),
+        Some(":robot_face:")
+    );
+    assert_eq!(calls[1].param("icon_url"), None);
+}
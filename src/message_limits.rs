@@ -0,0 +1,462 @@
+//!
+//! Helpers to deal with Slack message limits (text length and number of blocks)
+//! instead of failing with `msg_too_long` or `invalid_blocks` errors.
+//!
+
+use crate::api::*;
+use crate::blocks::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackMessageSplitConfig {
+    #[default = "SlackMessageSplitConfig::DEFAULT_MAX_TEXT_LENGTH"]
+    pub max_text_length: usize,
+    #[default = "SlackMessageSplitConfig::DEFAULT_MAX_BLOCKS"]
+    pub max_blocks: usize,
+}
+
+impl SlackMessageSplitConfig {
+    // Also the message limits checked by the dry-run validation
+    pub const DEFAULT_MAX_TEXT_LENGTH: usize = 4000;
+    pub const DEFAULT_MAX_BLOCKS: usize = 50;
+
+    const CODE_BLOCK_OPEN: &'static str = "```\n";
+    const CODE_BLOCK_CLOSE: &'static str = "\n```";
+    const TRUNCATED_TEXT_SUFFIX: &'static str = "…";
+
+    /// Splits content into parts fitting the limits.
+    /// Attachments and metadata are kept only in the first part.
+    pub fn split_content(&self, content: &SlackMessageContent) -> Vec<SlackMessageContent> {
+        let text_parts = content
+            .text
+            .as_ref()
+            .map(|text| self.split_text(text))
+            .unwrap_or_default();
+        let block_parts: Vec<Vec<SlackBlock>> = content
+            .blocks
+            .as_ref()
+            .map(|blocks| {
+                blocks
+                    .chunks(self.max_blocks.max(1))
+                    .map(|chunk| chunk.to_vec())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let parts_count = text_parts.len().max(block_parts.len()).max(1);
+        let mut text_parts = text_parts.into_iter();
+        let mut block_parts = block_parts.into_iter();
+
+        (0..parts_count)
+            .map(|idx| {
+                let part = SlackMessageContent::new()
+                    .opt_text(text_parts.next())
+                    .opt_blocks(block_parts.next());
+                if idx == 0 {
+                    part.opt_attachments(content.attachments.clone())
+                        .opt_metadata(content.metadata.clone())
+                } else {
+                    part
+                }
+            })
+            .collect()
+    }
+
+    /// Truncates text and blocks to fit the limits
+    pub fn truncate_content(&self, content: &SlackMessageContent) -> SlackMessageContent {
+        let text = content.text.as_ref().map(|text| {
+            if text.chars().count() <= self.max_text_length {
+                text.clone()
+            } else {
+                let mut truncated: String = text
+                    .chars()
+                    .take(self.max_text_length.saturating_sub(1))
+                    .collect();
+                truncated.push_str(Self::TRUNCATED_TEXT_SUFFIX);
+                truncated
+            }
+        });
+        let blocks = content
+            .blocks
+            .as_ref()
+            .map(|blocks| blocks.iter().take(self.max_blocks).cloned().collect());
+
+        SlackMessageContent {
+            text,
+            blocks,
+            ..content.clone()
+        }
+    }
+
+    /// Splits text preferring paragraphs, lines and words boundaries.
+    /// Code blocks split between parts are closed and reopened, so formatting is kept.
+    pub fn split_text(&self, text: &str) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut rest = text;
+        let mut in_code_block = false;
+
+        loop {
+            let reopen_len = if in_code_block {
+                Self::CODE_BLOCK_OPEN.len()
+            } else {
+                0
+            };
+            if reopen_len + rest.chars().count() <= self.max_text_length {
+                break;
+            }
+
+            let budget = self
+                .max_text_length
+                .saturating_sub(reopen_len + Self::CODE_BLOCK_CLOSE.len())
+                .max(1);
+            let limit = rest
+                .char_indices()
+                .nth(budget)
+                .map(|(idx, _)| idx)
+                .unwrap_or(rest.len());
+            let window = &rest[..limit];
+
+            let (split_at, separator_len) = [("\n\n", 2), ("\n", 1), (" ", 1)]
+                .iter()
+                .find_map(|(separator, len)| {
+                    window
+                        .rfind(separator)
+                        .filter(|idx| *idx > 0)
+                        .map(|idx| (idx, *len))
+                })
+                .unwrap_or((limit, 0));
+            // Splitting inside a code block marker would break formatting of both parts
+            let (split_at, separator_len) = rest
+                .match_indices("```")
+                .map(|(idx, _)| idx)
+                .find(|idx| *idx < split_at && split_at < idx + 3)
+                .map(|idx| (if idx > 0 { idx } else { idx + 3 }, 0))
+                .unwrap_or((split_at, separator_len));
+
+            let chunk = &rest[..split_at];
+            let chunk_ends_in_code_block = in_code_block ^ (chunk.matches("```").count() % 2 == 1);
+
+            let mut part = String::with_capacity(self.max_text_length);
+            if in_code_block {
+                part.push_str(Self::CODE_BLOCK_OPEN);
+            }
+            part.push_str(chunk);
+            if chunk_ends_in_code_block {
+                part.push_str(Self::CODE_BLOCK_CLOSE);
+            }
+            parts.push(part);
+
+            in_code_block = chunk_ends_in_code_block;
+            rest = &rest[split_at + separator_len..];
+            // The code block ends right after the split, so the added marker has closed it
+            if in_code_block && rest.starts_with("```") {
+                rest = &rest[3..];
+                in_code_block = false;
+            }
+            if !in_code_block {
+                rest = rest.trim_start_matches('\n');
+            }
+        }
+
+        if in_code_block {
+            parts.push(format!("{}{}", Self::CODE_BLOCK_OPEN, rest));
+        } else if !rest.is_empty() || parts.is_empty() {
+            parts.push(rest.to_string());
+        }
+
+        parts
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Posts a message splitting it into a sequence of messages if it exceeds the limits.
+    /// The first part is posted as requested, and the following parts
+    /// are posted as replies in the thread of the first one (or in `req.thread_ts`).
+    pub async fn chat_post_message_split(
+        &self,
+        req: &SlackApiChatPostMessageRequest,
+        config: &SlackMessageSplitConfig,
+    ) -> ClientResult<Vec<SlackApiChatPostMessageResponse>> {
+        let mut responses: Vec<SlackApiChatPostMessageResponse> = Vec::new();
+
+        for content in config.split_content(&req.content) {
+            let part_req = match responses.first() {
+                None => req.clone().with_content(content),
+                Some(first) => req
+                    .clone()
+                    .with_content(content)
+                    .with_thread_ts(req.thread_ts.clone().unwrap_or(first.ts.clone()))
+                    .without_reply_broadcast(),
+            };
+            responses.push(self.chat_post_message(&part_req).await?);
+        }
+
+        Ok(responses)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SlackMessageOverflowStrategy {
+    /// Drop blocks and text exceeding the limits
+    Truncate,
+    /// Update the message with the first part and post the rest as new messages in the channel
+    Rollover,
+    /// Update the message with the first part and post the rest as replies in its thread
+    ThreadReplies,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackChatUpdateOverflowResponse {
+    pub updated: SlackApiChatUpdateResponse,
+    /// Messages posted for the overflowed parts.
+    /// For `Rollover` the last one is the message to update next time.
+    pub posted: Vec<SlackApiChatPostMessageResponse>,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Updates a message handling content exceeding the limits with the specified strategy.
+    pub async fn chat_update_with_overflow(
+        &self,
+        req: &SlackApiChatUpdateRequest,
+        strategy: SlackMessageOverflowStrategy,
+        config: &SlackMessageSplitConfig,
+    ) -> ClientResult<SlackChatUpdateOverflowResponse> {
+        let mut parts = config.split_content(&req.content).into_iter();
+        let first_part = parts.next().unwrap_or_else(SlackMessageContent::new);
+
+        let updated = match strategy {
+            SlackMessageOverflowStrategy::Truncate => {
+                self.chat_update(
+                    &req.clone()
+                        .with_content(config.truncate_content(&req.content)),
+                )
+                .await?
+            }
+            _ => {
+                self.chat_update(&req.clone().with_content(first_part))
+                    .await?
+            }
+        };
+
+        let mut posted = Vec::new();
+        if strategy != SlackMessageOverflowStrategy::Truncate {
+            for content in parts {
+                let post_req = SlackApiChatPostMessageRequest::new(req.channel.clone(), content)
+                    .opt_as_user(req.as_user)
+                    .opt_link_names(req.link_names)
+                    .opt_parse(req.parse.clone());
+                let post_req = match strategy {
+                    SlackMessageOverflowStrategy::ThreadReplies => {
+                        post_req.with_thread_ts(updated.ts.clone())
+                    }
+                    _ => post_req,
+                };
+                posted.push(self.chat_post_message(&post_req).await?);
+            }
+        }
+
+        Ok(SlackChatUpdateOverflowResponse { updated, posted })
+    }
+}
+
+#[test]
+fn check_message_text_split() {
+    let config = SlackMessageSplitConfig::new().with_max_text_length(20);
+
+    assert_eq!(config.split_text("short"), vec!["short".to_string()]);
+
+    assert_eq!(
+        config.split_text("first paragraph\n\nsecond one here"),
+        vec!["first paragraph".to_string(), "second one here".to_string()]
+    );
+
+    let parts = config.split_text("```\nline one\nline two\nline three\n```");
+    assert!(parts.iter().all(|part| part.chars().count() <= 20));
+    assert!(parts
+        .iter()
+        .all(|part| part.starts_with("```") && part.ends_with("```")));
+    assert_eq!(parts.len(), 3);
+
+    assert_eq!(
+        config.split_text("0123456789abcde```tailtailtail```"),
+        vec![
+            "0123456789abcde".to_string(),
+            "```tailtailtail```".to_string()
+        ]
+    );
+}
+
+#[test]
+fn check_message_content_truncate() {
+    let config = SlackMessageSplitConfig::new()
+        .with_max_text_length(5)
+        .with_max_blocks(2);
+
+    let truncated = config.truncate_content(
+        &SlackMessageContent::new()
+            .with_text("日本語のテキスト".into())
+            .with_blocks(vec![SlackDividerBlock::new().into(); 3])
+            .with_attachments(vec![SlackMessageAttachment::new()]),
+    );
+    assert_eq!(truncated.text, Some("日本語の…".into()));
+    assert_eq!(truncated.blocks.map(|blocks| blocks.len()), Some(2));
+    assert_eq!(truncated.attachments.map(|a| a.len()), Some(1));
+
+    assert_eq!(
+        config
+            .truncate_content(&SlackMessageContent::new().with_text("短い".into()))
+            .text,
+        Some("短い".into())
+    );
+}
+
+#[cfg(test)]
+fn message_limits_test_connector() -> crate::test_connector::SlackTestConnector {
+    use crate::test_connector::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let posted = AtomicUsize::new(0);
+    SlackTestConnector::new(move |call: &SlackTestCall| match call.method.as_str() {
+        "chat.update" => Ok(serde_json::json!({ "channel": "C1", "ts": "1.1", "message": {} })),
+        _ => {
+            let ts = format!("2.{}", posted.fetch_add(1, Ordering::SeqCst) + 1);
+            Ok(serde_json::json!({ "channel": "C1", "ts": ts, "message": { "ts": ts } }))
+        }
+    })
+}
+
+#[cfg(test)]
+fn overflowing_update_calls(
+    strategy: SlackMessageOverflowStrategy,
+) -> (
+    SlackChatUpdateOverflowResponse,
+    Vec<crate::test_connector::SlackTestCall>,
+) {
+    let connector = message_limits_test_connector();
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let resp = futures::executor::block_on(
+        session.chat_update_with_overflow(
+            &SlackApiChatUpdateRequest::new(
+                "C1".into(),
+                SlackMessageContent::new()
+                    .with_text("first paragraph\n\nsecond one\n\nthird one".into()),
+                "1.1".into(),
+            ),
+            strategy,
+            &SlackMessageSplitConfig::new().with_max_text_length(20),
+        ),
+    )
+    .unwrap();
+    (resp, connector.calls())
+}
+
+#[test]
+fn check_chat_update_with_overflow_truncate() {
+    let (resp, calls) = overflowing_update_calls(SlackMessageOverflowStrategy::Truncate);
+
+    assert!(resp.posted.is_empty());
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].method, "chat.update");
+    assert_eq!(calls[0].param("ts").as_deref(), Some("1.1"));
+    assert_eq!(
+        calls[0].param("text").as_deref(),
+        Some("first paragraph\n\nse…")
+    );
+}
+
+#[test]
+fn check_chat_update_with_overflow_rollover() {
+    let (resp, calls) = overflowing_update_calls(SlackMessageOverflowStrategy::Rollover);
+
+    assert_eq!(
+        calls
+            .iter()
+            .map(|call| (call.method.as_str(), call.param("text").unwrap_or_default()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("chat.update", "first paragraph".to_string()),
+            ("chat.postMessage", "second one".to_string()),
+            ("chat.postMessage", "third one".to_string()),
+        ]
+    );
+    assert!(calls[1..]
+        .iter()
+        .all(|call| call.param("thread_ts").is_none() && call.param("reply_broadcast").is_none()));
+    assert_eq!(
+        resp.posted.last().map(|posted| posted.ts.clone()),
+        Some("2.2".into())
+    );
+}
+
+#[test]
+fn check_chat_update_with_overflow_thread_replies() {
+    let (resp, calls) = overflowing_update_calls(SlackMessageOverflowStrategy::ThreadReplies);
+
+    assert_eq!(
+        calls
+            .iter()
+            .map(|call| (call.method.as_str(), call.param("text").unwrap_or_default()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("chat.update", "first paragraph".to_string()),
+            ("chat.postMessage", "second one".to_string()),
+            ("chat.postMessage", "third one".to_string()),
+        ]
+    );
+    assert!(calls[1..].iter().all(|call| {
+        call.param("thread_ts").as_deref() == Some("1.1") && call.param("reply_broadcast").is_none()
+    }));
+    assert_eq!(resp.posted.len(), 2);
+}
+
+#[test]
+fn check_chat_post_message_split() {
+    let connector = message_limits_test_connector();
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let responses = futures::executor::block_on(
+        session.chat_post_message_split(
+            &SlackApiChatPostMessageRequest::new(
+                "C1".into(),
+                SlackMessageContent::new().with_text(
+                    "intro paragraph\n\n```\nlet a = 1;\nlet b = 2;\n```\n\nthe end".into(),
+                ),
+            )
+            .with_reply_broadcast(true),
+            &SlackMessageSplitConfig::new().with_max_text_length(30),
+        ),
+    )
+    .unwrap();
+
+    let calls = connector.calls_of("chat.postMessage");
+    assert_eq!(responses.len(), calls.len());
+    assert_eq!(
+        calls
+            .iter()
+            .map(|call| call.param("text").unwrap_or_default())
+            .collect::<Vec<_>>(),
+        vec![
+            "intro paragraph".to_string(),
+            "```\nlet a = 1;\nlet b = 2;\n```".to_string(),
+            "the end".to_string(),
+        ]
+    );
+    assert_eq!(calls[0].param("thread_ts"), None);
+    assert_eq!(calls[0].param("reply_broadcast").as_deref(), Some("true"));
+    assert!(calls[1..].iter().all(|call| {
+        call.param("thread_ts").as_deref() == Some("2.1") && call.param("reply_broadcast").is_none()
+    }));
+}
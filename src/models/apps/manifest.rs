@@ -1,6 +1,7 @@
 use rsb_derive::Builder;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
+use std::collections::BTreeMap;
 use url::Url;
 
 use crate::{SlackApiTokenScope, SlackCallbackId, SlackEventType, SlackShortcutType};
@@ -27,6 +28,27 @@ pub struct SlackAppManifestSettingsEventSubscriptions {
     pub request_url: Option<Url>,
     pub bot_events: Option<Vec<SlackEventType>>,
     pub user_events: Option<Vec<SlackEventType>>,
+    pub metadata_subscriptions: Option<Vec<SlackAppManifestSettingsMetadataSubscription>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestSettingsMetadataSubscription {
+    pub app_id: String,
+    pub event_type: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestSettingsIncomingWebhooks {
+    pub incoming_webhooks_enabled: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlackAppManifestFunctionRuntime {
+    Remote,
+    Slack,
 }
 
 #[skip_serializing_none]
@@ -45,6 +67,10 @@ pub struct SlackAppManifestSettings {
     pub interactivity: Option<SlackAppManifestSettingsInteractivity>,
     pub org_deploy_enabled: Option<bool>,
     pub socket_mode_enabled: Option<bool>,
+    pub token_rotation_enabled: Option<bool>,
+    pub incoming_webhooks: Option<SlackAppManifestSettingsIncomingWebhooks>,
+    pub function_runtime: Option<SlackAppManifestFunctionRuntime>,
+    pub is_hosted: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -98,6 +124,21 @@ pub struct SlackAppManifestFeatures {
     pub slash_commands: Option<Vec<SlackAppManifestFeaturesSlashCommand>>,
     pub unfurl_domains: Option<Vec<String>>,
     pub workflow_steps: Option<Vec<SlackAppManifestFeaturesWorkflowStep>>,
+    pub assistant_view: Option<SlackAppManifestFeaturesAssistantView>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestFeaturesAssistantViewPrompt {
+    pub title: String,
+    pub message: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestFeaturesAssistantView {
+    pub assistant_description: String,
+    pub suggested_prompts: Option<Vec<SlackAppManifestFeaturesAssistantViewPrompt>>,
 }
 
 #[skip_serializing_none]
@@ -112,6 +153,74 @@ pub struct SlackAppManifestOAuthConfigScopes {
 pub struct SlackAppManifestOAuthConfig {
     pub redirect_urls: Option<Vec<Url>>,
     pub scopes: Option<SlackAppManifestOAuthConfigScopes>,
+    pub token_management_enabled: Option<bool>,
+}
+
+/// Function or workflow parameter schema.
+/// Additional properties (e.g. `items`, `enum`, `choices`) are kept as they are.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestParameter {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub hint: Option<String>,
+    pub default: Option<serde_json::Value>,
+    pub is_required: Option<bool>,
+    #[serde(flatten)]
+    pub other: Option<BTreeMap<String, serde_json::Value>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestParameters {
+    pub properties: BTreeMap<String, SlackAppManifestParameter>,
+    pub required: Option<Vec<String>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestFunction {
+    pub title: String,
+    pub description: Option<String>,
+    pub input_parameters: SlackAppManifestParameters,
+    pub output_parameters: SlackAppManifestParameters,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestWorkflowStep {
+    pub id: String,
+    pub function_id: String,
+    pub inputs: BTreeMap<String, serde_json::Value>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestWorkflow {
+    pub title: String,
+    pub description: Option<String>,
+    pub input_parameters: Option<SlackAppManifestParameters>,
+    pub output_parameters: Option<SlackAppManifestParameters>,
+    pub steps: Vec<SlackAppManifestWorkflowStep>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestDatastoreAttribute {
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(flatten)]
+    pub other: Option<BTreeMap<String, serde_json::Value>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackAppManifestDatastore {
+    pub primary_key: String,
+    pub attributes: BTreeMap<String, SlackAppManifestDatastoreAttribute>,
+    pub time_to_live_attribute: Option<String>,
 }
 
 #[skip_serializing_none]
@@ -123,4 +232,12 @@ pub struct SlackAppManifest {
     pub settings: Option<SlackAppManifestSettings>,
     pub features: Option<SlackAppManifestFeatures>,
     pub oauth_config: Option<SlackAppManifestOAuthConfig>,
+    /// Functions by callback id
+    pub functions: Option<BTreeMap<String, SlackAppManifestFunction>>,
+    /// Workflows by callback id
+    pub workflows: Option<BTreeMap<String, SlackAppManifestWorkflow>>,
+    pub datastores: Option<BTreeMap<String, SlackAppManifestDatastore>>,
+    /// Custom types by name
+    pub types: Option<BTreeMap<String, SlackAppManifestParameter>>,
+    pub outgoing_domains: Option<Vec<String>>,
 }
@@ -29,6 +29,8 @@ pub enum SlackBlock {
     Input(SlackInputBlock),
     #[serde(rename = "file")]
     File(SlackFileBlock),
+    #[serde(rename = "call")]
+    Call(SlackCallBlock),
 
     // This block is still undocumented, so we don't define any structure yet we can return it back,
     #[serde(rename = "rich_text")]
@@ -152,6 +154,51 @@ impl From<SlackFileBlock> for SlackBlock {
     }
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCallBlock {
+    pub block_id: Option<SlackBlockId>,
+    pub call_id: SlackCallId,
+    pub api_decoration_available: Option<bool>,
+    /// Call details, provided by Slack in messages with call blocks
+    pub call: Option<SlackCallBlockCall>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCallBlockCall {
+    pub v1: Option<SlackCallBlockCallInfo>,
+    pub media_backend_type: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCallBlockCallInfo {
+    pub id: SlackCallId,
+    pub app_id: Option<SlackAppId>,
+    pub name: Option<String>,
+    pub created_by: Option<SlackUserId>,
+    pub date_start: Option<SlackDateTime>,
+    pub date_end: Option<SlackDateTime>,
+    pub active_participants: Option<Vec<SlackCallUser>>,
+    pub all_participants: Option<Vec<SlackCallUser>>,
+    pub display_id: Option<String>,
+    pub join_url: Option<Url>,
+    pub desktop_app_join_url: Option<Url>,
+    pub channels: Option<Vec<SlackChannelId>>,
+    pub is_dm_call: Option<bool>,
+    pub was_rejected: Option<bool>,
+    pub was_missed: Option<bool>,
+    pub was_accepted: Option<bool>,
+    pub has_ended: Option<bool>,
+}
+
+impl From<SlackCallBlock> for SlackBlock {
+    fn from(block: SlackCallBlock) -> Self {
+        SlackBlock::Call(block)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum SlackSectionBlockElement {
@@ -999,3 +1046,46 @@ impl From<&str> for SlackBlockPlainTextOnly {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slack_call_block_serialization() {
+        let output =
+            serde_json::to_value(SlackBlock::from(SlackCallBlock::new("R0E69JAIF".into())))
+                .unwrap();
+        assert_eq!(
+            output,
+            serde_json::json!({ "type": "call", "call_id": "R0E69JAIF" })
+        );
+    }
+
+    #[test]
+    fn test_slack_call_block_in_message() {
+        let payload = include_str!("./fixtures/slack_call_block_message.json");
+        let model: SlackHistoryMessage = serde_json::from_str(payload).unwrap();
+        let call_block = match model.content.blocks.as_deref() {
+            Some([SlackBlock::Call(call_block)]) => call_block,
+            other => panic!("Unexpected blocks: {:?}", other),
+        };
+        assert_eq!(call_block.call_id, "R0E69JAIF".into());
+
+        let call = call_block
+            .call
+            .as_ref()
+            .and_then(|call| call.v1.as_ref())
+            .unwrap();
+        assert_eq!(call.name.as_deref(), Some("Kimpossible sync up"));
+        assert_eq!(call.has_ended, Some(false));
+        assert_eq!(
+            call.all_participants.as_ref().map(|users| users.len()),
+            Some(2)
+        );
+        assert_eq!(
+            call.desktop_app_join_url.as_ref().map(|url| url.as_str()),
+            Some("callapp://join/1234567890")
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//!
+//! Borrowed models for hot paths (e.g. event processors handling thousands of events per second).
+//! Strings are borrowed from the input buffer when possible (`Cow::Borrowed`) and
+//! only the fields usually needed for routing and filtering are deserialized.
+//!
+
+use crate::*;
+use serde::Deserialize;
+use std::borrow::Cow;
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlackPushEventRef<'a> {
+    UrlVerification {
+        #[serde(borrow)]
+        challenge: Cow<'a, str>,
+    },
+    EventCallback(#[serde(borrow)] SlackPushEventCallbackRef<'a>),
+    AppRateLimited {
+        #[serde(borrow)]
+        team_id: Cow<'a, str>,
+        minute_rate_limited: SlackDateTime,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub struct SlackPushEventCallbackRef<'a> {
+    #[serde(borrow)]
+    pub team_id: Cow<'a, str>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub enterprise_id: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    pub api_app_id: Cow<'a, str>,
+    #[serde(borrow)]
+    pub event_id: Cow<'a, str>,
+    pub event_time: SlackDateTime,
+    #[serde(borrow)]
+    pub event: SlackEventCallbackBodyRef<'a>,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlackEventCallbackBodyRef<'a> {
+    Message(#[serde(borrow)] SlackMessageEventRef<'a>),
+    AppMention(#[serde(borrow)] SlackMessageEventRef<'a>),
+    /// Other events (use the owned models to process them)
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub struct SlackMessageEventRef<'a> {
+    #[serde(borrow)]
+    pub ts: Cow<'a, str>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub channel: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub channel_type: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub thread_ts: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub user: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub bot_id: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub subtype: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub text: Option<Cow<'a, str>>,
+}
+
+impl SlackMessageEventRef<'_> {
+    pub fn is_thread_reply(&self) -> bool {
+        self.thread_ts
+            .as_ref()
+            .is_some_and(|thread_ts| *thread_ts != self.ts)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub struct SlackHistoryMessageRef<'a> {
+    #[serde(borrow)]
+    pub ts: Cow<'a, str>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub thread_ts: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub user: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub bot_id: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub subtype: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub text: Option<Cow<'a, str>>,
+    pub reply_count: Option<usize>,
+}
+
+/// Borrowed `conversations.history` response (e.g. to process stored or exported history pages)
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub struct SlackConversationsHistoryPageRef<'a> {
+    #[serde(borrow)]
+    pub messages: Vec<SlackHistoryMessageRef<'a>>,
+    pub has_more: Option<bool>,
+    #[serde(borrow)]
+    pub response_metadata: Option<SlackResponseMetadataRef<'a>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub struct SlackResponseMetadataRef<'a> {
+    #[serde(borrow, default, deserialize_with = "deserialize_opt_borrowed_str")]
+    pub next_cursor: Option<Cow<'a, str>>,
+}
+
+// serde borrows only plain `Cow<str>` fields, so optional ones need a helper
+fn deserialize_opt_borrowed_str<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct BorrowedStr<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    Ok(Option::<BorrowedStr>::deserialize(deserializer)?.map(|value| value.0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_push_event() {
+        let payload = include_str!("./events/fixtures/message_changed.json");
+        match serde_json::from_str::<SlackPushEventRef>(payload).unwrap() {
+            SlackPushEventRef::EventCallback(callback) => {
+                assert!(matches!(callback.team_id, Cow::Borrowed(_)));
+                match callback.event {
+                    SlackEventCallbackBodyRef::Message(message) => {
+                        assert_eq!(message.subtype.as_deref(), Some("message_changed"));
+                        assert!(matches!(message.ts, Cow::Borrowed(_)));
+                    }
+                    other => panic!("Unexpected event: {other:?}"),
+                }
+            }
+            other => panic!("Unexpected event: {other:?}"),
+        }
+
+        let reaction_payload = include_str!("./events/fixtures/reaction_added.json");
+        assert!(matches!(
+            serde_json::from_str::<SlackPushEventRef>(reaction_payload).unwrap(),
+            SlackPushEventRef::EventCallback(SlackPushEventCallbackRef {
+                event: SlackEventCallbackBodyRef::Other,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_borrowed_history_page() {
+        let payload = include_str!("../api/fixtures/slack_api_conversations_history_response.json");
+        let page: SlackConversationsHistoryPageRef = serde_json::from_str(payload).unwrap();
+        assert_eq!(page.messages.len(), 3);
+        assert_eq!(page.messages[0].reply_count, Some(2));
+        assert!(matches!(page.messages[0].text, Some(Cow::Borrowed(_))));
+    }
+}
@@ -0,0 +1,28 @@
+use crate::*;
+
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// Call participant: either a Slack user or an external user
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCallUser {
+    pub slack_id: Option<SlackUserId>,
+    pub external_id: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<Url>,
+}
+
+impl SlackCallUser {
+    pub fn slack_user(user_id: SlackUserId) -> Self {
+        Self::new().with_slack_id(user_id)
+    }
+
+    pub fn external_user(external_id: String, display_name: String) -> Self {
+        Self::new()
+            .with_external_id(external_id)
+            .with_display_name(display_name)
+    }
+}
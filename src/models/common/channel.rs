@@ -85,3 +85,13 @@ impl HasChannelInfo for SlackBasicChannelInfo {
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackChannelPriority(pub f64);
+
+/// `#general (C123)`, or only the channel ID for channels without names (e.g. DMs)
+impl std::fmt::Display for SlackChannelInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name.as_ref() {
+            Some(name) => write!(f, "#{} ({})", name, self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
@@ -28,6 +28,10 @@ mod star;
 
 pub use star::*;
 
+mod call;
+
+pub use call::*;
+
 mod bot;
 
 pub use bot::*;
@@ -44,6 +48,14 @@ pub use formatters::*;
 pub struct SlackTs(pub String);
 
 impl SlackTs {
+    pub fn from_date_time(date_time: &DateTime<Utc>) -> Self {
+        SlackTs(format!(
+            "{}.{:06}",
+            date_time.timestamp(),
+            date_time.timestamp_subsec_micros()
+        ))
+    }
+
     pub fn to_date_time_opt(&self) -> Option<DateTime<Utc>> {
         let parts: Vec<&str> = self.value().split('.').collect();
         if let Ok(ts_int) = parts[0].parse::<i64>() {
@@ -56,12 +68,44 @@ impl SlackTs {
             None
         }
     }
+
+    /// Seconds and microseconds of the `1234567890.000100` format
+    pub fn to_parts(&self) -> Option<(u64, u32)> {
+        let (seconds, micros) = self.value().split_once('.').unwrap_or((self.value(), ""));
+        let micros = if micros.is_empty() {
+            0
+        } else if micros.len() <= 6 && micros.bytes().all(|b| b.is_ascii_digit()) {
+            micros.parse::<u32>().ok()? * 10u32.pow(6 - micros.len() as u32)
+        } else {
+            return None;
+        };
+        Some((seconds.parse().ok()?, micros))
+    }
+}
+
+impl Ord for SlackTs {
+    /// Chronological order (timestamps with an unexpected format are ordered after valid ones)
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        match (self.to_parts(), other.to_parts()) {
+            (Some(parts), Some(other_parts)) => parts.cmp(&other_parts),
+            (Some(_), None) => cmp::Ordering::Less,
+            (None, Some(_)) => cmp::Ordering::Greater,
+            (None, None) => cmp::Ordering::Equal,
+        }
+        .then_with(|| self.value().cmp(other.value()))
+    }
+}
+
+impl PartialOrd for SlackTs {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackScheduledMid(pub String);
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Default, Serialize, Deserialize, ValueStruct)]
 pub struct SlackTeamId(pub String);
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
@@ -88,6 +132,18 @@ pub struct SlackChannelType(pub String);
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackConversationId(pub String);
 
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackCallId(pub String);
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackCanvasId(pub String);
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackCanvasSectionId(pub String);
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackWorkflowTriggerId(pub String);
+
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackActionId(pub String);
 
@@ -115,6 +171,21 @@ pub struct SlackDateTime(#[serde(with = "ts_seconds")] pub DateTime<Utc>);
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackLocale(pub String);
 
+impl SlackLocale {
+    /// Language code, e.g. `en` for `en-US`
+    pub fn language(&self) -> &str {
+        self.0.split(['-', '_']).next().unwrap_or_default()
+    }
+
+    /// Region code if specified, e.g. `US` for `en-US`
+    pub fn region(&self) -> Option<&str> {
+        self.0
+            .split_once(['-', '_'])
+            .map(|(_, region)| region)
+            .filter(|region| !region.is_empty())
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackCursorId(pub String);
 
@@ -260,4 +331,15 @@ mod test {
         let json = serde_json::to_value(&dt).unwrap();
         assert_eq!(json.as_u64().unwrap(), 1577839362);
     }
+
+    #[test]
+    fn test_slack_ts_order() {
+        let ts = |value: &str| SlackTs(value.into());
+        assert_eq!(ts("1234567890.000100").to_parts(), Some((1234567890, 100)));
+        assert_eq!(ts("1234567890.1").to_parts(), Some((1234567890, 100000)));
+        assert_eq!(ts("invalid").to_parts(), None);
+        assert!(ts("999999999.900000") < ts("1234567890.000100"));
+        assert!(ts("1234567890.000100") < ts("1234567890.000200"));
+        assert!(ts("1234567890.000100") < ts("invalid"));
+    }
 }
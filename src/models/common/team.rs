@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
 #[skip_serializing_none]
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, Builder)]
 pub struct SlackTeamInfo {
     pub id: SlackTeamId,
     pub name: Option<String>,
@@ -23,6 +23,38 @@ pub struct SlackBasicTeamInfo {
     pub domain: Option<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackEnterpriseInfo {
+    pub id: SlackEnterpriseId,
+    pub name: Option<String>,
+}
+
+/// Identifies an app installation: either a single workspace or a whole Enterprise Grid organization
+/// (for org-wide installations with `is_enterprise_install`).
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum SlackInstallationKey {
+    Team(SlackTeamId),
+    Enterprise(SlackEnterpriseId),
+}
+
+impl SlackInstallationKey {
+    pub fn from_ids(
+        team_id: Option<&SlackTeamId>,
+        enterprise_id: Option<&SlackEnterpriseId>,
+        is_enterprise_install: bool,
+    ) -> Option<Self> {
+        match (enterprise_id, team_id) {
+            (Some(enterprise_id), _) if is_enterprise_install => {
+                Some(SlackInstallationKey::Enterprise(enterprise_id.clone()))
+            }
+            (_, Some(team_id)) => Some(SlackInstallationKey::Team(team_id.clone())),
+            _ => None,
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackTeamProfile {
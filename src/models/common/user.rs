@@ -26,6 +26,31 @@ pub struct SlackUser {
     pub enterprise_user: Option<SlackEnterpriseUser>,
 }
 
+impl SlackUser {
+    /// The name Slack shows for the user: display name, real name or user name
+    /// (whatever is specified first).
+    pub fn visible_name(&self) -> Option<&str> {
+        let profile_display_name = self
+            .profile
+            .as_ref()
+            .and_then(|profile| profile.display_name.as_deref());
+        let profile_real_name = self
+            .profile
+            .as_ref()
+            .and_then(|profile| profile.real_name.as_deref());
+
+        [
+            profile_display_name,
+            profile_real_name,
+            self.real_name.as_deref(),
+            self.name.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|name| !name.is_empty())
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackUserProfile {
@@ -123,3 +148,13 @@ pub struct SlackEnterpriseUser {
     pub flags: SlackUserFlags,
     pub teams: Option<Vec<SlackTeamId>>,
 }
+
+/// `@jane (U123)`, or only the user ID for users without names
+impl std::fmt::Display for SlackUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name.as_deref().or_else(|| self.visible_name()) {
+            Some(name) => write!(f, "@{} ({})", name, self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
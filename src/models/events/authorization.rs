@@ -1,13 +1,30 @@
 use rsb_derive::Builder;
+use rvstruct::ValueStruct;
 use serde::{Deserialize, Serialize};
-use serde_with::skip_serializing_none;
+use serde_with::{serde_as, skip_serializing_none};
 
 use crate::*;
 
+#[serde_as]
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackEventAuthorization {
+    pub enterprise_id: Option<SlackEnterpriseId>,
+    /// Empty for org-wide installations
+    #[serde(default)]
+    #[serde_as(as = "serde_with::DefaultOnNull")]
     pub team_id: SlackTeamId,
     pub user_id: SlackUserId,
     pub is_bot: Option<bool>,
+    pub is_enterprise_install: Option<bool>,
+}
+
+impl SlackEventAuthorization {
+    pub fn installation_key(&self) -> Option<SlackInstallationKey> {
+        SlackInstallationKey::from_ids(
+            Some(&self.team_id).filter(|team_id| !team_id.value().is_empty()),
+            self.enterprise_id.as_ref(),
+            self.is_enterprise_install.unwrap_or(false),
+        )
+    }
 }
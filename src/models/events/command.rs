@@ -9,6 +9,9 @@ use crate::*;
 pub struct SlackCommandEvent {
     pub team_id: SlackTeamId,
     pub team_domain: Option<String>,
+    pub enterprise_id: Option<SlackEnterpriseId>,
+    pub enterprise_name: Option<String>,
+    pub is_enterprise_install: Option<bool>,
     pub channel_id: SlackChannelId,
     pub channel_name: Option<String>,
     pub user_id: SlackUserId,
@@ -18,6 +21,17 @@ pub struct SlackCommandEvent {
     pub trigger_id: SlackTriggerId,
 }
 
+impl SlackCommandEvent {
+    pub fn installation_key(&self) -> SlackInstallationKey {
+        SlackInstallationKey::from_ids(
+            Some(&self.team_id),
+            self.enterprise_id.as_ref(),
+            self.is_enterprise_install.unwrap_or(false),
+        )
+        .unwrap_or_else(|| SlackInstallationKey::Team(self.team_id.clone()))
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackCommandEventResponse {
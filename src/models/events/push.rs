@@ -38,6 +38,7 @@ pub struct SlackAppRateLimitedEvent {
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackPushEventCallback {
     pub team_id: SlackTeamId,
+    pub enterprise_id: Option<SlackEnterpriseId>,
     pub api_app_id: SlackAppId,
     pub event: SlackEventCallbackBody,
     pub event_id: SlackEventId,
@@ -45,6 +46,20 @@ pub struct SlackPushEventCallback {
     pub event_context: Option<SlackEventContext>,
     pub authed_users: Option<Vec<SlackUserId>>,
     pub authorizations: Option<Vec<SlackEventAuthorization>>,
+    pub is_ext_shared_channel: Option<bool>,
+}
+
+impl SlackPushEventCallback {
+    /// Installation to find a token for this event.
+    /// Org-wide installations are resolved using the enterprise from authorizations,
+    /// otherwise the event's workspace is used.
+    pub fn installation_key(&self) -> SlackInstallationKey {
+        self.authorizations
+            .iter()
+            .flatten()
+            .find_map(|authorization| authorization.installation_key())
+            .unwrap_or_else(|| SlackInstallationKey::Team(self.team_id.clone()))
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -396,6 +411,110 @@ pub struct SlackUserStatusChangedEvent {
     pub cache_ts: SlackDateTime,
 }
 
+impl SlackEventCallbackBody {
+    /// Event type as sent by Slack (e.g. `app_mention`)
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            SlackEventCallbackBody::Message(_) => "message",
+            SlackEventCallbackBody::AppHomeOpened(_) => "app_home_opened",
+            SlackEventCallbackBody::AppMention(_) => "app_mention",
+            SlackEventCallbackBody::AppUninstalled(_) => "app_uninstalled",
+            SlackEventCallbackBody::LinkShared(_) => "link_shared",
+            SlackEventCallbackBody::EmojiChanged(_) => "emoji_changed",
+            SlackEventCallbackBody::MemberJoinedChannel(_) => "member_joined_channel",
+            SlackEventCallbackBody::MemberLeftChannel(_) => "member_left_channel",
+            SlackEventCallbackBody::ChannelCreated(_) => "channel_created",
+            SlackEventCallbackBody::ChannelDeleted(_) => "channel_deleted",
+            SlackEventCallbackBody::ChannelArchive(_) => "channel_archive",
+            SlackEventCallbackBody::ChannelRename(_) => "channel_rename",
+            SlackEventCallbackBody::ChannelUnarchive(_) => "channel_unarchive",
+            SlackEventCallbackBody::TeamJoin(_) => "team_join",
+            SlackEventCallbackBody::FileCreated(_) => "file_created",
+            SlackEventCallbackBody::FileChange(_) => "file_change",
+            SlackEventCallbackBody::FileDeleted(_) => "file_deleted",
+            SlackEventCallbackBody::FileShared(_) => "file_shared",
+            SlackEventCallbackBody::FileUnshared(_) => "file_unshared",
+            SlackEventCallbackBody::FilePublic(_) => "file_public",
+            SlackEventCallbackBody::ReactionAdded(_) => "reaction_added",
+            SlackEventCallbackBody::ReactionRemoved(_) => "reaction_removed",
+            SlackEventCallbackBody::StarAdded(_) => "star_added",
+            SlackEventCallbackBody::StarRemoved(_) => "star_removed",
+            SlackEventCallbackBody::UserChange(_) => "user_change",
+            SlackEventCallbackBody::UserStatusChanged(_) => "user_status_changed",
+        }
+    }
+}
+
+impl std::fmt::Display for SlackMessageEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_message_summary(
+            f,
+            &self.origin,
+            &self.sender,
+            self.content
+                .as_ref()
+                .and_then(|content| content.text.as_deref()),
+        )?;
+        if let Some(subtype) = self.subtype.as_ref() {
+            write!(f, " [{subtype:?}]")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for SlackEventCallbackBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlackEventCallbackBody::Message(event) => write!(f, "{event}"),
+            SlackEventCallbackBody::AppMention(event) => write!(
+                f,
+                "app_mention {} in {} from {}",
+                event.origin.ts, event.channel, event.user
+            ),
+            SlackEventCallbackBody::ReactionAdded(event) => {
+                write!(f, "reaction_added :{}: by {}", event.reaction, event.user)
+            }
+            SlackEventCallbackBody::ReactionRemoved(event) => {
+                write!(f, "reaction_removed :{}: by {}", event.reaction, event.user)
+            }
+            SlackEventCallbackBody::MemberJoinedChannel(event) => write!(
+                f,
+                "member_joined_channel {} in {}",
+                event.user, event.channel
+            ),
+            SlackEventCallbackBody::MemberLeftChannel(event) => {
+                write!(f, "member_left_channel {} in {}", event.user, event.channel)
+            }
+            other => write!(f, "{}", other.event_type()),
+        }
+    }
+}
+
+/// `event_callback Ev123 (team T123): message 1700000000.000100 in C123 from U456: Hello`
+impl std::fmt::Display for SlackPushEventCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "event_callback {} (team {}): {}",
+            self.event_id, self.team_id, self.event
+        )
+    }
+}
+
+impl std::fmt::Display for SlackPushEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlackPushEvent::UrlVerification(_) => write!(f, "url_verification"),
+            SlackPushEvent::EventCallback(event) => write!(f, "{event}"),
+            SlackPushEvent::AppRateLimited(event) => write!(
+                f,
+                "app_rate_limited (team {}, minute {})",
+                event.team_id, event.minute_rate_limited.0
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -457,4 +576,34 @@ mod test {
             _ => panic!("Unexpected event type"),
         }
     }
+
+    #[test]
+    fn test_slack_event_org_install_installation_key() {
+        let payload = include_str!("./fixtures/app_mention_org_install.json");
+        let event: SlackPushEventCallback = serde_json::from_str(payload).unwrap();
+        assert_eq!(event.enterprise_id, Some("EXXXXXXXXXX".into()));
+        assert_eq!(
+            event.installation_key(),
+            SlackInstallationKey::Enterprise("EXXXXXXXXXX".into())
+        );
+
+        let payload = include_str!("./fixtures/message_changed.json");
+        let event: SlackPushEventCallback = serde_json::from_str(payload).unwrap();
+        assert_eq!(
+            event.installation_key(),
+            SlackInstallationKey::Team("TXXXXXXXXXX".into())
+        );
+    }
+
+    #[test]
+    fn test_slack_push_event_display() {
+        let payload = include_str!("./fixtures/message_changed.json");
+        let event: SlackPushEventCallback = serde_json::from_str(payload).unwrap();
+        let summary = SlackPushEvent::EventCallback(event.clone()).to_string();
+        assert!(summary.starts_with(&format!(
+            "event_callback {} (team {}): message ",
+            event.event_id, event.team_id
+        )));
+        assert_eq!(event.event.event_type(), "message");
+    }
 }
@@ -0,0 +1,110 @@
+//!
+//! JSON Schema definitions (with the `schemars` feature) for Slack primitive models
+//! (IDs, timestamps, URLs), matching their serde representation.
+//! Request and response models don't implement `JsonSchema` yet.
+//!
+
+use crate::models::blocks::SlackBlockId;
+use crate::models::events::{SlackEventContext, SlackEventId};
+use crate::models::socket_mode::SlackSocketModeEnvelopeId;
+use crate::*;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+macro_rules! slack_json_schema {
+    ($schema:tt => $($ty:ident),* $(,)?) => {
+        $(
+            impl JsonSchema for $ty {
+                fn inline_schema() -> bool {
+                    true
+                }
+
+                fn schema_name() -> Cow<'static, str> {
+                    stringify!($ty).into()
+                }
+
+                fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+                    json_schema!($schema)
+                }
+            }
+        )*
+    };
+}
+
+slack_json_schema!({ "type": "string" } =>
+    SlackActionId,
+    SlackActionType,
+    SlackApiTokenScope,
+    SlackAppId,
+    SlackAvatarHash,
+    SlackBlockId,
+    SlackBotId,
+    SlackCallId,
+    SlackCallbackId,
+    SlackCanvasId,
+    SlackCanvasSectionId,
+    SlackChannelId,
+    SlackChannelType,
+    SlackClientId,
+    SlackClientMessageId,
+    SlackClientSecret,
+    SlackColor,
+    SlackCommandId,
+    SlackConversationId,
+    SlackCursorId,
+    SlackEmoji,
+    SlackEnterpriseId,
+    SlackEnterpriseSubteamId,
+    SlackEventContext,
+    SlackEventId,
+    SlackEventType,
+    SlackFileCommentId,
+    SlackFileExternalType,
+    SlackFileId,
+    SlackFilePrettyType,
+    SlackFileSnippetType,
+    SlackFileType,
+    SlackLocale,
+    SlackMimeType,
+    SlackReactionName,
+    SlackScheduledMid,
+    SlackSigningSecret,
+    SlackSocketModeEnvelopeId,
+    SlackTeamId,
+    SlackTeamProfileFieldId,
+    SlackTriggerId,
+    SlackUnfurlId,
+    SlackUserGroupId,
+    SlackUserId,
+    SlackVerificationToken,
+    SlackViewId,
+    SlackWorkflowTriggerId,
+);
+
+slack_json_schema!({ "type": "string", "pattern": "^\\d+(\\.\\d+)?$" } => SlackTs);
+
+slack_json_schema!({ "type": "string", "format": "email" } => EmailAddress);
+
+slack_json_schema!({ "type": "string", "format": "uri" } =>
+    SlackFileUploadUrl,
+    SlackResponseUrl,
+    SlackTeamUrl,
+    SlackWebSocketsUrl,
+);
+
+// Unix timestamps in seconds
+slack_json_schema!({ "type": "integer", "format": "int64" } => SlackDateTime);
+
+slack_json_schema!({ "type": "number" } => SlackChannelPriority);
+
+#[test]
+fn check_primitive_json_schemas() {
+    assert_eq!(
+        schemars::schema_for!(Vec<SlackChannelId>).get("items"),
+        Some(&serde_json::json!({ "type": "string" }))
+    );
+    assert_eq!(
+        SlackDateTime::json_schema(&mut SchemaGenerator::default()).get("type"),
+        Some(&serde_json::json!("integer"))
+    );
+}
@@ -137,3 +137,57 @@ pub struct SlackMessageAttachmentFieldObject {
     pub value: Option<String>,
     pub short: Option<bool>,
 }
+
+impl std::fmt::Display for SlackMessageSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.user.as_ref(), self.bot_id.as_ref()) {
+            (Some(user), _) => write!(f, "{user}"),
+            (None, Some(bot_id)) => write!(f, "bot {bot_id}"),
+            (None, None) => write!(f, "unknown sender"),
+        }
+    }
+}
+
+/// Log-friendly message summary: `message 1700000000.000100 in C123 from U456: Hello...`
+pub(crate) fn fmt_message_summary(
+    f: &mut std::fmt::Formatter<'_>,
+    origin: &SlackMessageOrigin,
+    sender: &SlackMessageSender,
+    text: Option<&str>,
+) -> std::fmt::Result {
+    const TEXT_PREVIEW_CHARS: usize = 40;
+
+    write!(f, "message {}", origin.ts)?;
+    if let Some(channel) = origin.channel.as_ref() {
+        write!(f, " in {channel}")?;
+    }
+    if let Some(thread_ts) = origin.thread_ts.as_ref().filter(|ts| **ts != origin.ts) {
+        write!(f, " (thread {thread_ts})")?;
+    }
+    write!(f, " from {sender}")?;
+    if let Some(text) = text.filter(|text| !text.is_empty()) {
+        let preview: String = text
+            .chars()
+            .take(TEXT_PREVIEW_CHARS)
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        if text.chars().nth(TEXT_PREVIEW_CHARS).is_some() {
+            write!(f, ": {preview}...")?;
+        } else {
+            write!(f, ": {preview}")?;
+        }
+    }
+    Ok(())
+}
+
+impl std::fmt::Display for SlackMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_message_summary(f, &self.origin, &self.sender, self.content.text.as_deref())
+    }
+}
+
+impl std::fmt::Display for SlackHistoryMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_message_summary(f, &self.origin, &self.sender, self.content.text.as_deref())
+    }
+}
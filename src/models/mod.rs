@@ -15,11 +15,15 @@ mod common;
 
 pub mod apps;
 pub mod blocks;
+pub mod borrowed;
 pub mod events;
 pub mod files;
 mod messages;
 pub mod socket_mode;
 
+#[cfg(feature = "schemars")]
+mod json_schema;
+
 pub use apps::*;
 pub use common::*;
 pub use files::*;
@@ -0,0 +1,418 @@
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::token::*;
+use crate::{ClientResult, SlackClient, SlackClientHttpConnector};
+use base64::prelude::*;
+use ring::signature;
+use rsb_derive::Builder;
+use rvstruct::*;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct SlackOpenIdTokenVerifier {
+    client_id: SlackClientId,
+    keys: SlackOpenIdConnectKeysResponse,
+    leeway: Duration,
+    issuer: String,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+struct SlackOpenIdTokenHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+impl SlackOpenIdTokenVerifier {
+    pub const SUPPORTED_ALGORITHM: &'static str = "RS256";
+    pub const DEFAULT_LEEWAY: Duration = Duration::from_secs(60);
+
+    pub fn new(client_id: &SlackClientId, keys: SlackOpenIdConnectKeysResponse) -> Self {
+        Self {
+            client_id: client_id.clone(),
+            keys,
+            leeway: Self::DEFAULT_LEEWAY,
+            issuer: SlackOpenIdConnectConfig::OPENID_ISSUER_VALUE.into(),
+        }
+    }
+
+    pub fn with_leeway(self, leeway: Duration) -> Self {
+        Self { leeway, ..self }
+    }
+
+    /// Expected issuer for region-specific endpoints (see `SlackApiEndpoints::openid_issuer`)
+    pub fn with_issuer(self, issuer: &str) -> Self {
+        Self {
+            issuer: issuer.into(),
+            ..self
+        }
+    }
+
+    pub fn verify(
+        &self,
+        id_token: &SlackOpenIdToken,
+        expected_nonce: Option<&SlackOpenIdNonce>,
+    ) -> Result<SlackOpenIdConnectIdTokenClaims, SlackOpenIdTokenVerifierError> {
+        let parts: Vec<&str> = id_token.value().split('.').collect();
+        let (encoded_header, encoded_claims, encoded_signature) = match parts.as_slice() {
+            [header, claims, signature] => (*header, *claims, *signature),
+            _ => {
+                return Err(Self::malformed_error(
+                    "ID token must contain exactly three parts",
+                ))
+            }
+        };
+
+        let header: SlackOpenIdTokenHeader = Self::decode_json_part(encoded_header)?;
+        if header.alg != Self::SUPPORTED_ALGORITHM {
+            return Err(Self::malformed_error(
+                format!("Unsupported ID token algorithm: {}", header.alg).as_str(),
+            ));
+        }
+
+        let kid = header
+            .kid
+            .ok_or_else(|| Self::malformed_error("ID token header has no key id"))?;
+        let key = self
+            .keys
+            .keys
+            .iter()
+            .find(|key| key.kty == "RSA" && key.kid == kid)
+            .ok_or_else(|| {
+                SlackOpenIdTokenVerifierError::WrongSignatureError(
+                    SlackOpenIdWrongSignatureError::new().with_kid(kid.clone()),
+                )
+            })?;
+
+        let signature_bytes = Self::decode_part(encoded_signature)?;
+        let public_key = signature::RsaPublicKeyComponents {
+            n: Self::decode_part(&key.n)?,
+            e: Self::decode_part(&key.e)?,
+        };
+        public_key
+            .verify(
+                &signature::RSA_PKCS1_2048_8192_SHA256,
+                format!("{encoded_header}.{encoded_claims}").as_bytes(),
+                &signature_bytes,
+            )
+            .map_err(|_| {
+                SlackOpenIdTokenVerifierError::WrongSignatureError(
+                    SlackOpenIdWrongSignatureError::new().with_kid(key.kid.clone()),
+                )
+            })?;
+
+        let claims: SlackOpenIdConnectIdTokenClaims = Self::decode_json_part(encoded_claims)?;
+        self.verify_claims(&claims, expected_nonce)?;
+        Ok(claims)
+    }
+
+    fn verify_claims(
+        &self,
+        claims: &SlackOpenIdConnectIdTokenClaims,
+        expected_nonce: Option<&SlackOpenIdNonce>,
+    ) -> Result<(), SlackOpenIdTokenVerifierError> {
+        if claims.iss != self.issuer {
+            Err(Self::claims_error(
+                format!("Unexpected issuer: {}", claims.iss).as_str(),
+            ))
+        } else if claims.aud != self.client_id {
+            Err(Self::claims_error(
+                format!("Unexpected audience: {}", claims.aud).as_str(),
+            ))
+        } else if claims.exp.value().timestamp() + (self.leeway.as_secs() as i64)
+            < chrono::Utc::now().timestamp()
+        {
+            Err(Self::claims_error("ID token is expired"))
+        } else {
+            match expected_nonce {
+                Some(nonce) if claims.nonce.as_ref() != Some(nonce) => {
+                    Err(Self::claims_error("ID token nonce doesn't match"))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    fn decode_part(part: &str) -> Result<Vec<u8>, SlackOpenIdTokenVerifierError> {
+        BASE64_URL_SAFE_NO_PAD
+            .decode(part.trim_end_matches('='))
+            .map_err(|err| Self::malformed_error(err.to_string().as_str()))
+    }
+
+    fn decode_json_part<T>(part: &str) -> Result<T, SlackOpenIdTokenVerifierError>
+    where
+        T: for<'de> serde::de::Deserialize<'de>,
+    {
+        serde_json::from_slice(&Self::decode_part(part)?)
+            .map_err(|err| Self::malformed_error(err.to_string().as_str()))
+    }
+
+    fn malformed_error(message: &str) -> SlackOpenIdTokenVerifierError {
+        SlackOpenIdTokenVerifierError::MalformedTokenError(SlackOpenIdMalformedTokenError::new(
+            message.into(),
+        ))
+    }
+
+    fn claims_error(message: &str) -> SlackOpenIdTokenVerifierError {
+        SlackOpenIdTokenVerifierError::InvalidClaimsError(SlackOpenIdInvalidClaimsError::new(
+            message.into(),
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackOpenIdSignInResult {
+    pub token: SlackOpenIdConnectTokenResponse,
+    pub claims: SlackOpenIdConnectIdTokenClaims,
+    pub user_info: SlackOpenIdConnectUserInfoResponse,
+}
+
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    ///
+    /// Sign in with Slack: exchanges the received code, verifies the ID token
+    /// using Slack public keys and the expected nonce, and retrieves the user info.
+    ///
+    pub async fn openid_sign_in(
+        &self,
+        config: &SlackOpenIdConnectConfig,
+        code: &SlackOAuthCode,
+        expected_nonce: &SlackOpenIdNonce,
+    ) -> ClientResult<SlackOpenIdSignInResult> {
+        let token_resp = self
+            .openid_connect_token(
+                &SlackOpenIdConnectTokenRequest::new(
+                    config.client_id.clone(),
+                    config.client_secret.clone(),
+                )
+                .with_code(code.clone())
+                .with_redirect_uri(config.redirect_uri.clone()),
+            )
+            .await?;
+
+        let keys = self.openid_connect_keys().await?;
+
+        let claims = SlackOpenIdTokenVerifier::new(&config.client_id, keys)
+            .with_issuer(&self.endpoints().openid_issuer)
+            .verify(&token_resp.id_token, Some(expected_nonce))
+            .map_err(|err| {
+                SlackClientError::SystemError(
+                    SlackClientSystemError::new()
+                        .with_message("Slack OpenID Connect ID token verification failed".into())
+                        .with_cause(Box::new(err)),
+                )
+            })?;
+
+        let user_token = SlackApiToken::new(token_resp.access_token.clone())
+            .with_team_id(claims.profile.team_id.clone())
+            .with_token_type(SlackApiTokenType::User);
+
+        let user_info = self
+            .open_session(&user_token)
+            .openid_connect_user_info()
+            .await?;
+
+        Ok(SlackOpenIdSignInResult::new(token_resp, claims, user_info))
+    }
+}
+
+#[derive(Debug)]
+pub enum SlackOpenIdTokenVerifierError {
+    MalformedTokenError(SlackOpenIdMalformedTokenError),
+    WrongSignatureError(SlackOpenIdWrongSignatureError),
+    InvalidClaimsError(SlackOpenIdInvalidClaimsError),
+}
+
+impl Display for SlackOpenIdTokenVerifierError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match *self {
+            SlackOpenIdTokenVerifierError::MalformedTokenError(ref err) => err.fmt(f),
+            SlackOpenIdTokenVerifierError::WrongSignatureError(ref err) => err.fmt(f),
+            SlackOpenIdTokenVerifierError::InvalidClaimsError(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for SlackOpenIdTokenVerifierError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            SlackOpenIdTokenVerifierError::MalformedTokenError(ref err) => Some(err),
+            SlackOpenIdTokenVerifierError::WrongSignatureError(ref err) => Some(err),
+            SlackOpenIdTokenVerifierError::InvalidClaimsError(ref err) => Some(err),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackOpenIdMalformedTokenError {
+    pub message: String,
+}
+
+impl Display for SlackOpenIdMalformedTokenError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack OpenID malformed ID token: {}", self.message)
+    }
+}
+
+impl Error for SlackOpenIdMalformedTokenError {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackOpenIdWrongSignatureError {
+    pub kid: Option<String>,
+}
+
+impl Display for SlackOpenIdWrongSignatureError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Slack OpenID ID token signature validation error. Key id: {}",
+            self.kid.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+impl Error for SlackOpenIdWrongSignatureError {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackOpenIdInvalidClaimsError {
+    pub message: String,
+}
+
+impl Display for SlackOpenIdInvalidClaimsError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack OpenID ID token claims error: {}", self.message)
+    }
+}
+
+impl Error for SlackOpenIdInvalidClaimsError {}
+
+#[cfg(test)]
+fn test_claims(
+    aud: &str,
+    nonce: &str,
+    exp: chrono::DateTime<chrono::Utc>,
+) -> SlackOpenIdConnectIdTokenClaims {
+    SlackOpenIdConnectIdTokenClaims::new(
+        SlackOpenIdConnectConfig::OPENID_ISSUER_VALUE.into(),
+        aud.into(),
+        SlackDateTime(exp),
+        SlackDateTime(chrono::Utc::now()),
+        SlackOpenIdConnectUserProfile::new("U1".into(), "U1".into(), "T1".into()),
+    )
+    .with_nonce(nonce.to_string().into())
+}
+
+#[test]
+fn check_valid_claims() {
+    let verifier = SlackOpenIdTokenVerifier::new(
+        &"test-client".into(),
+        SlackOpenIdConnectKeysResponse::new(vec![]),
+    );
+    let nonce: SlackOpenIdNonce = "test-nonce".to_string().into();
+    let claims = test_claims(
+        "test-client",
+        "test-nonce",
+        chrono::Utc::now() + chrono::Duration::minutes(5),
+    );
+
+    verifier
+        .verify_claims(&claims, Some(&nonce))
+        .expect("claims verification failed");
+}
+
+#[test]
+fn check_invalid_claims() {
+    let verifier = SlackOpenIdTokenVerifier::new(
+        &"test-client".into(),
+        SlackOpenIdConnectKeysResponse::new(vec![]),
+    );
+    let nonce: SlackOpenIdNonce = "test-nonce".to_string().into();
+    let in_future = chrono::Utc::now() + chrono::Duration::minutes(5);
+
+    for claims in [
+        test_claims("other-client", "test-nonce", in_future),
+        test_claims("test-client", "other-nonce", in_future),
+        test_claims(
+            "test-client",
+            "test-nonce",
+            chrono::Utc::now() - chrono::Duration::minutes(5),
+        ),
+    ] {
+        match verifier.verify_claims(&claims, Some(&nonce)) {
+            Err(SlackOpenIdTokenVerifierError::InvalidClaimsError(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn check_malformed_token() {
+    let verifier = SlackOpenIdTokenVerifier::new(
+        &"test-client".into(),
+        SlackOpenIdConnectKeysResponse::new(vec![]),
+    );
+    match verifier.verify(&"not-a-jwt".to_string().into(), None) {
+        Err(SlackOpenIdTokenVerifierError::MalformedTokenError(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+const TEST_ID_TOKEN_CLAIMS: &str = "eyJpc3MiOiJodHRwczovL3NsYWNrLmNvbSIsImF1ZCI6InRlc3QtY2xpZW50IiwiZXhwIjo0MTAyNDQ0ODAwLCJpYXQiOjE3MDAwMDAwMDAsIm5vbmNlIjoidGVzdC1ub25jZSIsInN1YiI6IlUxIiwiaHR0cHM6Ly9zbGFjay5jb20vdXNlcl9pZCI6IlUxIiwiaHR0cHM6Ly9zbGFjay5jb20vdGVhbV9pZCI6IlQxIn0";
+
+#[cfg(test)]
+fn test_jwk_keys() -> SlackOpenIdConnectKeysResponse {
+    SlackOpenIdConnectKeysResponse::new(vec![SlackOpenIdConnectJsonWebKey::new(
+        "test-key".into(),
+        "RSA".into(),
+        "3FbsSB4w81HJEWG7vMMxJILVeAuAiZykpQmEHO9SyzlhRhsjDF5UVgBvU9jwhEx9q9V2pPb5MNtr6YTnhag8jlUJLKFKq7Jvxh1bVskfMfxv-fipdMG85Y9A7Cjb-I1eRAVgaNryekpfd2UDNJmeUqi3c7mSsbF-DluuR-MqF-hky7RPZ8fvb7sHa7zaVtQP5ApVYYCFszq_DW60Mem71YYGOektJC7s64fi4jHA6QWOEKqxCFvU-QXASEJT5jlC5mJgr0AMiCGyk_ekY9mtqHDfoOBkqH7txfaam8CyIkM-fJxnC7OJ3td9PVogEh5TMYPPInHybtE7TAzBQAWJYw".into(),
+        "AQAB".into(),
+    )])
+}
+
+#[test]
+fn check_rs256_signature() {
+    let verifier = SlackOpenIdTokenVerifier::new(&"test-client".into(), test_jwk_keys());
+    let nonce: SlackOpenIdNonce = "test-nonce".to_string().into();
+    // Signed with the private key of the test JWK, header: `{"alg":"RS256","kid":"test-key","typ":"JWT"}`
+    let header = "eyJhbGciOiJSUzI1NiIsImtpZCI6InRlc3Qta2V5IiwidHlwIjoiSldUIn0";
+    let signature = "TmB7mu7BS1nl_le_935iDYJmhikDq6pHfXsKPu1nsTIT5Etz_D26yfjp3U5019XhPl0XsO0GlS-HP5R2BLnaLIV_75PBAMd-BfTcQH-UJi5EcvtBjJpQB5aRIUmqspmA4Ih_4xpoXjG2EOMD352N4xXM7FJ2JCidko43s25ZGu-8GDpBoL9cOcQO9CjRjPGbPkBjIxZ0rAycsvJ11zy6igw01ZeDwZBxTxUSlUq4L82jY9y3dfNSXXNkVhiNnyD1cHuNN_HHG5FfoIdzjpaIMIeRplMGwfmFo9AYoCHmWoxulBfltNL8DkSn9JMqV57GMeCwoYHzVaLIAPawZE2_Tw";
+
+    let claims = verifier
+        .verify(
+            &format!("{header}.{TEST_ID_TOKEN_CLAIMS}.{signature}").into(),
+            Some(&nonce),
+        )
+        .expect("ID token verification failed");
+    assert_eq!(claims.profile.team_id, "T1".into());
+
+    let tampered_claims = TEST_ID_TOKEN_CLAIMS.replacen('e', "f", 1);
+    match verifier.verify(
+        &format!("{header}.{tampered_claims}.{signature}").into(),
+        Some(&nonce),
+    ) {
+        Err(SlackOpenIdTokenVerifierError::WrongSignatureError(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn check_token_without_kid() {
+    let verifier = SlackOpenIdTokenVerifier::new(&"test-client".into(), test_jwk_keys());
+    // A valid signature of the test JWK, header: `{"alg":"RS256","typ":"JWT"}`
+    let header = "eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCJ9";
+    let signature = "X0ZckiG1rGjuSO05CuHBSYVu9RXPgTrsMotUALY28opH1R0pop76kDXMEmYKAzmuo1ZxffN3EXQLOTG1yYSOKE9WyWjlJNlFjAgKxscbe8Istqm2nkIBi1yFVgaVnzhny11Om77HKnzH6AjsnqSSEErxuVQfgpyIvIL25kyI0zUZKyHlk52tSKpQE60EIlo6Aus1D461hUtGMtspjqF53KLyLKePM9X2gqcdEvbcIEylANjfXYe_SsMpKXhhm63S3mxpxzPmUjFZh8C2hETnvVM7MOEbUAcKr4qthb8xqPxaJdsZYkBF7R_L_OwmrywcDXdhGAqt05KV7h4evwr3Ow";
+
+    match verifier.verify(
+        &format!("{header}.{TEST_ID_TOKEN_CLAIMS}.{signature}").into(),
+        None,
+    ) {
+        Err(SlackOpenIdTokenVerifierError::MalformedTokenError(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
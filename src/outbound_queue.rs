@@ -0,0 +1,371 @@
+//!
+//! Outbound messages queue: keeps messages that can't be delivered now
+//! (e.g. during Slack outages) in a pluggable store, and delivers them later in order.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SlackOutboundQueueEntry {
+    /// Sequence number defining the delivery order
+    pub seq: u64,
+    pub request: SlackApiChatPostMessageRequest,
+    pub enqueued_at: SlackDateTime,
+}
+
+/// Queue store. Implementations must keep entries in the `seq` order
+/// and should be durable to survive app restarts.
+pub trait SlackOutboundQueueStore: Send + Sync {
+    fn push(
+        &self,
+        request: SlackApiChatPostMessageRequest,
+    ) -> AnyStdResult<SlackOutboundQueueEntry>;
+
+    /// The oldest entry without removing it
+    fn peek(&self) -> AnyStdResult<Option<SlackOutboundQueueEntry>>;
+
+    fn remove(&self, entry: &SlackOutboundQueueEntry) -> AnyStdResult<()>;
+
+    fn len(&self) -> AnyStdResult<usize>;
+
+    fn is_empty(&self) -> AnyStdResult<bool> {
+        Ok(self.len()? == 0)
+    }
+}
+
+/// Non-durable store, useful for tests or when losing messages on restarts is acceptable
+#[derive(Debug, Default)]
+pub struct SlackMemoryOutboundQueueStore {
+    entries: Mutex<(u64, VecDeque<SlackOutboundQueueEntry>)>,
+}
+
+impl SlackMemoryOutboundQueueStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SlackOutboundQueueStore for SlackMemoryOutboundQueueStore {
+    fn push(
+        &self,
+        request: SlackApiChatPostMessageRequest,
+    ) -> AnyStdResult<SlackOutboundQueueEntry> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.0 += 1;
+        let entry = SlackOutboundQueueEntry {
+            seq: entries.0,
+            request,
+            enqueued_at: SlackDateTime(chrono::Utc::now()),
+        };
+        entries.1.push_back(entry.clone());
+        Ok(entry)
+    }
+
+    fn peek(&self) -> AnyStdResult<Option<SlackOutboundQueueEntry>> {
+        Ok(self.entries.lock().unwrap().1.front().cloned())
+    }
+
+    fn remove(&self, entry: &SlackOutboundQueueEntry) -> AnyStdResult<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .1
+            .retain(|existing| existing.seq != entry.seq);
+        Ok(())
+    }
+
+    fn len(&self) -> AnyStdResult<usize> {
+        Ok(self.entries.lock().unwrap().1.len())
+    }
+}
+
+/// Durable store keeping each entry in a directory as `<seq>.json`.
+/// Entries are written to a temporary `.part` file first, so partially written entries are never read.
+#[derive(Debug)]
+pub struct SlackFileOutboundQueueStore {
+    dir: PathBuf,
+    last_seq: Mutex<u64>,
+}
+
+impl SlackFileOutboundQueueStore {
+    const ENTRY_EXTENSION: &'static str = "json";
+
+    pub fn new<P: Into<PathBuf>>(dir: P) -> AnyStdResult<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        let store = Self {
+            dir,
+            last_seq: Mutex::new(0),
+        };
+        let last_seq = store.entry_seqs()?.last().copied().unwrap_or(0);
+        *store.last_seq.lock().unwrap() = last_seq;
+        Ok(store)
+    }
+
+    fn entry_path(&self, seq: u64) -> PathBuf {
+        self.dir
+            .join(format!("{:020}.{}", seq, Self::ENTRY_EXTENSION))
+    }
+
+    fn entry_seqs(&self) -> AnyStdResult<Vec<u64>> {
+        let mut seqs = Vec::new();
+        for dir_entry in std::fs::read_dir(&self.dir)? {
+            let path = dir_entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some(Self::ENTRY_EXTENSION) {
+                if let Some(seq) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.parse().ok())
+                {
+                    seqs.push(seq);
+                }
+            }
+        }
+        seqs.sort_unstable();
+        Ok(seqs)
+    }
+}
+
+impl SlackOutboundQueueStore for SlackFileOutboundQueueStore {
+    fn push(
+        &self,
+        request: SlackApiChatPostMessageRequest,
+    ) -> AnyStdResult<SlackOutboundQueueEntry> {
+        let mut last_seq = self.last_seq.lock().unwrap();
+        let entry = SlackOutboundQueueEntry {
+            seq: *last_seq + 1,
+            request,
+            enqueued_at: SlackDateTime(chrono::Utc::now()),
+        };
+        let path = self.entry_path(entry.seq);
+        let part_path = path.with_extension("part");
+        std::fs::write(&part_path, serde_json::to_vec(&entry)?)?;
+        std::fs::rename(&part_path, &path)?;
+        *last_seq = entry.seq;
+        Ok(entry)
+    }
+
+    fn peek(&self) -> AnyStdResult<Option<SlackOutboundQueueEntry>> {
+        let _last_seq = self.last_seq.lock().unwrap();
+        match self.entry_seqs()?.first() {
+            Some(seq) => Ok(Some(serde_json::from_slice(&std::fs::read(
+                self.entry_path(*seq),
+            )?)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn remove(&self, entry: &SlackOutboundQueueEntry) -> AnyStdResult<()> {
+        let _last_seq = self.last_seq.lock().unwrap();
+        match std::fs::remove_file(self.entry_path(entry.seq)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn len(&self) -> AnyStdResult<usize> {
+        Ok(self.entry_seqs()?.len())
+    }
+}
+
+#[derive(Debug)]
+pub enum SlackOutboundPostResult {
+    Delivered(SlackApiChatPostMessageResponse),
+    Enqueued(SlackOutboundQueueEntry),
+}
+
+#[derive(Debug, Default)]
+pub struct SlackOutboundQueueDrainReport {
+    pub delivered: Vec<SlackApiChatPostMessageResponse>,
+    /// Entries removed from the queue, because Slack rejected them (not transient errors)
+    pub rejected: Vec<(SlackOutboundQueueEntry, SlackClientError)>,
+    /// Transient error stopped draining, remaining entries are kept in the queue
+    pub stopped_by: Option<SlackClientError>,
+}
+
+impl SlackOutboundQueueDrainReport {
+    pub fn is_complete(&self) -> bool {
+        self.stopped_by.is_none()
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Posts a message, or enqueues it if Slack is unavailable (transient errors).
+    /// Messages are enqueued without trying to post them while the queue isn't empty,
+    /// to keep the delivery order.
+    pub async fn chat_post_message_or_enqueue<S>(
+        &self,
+        req: &SlackApiChatPostMessageRequest,
+        store: &S,
+    ) -> ClientResult<SlackOutboundPostResult>
+    where
+        S: SlackOutboundQueueStore + ?Sized,
+    {
+        if !store.is_empty().map_err(map_outbound_queue_store_error)? {
+            return Ok(SlackOutboundPostResult::Enqueued(
+                store
+                    .push(req.clone())
+                    .map_err(map_outbound_queue_store_error)?,
+            ));
+        }
+
+        match self.chat_post_message(req).await {
+            Ok(resp) => Ok(SlackOutboundPostResult::Delivered(resp)),
+            Err(err) if err.is_transient() => {
+                debug!("Slack is unavailable, enqueueing the message: {}", err);
+                Ok(SlackOutboundPostResult::Enqueued(
+                    store
+                        .push(req.clone())
+                        .map_err(map_outbound_queue_store_error)?,
+                ))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Delivers queued messages in order until the queue is empty or a transient error happens.
+    /// Call it periodically (or when connectivity returns) to drain the queue.
+    pub async fn drain_outbound_queue<S>(
+        &self,
+        store: &S,
+    ) -> ClientResult<SlackOutboundQueueDrainReport>
+    where
+        S: SlackOutboundQueueStore + ?Sized,
+    {
+        let mut report = SlackOutboundQueueDrainReport::default();
+
+        while let Some(entry) = store.peek().map_err(map_outbound_queue_store_error)? {
+            match self.chat_post_message(&entry.request).await {
+                Ok(resp) => report.delivered.push(resp),
+                Err(err) if err.is_transient() => {
+                    debug!(
+                        "Outbound queue draining stopped at {} with: {}",
+                        entry.seq, err
+                    );
+                    report.stopped_by = Some(err);
+                    break;
+                }
+                Err(err) => {
+                    warn!("Queued message {} was rejected: {}", entry.seq, err);
+                    store
+                        .remove(&entry)
+                        .map_err(map_outbound_queue_store_error)?;
+                    report.rejected.push((entry, err));
+                    continue;
+                }
+            }
+            store
+                .remove(&entry)
+                .map_err(map_outbound_queue_store_error)?;
+        }
+
+        Ok(report)
+    }
+}
+
+fn map_outbound_queue_store_error(err: BoxError) -> SlackClientError {
+    SlackClientError::SystemError(
+        SlackClientSystemError::new()
+            .with_message("Outbound queue store error".into())
+            .with_cause(err),
+    )
+}
+
+#[test]
+fn check_file_outbound_queue_store_order() {
+    let dir = std::env::temp_dir().join(format!(
+        "slack-morphism-outbound-queue-{}",
+        std::process::id()
+    ));
+    let store = SlackFileOutboundQueueStore::new(&dir).unwrap();
+    for text in ["first", "second"] {
+        store
+            .push(SlackApiChatPostMessageRequest::new(
+                "C1".into(),
+                SlackMessageContent::new().with_text(text.into()),
+            ))
+            .unwrap();
+    }
+
+    let reopened_store = SlackFileOutboundQueueStore::new(&dir).unwrap();
+    let head = reopened_store.peek().unwrap().unwrap();
+    assert_eq!(head.request.content.text, Some("first".into()));
+    reopened_store.remove(&head).unwrap();
+    let next = reopened_store
+        .push(SlackApiChatPostMessageRequest::new(
+            "C1".into(),
+            SlackMessageContent::new().with_text("third".into()),
+        ))
+        .unwrap();
+    assert_eq!(next.seq, 3);
+    assert_eq!(reopened_store.len().unwrap(), 2);
+    assert_eq!(
+        reopened_store.peek().unwrap().unwrap().request.content.text,
+        Some("second".into())
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn check_outbound_queue_keeps_messages_while_circuit_is_open() {
+    use crate::test_connector::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let slack_available = Arc::new(AtomicBool::new(false));
+    let connector_slack_available = slack_available.clone();
+    let connector = SlackTestConnector::new(move |call| {
+        if connector_slack_available.load(Ordering::SeqCst) {
+            Ok(serde_json::json!({ "channel": "C1", "ts": "1.1", "message": { "ts": "1.1" } }))
+        } else {
+            Err(SlackClientError::CircuitBreakerOpenError(
+                SlackClientCircuitBreakerOpenError::new(call.url.path().into()),
+            ))
+        }
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let store = SlackMemoryOutboundQueueStore::new();
+    let req = SlackApiChatPostMessageRequest::new(
+        "C1".into(),
+        SlackMessageContent::new().with_text("Hey".into()),
+    );
+
+    futures::executor::block_on(async {
+        let result = session
+            .chat_post_message_or_enqueue(&req, &store)
+            .await
+            .unwrap();
+        assert!(matches!(result, SlackOutboundPostResult::Enqueued(_)));
+
+        let report = session.drain_outbound_queue(&store).await.unwrap();
+        assert!(!report.is_complete());
+        assert!(report.rejected.is_empty());
+        assert_eq!(store.len().unwrap(), 1);
+
+        slack_available.store(true, Ordering::SeqCst);
+        let report = session.drain_outbound_queue(&store).await.unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.delivered.len(), 1);
+        assert!(store.is_empty().unwrap());
+    });
+
+    let posts = connector.calls_of("chat.postMessage");
+    assert_eq!(posts.len(), 3);
+    assert!(posts
+        .iter()
+        .all(|call| call.param("channel") == Some("C1".into())));
+}
@@ -0,0 +1,111 @@
+//!
+//! Bot personas: per-message username and icon overrides for apps posting as multiple logical bots.
+//! Overrides require the `chat:write.customize` scope, otherwise Slack ignores them.
+//!
+
+use crate::api::*;
+use crate::*;
+use rsb_derive::Builder;
+use url::Url;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SlackBotPersonaIcon {
+    /// Emoji name like `:robot_face:`
+    Emoji(String),
+    Url(Url),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackBotPersona {
+    pub username: String,
+    pub icon: Option<SlackBotPersonaIcon>,
+}
+
+impl SlackBotPersona {
+    pub const REQUIRED_SCOPE: SlackScope = SlackScope::ChatWriteCustomize;
+
+    pub fn with_icon_emoji(self, emoji: String) -> Self {
+        self.with_icon(SlackBotPersonaIcon::Emoji(emoji))
+    }
+
+    pub fn with_icon_url(self, url: Url) -> Self {
+        self.with_icon(SlackBotPersonaIcon::Url(url))
+    }
+
+    fn icon_emoji(&self) -> Option<String> {
+        match self.icon.as_ref() {
+            Some(SlackBotPersonaIcon::Emoji(emoji)) => Some(emoji.clone()),
+            _ => None,
+        }
+    }
+
+    fn icon_url(&self) -> Option<String> {
+        match self.icon.as_ref() {
+            Some(SlackBotPersonaIcon::Url(url)) => Some(url.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Messages posted on behalf of a persona. Replaces username and icon overrides of the message.
+pub trait SlackBotPersonaMessage {
+    fn with_persona(self, persona: &SlackBotPersona) -> Self;
+}
+
+impl SlackBotPersonaMessage for SlackApiChatPostMessageRequest {
+    fn with_persona(self, persona: &SlackBotPersona) -> Self {
+        Self {
+            username: Some(persona.username.clone()),
+            icon_emoji: persona.icon_emoji(),
+            icon_url: persona.icon_url(),
+            ..self
+        }
+    }
+}
+
+impl SlackBotPersonaMessage for SlackApiChatPostEphemeralRequest {
+    fn with_persona(self, persona: &SlackBotPersona) -> Self {
+        Self {
+            username: Some(persona.username.clone()),
+            icon_emoji: persona.icon_emoji(),
+            icon_url: persona.icon_url(),
+            ..self
+        }
+    }
+}
+
+impl SlackBotPersonaMessage for SlackApiChatScheduleMessageRequest {
+    fn with_persona(self, persona: &SlackBotPersona) -> Self {
+        Self {
+            username: Some(persona.username.clone()),
+            icon_emoji: persona.icon_emoji(),
+            icon_url: persona.icon_url(),
+            ..self
+        }
+    }
+}
+
+impl SlackBotPersonaMessage for SlackMessageDefaults {
+    fn with_persona(self, persona: &SlackBotPersona) -> Self {
+        Self {
+            username: Some(persona.username.clone()),
+            icon_emoji: persona.icon_emoji(),
+            icon_url: persona.icon_url(),
+            ..self
+        }
+    }
+}
+
+#[test]
+fn check_bot_persona_message() {
+    use crate::models::*;
+
+    let persona = SlackBotPersona::new("Deploy Bot".into()).with_icon_emoji(":rocket:".into());
+    let req = SlackApiChatPostMessageRequest::new("C1".into(), SlackMessageContent::new())
+        .with_icon_url("https://example.com/icon.png".into())
+        .with_persona(&persona);
+
+    assert_eq!(req.username.as_deref(), Some("Deploy Bot"));
+    assert_eq!(req.icon_emoji.as_deref(), Some(":rocket:"));
+    assert_eq!(req.icon_url, None);
+}
@@ -0,0 +1,301 @@
+use rsb_derive::Builder;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackApiCircuitBreakerConfig {
+    /// Failure rate (from 0.0 to 1.0) in the window to open the circuit
+    #[default = "SlackApiCircuitBreakerConfig::DEFAULT_FAILURE_RATE_THRESHOLD"]
+    pub failure_rate_threshold: f64,
+    /// Minimal number of requests in the window before the failure rate is considered
+    #[default = "SlackApiCircuitBreakerConfig::DEFAULT_MIN_REQUESTS"]
+    pub min_requests: usize,
+    #[default = "SlackApiCircuitBreakerConfig::DEFAULT_WINDOW"]
+    pub window: Duration,
+    /// How long the circuit stays open before trial requests are allowed
+    #[default = "SlackApiCircuitBreakerConfig::DEFAULT_OPEN_DURATION"]
+    pub open_duration: Duration,
+    /// Trial requests allowed at the same time in the half-open state
+    #[default = "SlackApiCircuitBreakerConfig::DEFAULT_HALF_OPEN_MAX_REQUESTS"]
+    pub half_open_max_requests: usize,
+}
+
+impl SlackApiCircuitBreakerConfig {
+    pub const DEFAULT_FAILURE_RATE_THRESHOLD: f64 = 0.5;
+    pub const DEFAULT_MIN_REQUESTS: usize = 10;
+    pub const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+    pub const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+    pub const DEFAULT_HALF_OPEN_MAX_REQUESTS: usize = 1;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SlackApiCircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct SlackApiCircuitBreakerState {
+    state: SlackApiCircuitState,
+    outcomes: VecDeque<(Instant, bool)>,
+    opened_at: Option<Instant>,
+    half_open_requests: usize,
+}
+
+/// Error-rate based circuit breaker. Share the same instance (using `Arc`)
+/// between connectors to protect both Slack and your app during incidents.
+#[derive(Debug)]
+pub struct SlackApiCircuitBreaker {
+    pub config: SlackApiCircuitBreakerConfig,
+    state: Mutex<SlackApiCircuitBreakerState>,
+}
+
+impl SlackApiCircuitBreaker {
+    pub fn new(config: SlackApiCircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(SlackApiCircuitBreakerState {
+                state: SlackApiCircuitState::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+                half_open_requests: 0,
+            }),
+        }
+    }
+
+    pub fn state(&self) -> SlackApiCircuitState {
+        self.state_at(Instant::now())
+    }
+
+    /// Returns a permit to send a request, or None if a request shouldn't be sent now.
+    /// The request outcome should be recorded with the permit. Dropping it without
+    /// recording (e.g. for cancelled requests) is recorded as a failure,
+    /// so half-open trial slots are always released.
+    pub fn try_acquire(&self) -> Option<SlackApiCircuitBreakerPermit<'_>> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn state_at(&self, now: Instant) -> SlackApiCircuitState {
+        let mut state = self.state.lock().unwrap();
+        self.update_open_state(&mut state, now);
+        state.state
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Option<SlackApiCircuitBreakerPermit<'_>> {
+        let mut state = self.state.lock().unwrap();
+        self.update_open_state(&mut state, now);
+        let acquired = match state.state {
+            SlackApiCircuitState::Closed => true,
+            SlackApiCircuitState::Open => false,
+            SlackApiCircuitState::HalfOpen
+                if state.half_open_requests < self.config.half_open_max_requests =>
+            {
+                state.half_open_requests += 1;
+                true
+            }
+            SlackApiCircuitState::HalfOpen => false,
+        };
+        acquired.then(|| SlackApiCircuitBreakerPermit {
+            circuit_breaker: self,
+            recorded: false,
+        })
+    }
+
+    fn record_at(&self, success: bool, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        match state.state {
+            SlackApiCircuitState::HalfOpen if success => {
+                state.state = SlackApiCircuitState::Closed;
+                state.outcomes.clear();
+                state.opened_at = None;
+                state.half_open_requests = 0;
+            }
+            SlackApiCircuitState::HalfOpen => Self::open(&mut state, now),
+            SlackApiCircuitState::Open => {}
+            SlackApiCircuitState::Closed => {
+                state.outcomes.push_back((now, success));
+                while state
+                    .outcomes
+                    .front()
+                    .filter(|(at, _)| now.duration_since(*at) > self.config.window)
+                    .is_some()
+                {
+                    state.outcomes.pop_front();
+                }
+
+                let failures = state.outcomes.iter().filter(|(_, ok)| !ok).count();
+                if state.outcomes.len() >= self.config.min_requests
+                    && failures as f64 / state.outcomes.len() as f64
+                        >= self.config.failure_rate_threshold
+                {
+                    Self::open(&mut state, now);
+                }
+            }
+        }
+    }
+
+    fn update_open_state(&self, state: &mut SlackApiCircuitBreakerState, now: Instant) {
+        if let Some(opened_at) = state
+            .opened_at
+            .filter(|_| state.state == SlackApiCircuitState::Open)
+        {
+            if now.duration_since(opened_at) >= self.config.open_duration {
+                state.state = SlackApiCircuitState::HalfOpen;
+                state.half_open_requests = 0;
+            }
+        }
+    }
+
+    fn open(state: &mut SlackApiCircuitBreakerState, now: Instant) {
+        state.state = SlackApiCircuitState::Open;
+        state.outcomes.clear();
+        state.opened_at = Some(now);
+        state.half_open_requests = 0;
+    }
+}
+
+/// A permission to send a request acquired from the circuit breaker
+#[derive(Debug)]
+pub struct SlackApiCircuitBreakerPermit<'a> {
+    circuit_breaker: &'a SlackApiCircuitBreaker,
+    recorded: bool,
+}
+
+impl SlackApiCircuitBreakerPermit<'_> {
+    /// Records the request outcome, where failures are errors meaning that Slack is unavailable
+    /// (network and server errors), not API errors for a specific request.
+    pub fn record(mut self, success: bool) {
+        self.recorded = true;
+        self.circuit_breaker.record_at(success, Instant::now());
+    }
+}
+
+impl Drop for SlackApiCircuitBreakerPermit<'_> {
+    fn drop(&mut self) {
+        if !self.recorded {
+            self.circuit_breaker.record_at(false, Instant::now());
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackApiRetryBudgetConfig {
+    /// Retries allowed per each sent request
+    #[default = "SlackApiRetryBudgetConfig::DEFAULT_RETRY_RATIO"]
+    pub retry_ratio: f64,
+    /// Retries available from the start
+    #[default = "SlackApiRetryBudgetConfig::DEFAULT_MIN_RETRIES"]
+    pub min_retries: usize,
+    /// Maximum retries to accumulate
+    #[default = "SlackApiRetryBudgetConfig::DEFAULT_MAX_RETRIES"]
+    pub max_retries: usize,
+}
+
+impl SlackApiRetryBudgetConfig {
+    pub const DEFAULT_RETRY_RATIO: f64 = 0.2;
+    pub const DEFAULT_MIN_RETRIES: usize = 10;
+    pub const DEFAULT_MAX_RETRIES: usize = 100;
+}
+
+/// Retry budget shared across workers (using `Arc`), so retries can't multiply the load during incidents.
+#[derive(Debug)]
+pub struct SlackApiRetryBudget {
+    pub config: SlackApiRetryBudgetConfig,
+    balance: Mutex<f64>,
+}
+
+impl SlackApiRetryBudget {
+    pub fn new(config: SlackApiRetryBudgetConfig) -> Self {
+        let balance = config.min_retries as f64;
+        Self {
+            config,
+            balance: Mutex::new(balance),
+        }
+    }
+
+    /// Records a sent (not retried) request
+    pub fn deposit(&self) {
+        let mut balance = self.balance.lock().unwrap();
+        *balance = (*balance + self.config.retry_ratio).min(self.config.max_retries as f64);
+    }
+
+    /// Returns false if the budget is exhausted and a request shouldn't be retried
+    pub fn try_withdraw(&self) -> bool {
+        let mut balance = self.balance.lock().unwrap();
+        if *balance >= 1.0 {
+            *balance -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn available_retries(&self) -> usize {
+        *self.balance.lock().unwrap() as usize
+    }
+}
+
+#[test]
+fn check_circuit_breaker_states() {
+    let breaker = SlackApiCircuitBreaker::new(
+        SlackApiCircuitBreakerConfig::new()
+            .with_min_requests(4)
+            .with_open_duration(Duration::from_secs(10)),
+    );
+    let now = Instant::now();
+
+    breaker.record_at(true, now);
+    breaker.record_at(false, now);
+    breaker.record_at(true, now);
+    assert_eq!(breaker.state_at(now), SlackApiCircuitState::Closed);
+    breaker.record_at(false, now);
+    assert_eq!(breaker.state_at(now), SlackApiCircuitState::Open);
+    assert!(breaker.try_acquire_at(now).is_none());
+
+    let later = now + Duration::from_secs(10);
+    let trial = breaker.try_acquire_at(later).unwrap();
+    assert!(breaker.try_acquire_at(later).is_none());
+    trial.record(false);
+    assert_eq!(breaker.state_at(later), SlackApiCircuitState::Open);
+
+    let much_later = later + Duration::from_secs(10);
+    breaker.try_acquire_at(much_later).unwrap().record(true);
+    assert_eq!(breaker.state_at(much_later), SlackApiCircuitState::Closed);
+}
+
+#[test]
+fn check_circuit_breaker_cancelled_trial() {
+    let breaker = SlackApiCircuitBreaker::new(
+        SlackApiCircuitBreakerConfig::new()
+            .with_min_requests(1)
+            .with_open_duration(Duration::from_secs(10)),
+    );
+    breaker.try_acquire().unwrap().record(false);
+    assert_eq!(breaker.state(), SlackApiCircuitState::Open);
+
+    let later = Instant::now() + Duration::from_secs(10);
+    // A trial request cancelled without recording its outcome releases the half-open slot
+    drop(breaker.try_acquire_at(later).unwrap());
+    assert_eq!(breaker.state_at(later), SlackApiCircuitState::Open);
+
+    let much_later = later + Duration::from_secs(10);
+    breaker.try_acquire_at(much_later).unwrap().record(true);
+    assert_eq!(breaker.state_at(much_later), SlackApiCircuitState::Closed);
+}
+
+#[test]
+fn check_retry_budget() {
+    let budget = SlackApiRetryBudget::new(
+        SlackApiRetryBudgetConfig::new()
+            .with_min_retries(1)
+            .with_retry_ratio(0.5),
+    );
+    assert!(budget.try_withdraw());
+    assert!(!budget.try_withdraw());
+    budget.deposit();
+    budget.deposit();
+    assert_eq!(budget.available_retries(), 1);
+    assert!(budget.try_withdraw());
+}
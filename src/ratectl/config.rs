@@ -18,12 +18,66 @@ pub struct SlackApiRateControlConfig {
     #[default = "SLACK_TIERS_DEFAULT_LIMITS_MAP.clone()"]
     pub tiers_limits: HashMap<SlackApiMethodRateTier, SlackApiRateControlLimit>,
 
+    /// Retries requiring a longer delay aren't made and fail with the original error
     pub max_delay_timeout: Option<std::time::Duration>,
     pub max_retries: Option<usize>,
+
+    #[default = "SlackApiRateControlRetryBackoffConfig::new()"]
+    pub retry_backoff: SlackApiRateControlRetryBackoffConfig,
+
+    #[default = "SlackApiRateControlBackpressureConfig::new()"]
+    pub backpressure: SlackApiRateControlBackpressureConfig,
 }
 
 impl SlackApiRateControlConfig {}
 
+/// Team limit applied after Slack signalled event delivery throttling (`app_rate_limited` events),
+/// so event-driven API usage automatically slows down.
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackApiRateControlBackpressureConfig {
+    #[default = "SlackApiRateControlBackpressureConfig::DEFAULT_LIMIT"]
+    pub limit: SlackApiRateControlLimit,
+    /// How long the limit is applied after the last signal
+    #[default = "SlackApiRateControlBackpressureConfig::DEFAULT_DURATION"]
+    pub duration: std::time::Duration,
+}
+
+impl SlackApiRateControlBackpressureConfig {
+    pub const DEFAULT_LIMIT: SlackApiRateControlLimit = SlackApiRateControlLimit {
+        value: 1,
+        per: std::time::Duration::from_secs(1),
+    };
+    pub const DEFAULT_DURATION: std::time::Duration = std::time::Duration::from_secs(60);
+}
+
+/// Exponential backoff for retries of rate limit errors without `Retry-After`
+/// and (optionally) server errors.
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackApiRateControlRetryBackoffConfig {
+    #[default = "SlackApiRateControlRetryBackoffConfig::DEFAULT_INITIAL_DELAY"]
+    pub initial_delay: std::time::Duration,
+    #[default = "SlackApiRateControlRetryBackoffConfig::DEFAULT_MAX_DELAY"]
+    pub max_delay: std::time::Duration,
+    /// Retry server errors and network failures of GET requests as well.
+    /// POST requests (such as `chat.postMessage`) aren't retried, since they might have been
+    /// already processed by Slack.
+    #[default = "false"]
+    pub retry_server_errors: bool,
+}
+
+impl SlackApiRateControlRetryBackoffConfig {
+    pub const DEFAULT_INITIAL_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+    pub const DEFAULT_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+    /// Delay before the retry after `retried` previous retries
+    pub fn delay_for(&self, retried: usize) -> std::time::Duration {
+        self.initial_delay
+            .checked_mul(2u32.saturating_pow(retried.min(u32::MAX as usize) as u32))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
 lazy_static! {
     pub static ref SLACK_TIER1_METHOD_CONFIG: SlackApiMethodRateControlConfig =
         SlackApiMethodRateControlConfig::new().with_tier(SlackApiMethodRateTier::Tier1);
@@ -34,3 +88,11 @@ lazy_static! {
     pub static ref SLACK_TIER4_METHOD_CONFIG: SlackApiMethodRateControlConfig =
         SlackApiMethodRateControlConfig::new().with_tier(SlackApiMethodRateTier::Tier4);
 }
+
+#[test]
+fn check_retry_backoff_delays() {
+    let backoff = SlackApiRateControlRetryBackoffConfig::new();
+    let delays: Vec<u64> = (0..8).map(|n| backoff.delay_for(n).as_secs()).collect();
+    assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+    assert_eq!(backoff.delay_for(100).as_secs(), 60);
+}
@@ -1,3 +1,4 @@
+mod circuit_breaker;
 mod config;
 mod limit;
 mod special_limit;
@@ -5,6 +6,7 @@ mod team_limits;
 mod throttler;
 mod throttling_counter;
 mod tier;
+pub use circuit_breaker::*;
 pub use config::*;
 pub use limit::*;
 pub use special_limit::*;
@@ -7,9 +7,16 @@ pub struct SlackTeamLimits {
     pub team_limit_counter: Option<ThrottlingCounter>,
     pub tier_limits: HashMap<SlackApiMethodRateTier, ThrottlingCounter>,
     pub special_limits: HashMap<SlackApiRateControlSpecialLimitKey, ThrottlingCounter>,
+    pub backpressure: Option<SlackTeamBackpressure>,
     pub updated: Instant,
 }
 
+#[derive(Debug)]
+pub struct SlackTeamBackpressure {
+    pub counter: ThrottlingCounter,
+    pub until: Instant,
+}
+
 impl SlackTeamLimits {
     pub fn new(rate_control_config: &SlackApiRateControlConfig) -> Self {
         Self {
@@ -19,6 +26,7 @@ impl SlackTeamLimits {
                 .map(|rl| rl.to_throttling_counter()),
             tier_limits: HashMap::new(),
             special_limits: HashMap::new(),
+            backpressure: None,
             updated: Instant::now(),
         }
     }
@@ -58,6 +58,22 @@ impl SlackRateThrottler {
                     .or_insert_with(|| SlackTeamLimits::new(&self.config));
                 team_limits.updated = now;
 
+                if team_limits
+                    .backpressure
+                    .as_ref()
+                    .is_some_and(|backpressure| backpressure.until <= now)
+                {
+                    team_limits.backpressure = None;
+                }
+
+                if let Some(ref mut backpressure) = team_limits.backpressure {
+                    backpressure.counter = backpressure.counter.update(now);
+
+                    if !backpressure.counter.delay().is_zero() {
+                        delays_heap.push(*backpressure.counter.delay())
+                    }
+                }
+
                 team_limits
                     .team_limit_counter
                     .as_ref()
@@ -120,4 +136,54 @@ impl SlackRateThrottler {
 
         delays_heap.pop()
     }
+
+    /// Applies the backpressure limit to the team (again) for the configured duration
+    pub fn signal_backpressure(&mut self, team_id: SlackTeamId) {
+        let now = Instant::now();
+        let backpressure_config = &self.config.backpressure;
+        let team_limits = self
+            .rate_limit_per_team
+            .entry(team_id)
+            .or_insert_with(|| SlackTeamLimits::new(&self.config));
+        team_limits.updated = now;
+
+        let until = now + backpressure_config.duration;
+        match team_limits.backpressure {
+            Some(ref mut backpressure) => backpressure.until = until,
+            None => {
+                team_limits.backpressure = Some(SlackTeamBackpressure {
+                    counter: backpressure_config.limit.to_throttling_counter(),
+                    until,
+                })
+            }
+        }
+    }
+
+    pub fn backpressure_until(&self, team_id: &SlackTeamId) -> Option<Instant> {
+        self.rate_limit_per_team
+            .get(team_id)
+            .and_then(|team_limits| team_limits.backpressure.as_ref())
+            .map(|backpressure| backpressure.until)
+            .filter(|until| *until > Instant::now())
+    }
+}
+
+#[test]
+fn check_backpressure_throttling() {
+    let mut throttler = SlackRateThrottler::new(SlackApiRateControlConfig::new());
+    let team_id: SlackTeamId = "T1".into();
+    let method_rate_ctl = SlackApiMethodRateControlConfig::new();
+
+    assert_eq!(
+        throttler.calc_throttle_delay(&method_rate_ctl, Some(team_id.clone()), None),
+        None
+    );
+    assert!(throttler.backpressure_until(&team_id).is_none());
+
+    throttler.signal_backpressure(team_id.clone());
+    assert!(throttler.backpressure_until(&team_id).is_some());
+    throttler.calc_throttle_delay(&method_rate_ctl, Some(team_id.clone()), None);
+    assert!(throttler
+        .calc_throttle_delay(&method_rate_ctl, Some(team_id), None)
+        .is_some_and(|delay| !delay.is_zero()));
 }
@@ -62,6 +62,7 @@ impl ThrottlingCounter {
             let delay_penalty = (self.rate_limit_in_millis as f64 * self.capacity.abs() as f64
                 / self.max_capacity as f64) as u64;
 
+            #[allow(clippy::implicit_saturating_sub)]
             let delay_in_millis = if base_delay_in_millis < self.rate_limit_in_millis {
                 self.rate_limit_in_millis - base_delay_in_millis
             } else {
@@ -0,0 +1,261 @@
+//!
+//! Reactions aggregation per emoji and per user for a message
+//! or channel messages in a date range (e.g. for voting and poll bots).
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SlackEmojiReactions {
+    /// Reactions count reported by Slack (may be more than users if the list is truncated)
+    pub count: usize,
+    pub users: HashSet<SlackUserId>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SlackReactionsSummary {
+    /// Messages included in the summary
+    pub messages: usize,
+    pub by_emoji: HashMap<SlackReactionName, SlackEmojiReactions>,
+    pub by_user: HashMap<SlackUserId, HashSet<SlackReactionName>>,
+}
+
+impl SlackReactionsSummary {
+    pub fn from_reactions(reactions: &[SlackReaction]) -> Self {
+        let mut summary = Self::default();
+        summary.add_message_reactions(reactions);
+        summary
+    }
+
+    pub fn add_message_reactions(&mut self, reactions: &[SlackReaction]) {
+        self.messages += 1;
+        for reaction in reactions {
+            let emoji_reactions = self.by_emoji.entry(reaction.name.clone()).or_default();
+            emoji_reactions.count += reaction.count;
+            for user in reaction.users.iter() {
+                emoji_reactions.users.insert(user.clone());
+                self.by_user
+                    .entry(user.clone())
+                    .or_default()
+                    .insert(reaction.name.clone());
+            }
+        }
+    }
+
+    pub fn count(&self, emoji: &SlackReactionName) -> usize {
+        self.by_emoji
+            .get(emoji)
+            .map(|emoji_reactions| emoji_reactions.count)
+            .unwrap_or(0)
+    }
+
+    /// Emojis sorted by reactions count (descending) and name
+    pub fn ranking(&self) -> Vec<(&SlackReactionName, usize)> {
+        let mut ranking: Vec<(&SlackReactionName, usize)> = self
+            .by_emoji
+            .iter()
+            .map(|(emoji, emoji_reactions)| (emoji, emoji_reactions.count))
+            .collect();
+        ranking.sort_by(|(emoji_a, count_a), (emoji_b, count_b)| {
+            count_b
+                .cmp(count_a)
+                .then_with(|| emoji_a.value().cmp(emoji_b.value()))
+        });
+        ranking
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackChannelReactionsSummaryRequest {
+    pub channel: SlackChannelId,
+    pub oldest: Option<SlackDateTime>,
+    pub latest: Option<SlackDateTime>,
+    #[default = "SlackChannelReactionsSummaryRequest::DEFAULT_PAGE_LIMIT"]
+    pub page_limit: u16,
+}
+
+impl SlackChannelReactionsSummaryRequest {
+    pub const DEFAULT_PAGE_LIMIT: u16 = 200;
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Reactions summary for a message with complete users lists (using `reactions.get`)
+    pub async fn message_reactions_summary(
+        &self,
+        channel: &SlackChannelId,
+        ts: &SlackTs,
+    ) -> ClientResult<SlackReactionsSummary> {
+        let reactions = self.message_full_reactions(channel, ts).await?;
+        Ok(SlackReactionsSummary::from_reactions(&reactions))
+    }
+
+    /// Reactions summary for channel messages in a date range.
+    /// Reactions from history are used unless users lists are truncated by Slack,
+    /// in that case complete lists are loaded with `reactions.get`.
+    pub async fn channel_reactions_summary(
+        &self,
+        req: &SlackChannelReactionsSummaryRequest,
+    ) -> ClientResult<SlackReactionsSummary> {
+        let mut summary = SlackReactionsSummary::default();
+        let mut cursor: Option<SlackCursorId> = None;
+
+        loop {
+            let history = self
+                .conversations_history(
+                    &SlackApiConversationsHistoryRequest::new()
+                        .with_channel(req.channel.clone())
+                        .with_limit(req.page_limit)
+                        .opt_oldest(req.oldest.as_ref().map(|dt| SlackTs::from_date_time(&dt.0)))
+                        .opt_latest(req.latest.as_ref().map(|dt| SlackTs::from_date_time(&dt.0)))
+                        .opt_cursor(cursor),
+                )
+                .await?;
+
+            for message in history.messages {
+                match message.content.reactions.as_ref() {
+                    Some(reactions)
+                        if reactions
+                            .iter()
+                            .any(|reaction| reaction.users.len() < reaction.count) =>
+                    {
+                        let full_reactions = self
+                            .message_full_reactions(&req.channel, &message.origin.ts)
+                            .await?;
+                        summary.add_message_reactions(&full_reactions);
+                    }
+                    Some(reactions) => summary.add_message_reactions(reactions),
+                    None => summary.add_message_reactions(&[]),
+                }
+            }
+
+            cursor = history
+                .response_metadata
+                .and_then(|rm| rm.next_cursor)
+                .filter(|next_cursor| !next_cursor.value().is_empty());
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn message_full_reactions(
+        &self,
+        channel: &SlackChannelId,
+        ts: &SlackTs,
+    ) -> ClientResult<Vec<SlackReaction>> {
+        match self
+            .reactions_get(
+                &SlackApiReactionsGetRequest::new()
+                    .with_channel(channel.clone())
+                    .with_timestamp(ts.clone())
+                    .with_full(true),
+            )
+            .await?
+        {
+            SlackApiReactionsGetResponse::Message(message_resp) => {
+                Ok(message_resp.message.content.reactions.unwrap_or_default())
+            }
+            SlackApiReactionsGetResponse::File(_) => Err(SlackClientError::SystemError(
+                SlackClientSystemError::new().with_message(format!(
+                    "Unexpected file reactions for message {channel}/{ts}"
+                )),
+            )),
+        }
+    }
+}
+
+#[test]
+fn check_reactions_summary() {
+    let mut summary = SlackReactionsSummary::from_reactions(&[
+        SlackReaction::new("thumbsup".into(), 2, vec!["U1".into(), "U2".into()]),
+        SlackReaction::new("tada".into(), 1, vec!["U1".into()]),
+    ]);
+    summary.add_message_reactions(&[SlackReaction::new(
+        "tada".into(),
+        2,
+        vec!["U2".into(), "U3".into()],
+    )]);
+
+    assert_eq!(summary.messages, 2);
+    assert_eq!(summary.count(&"tada".into()), 3);
+    assert_eq!(
+        summary.ranking(),
+        vec![(&"tada".into(), 3), (&"thumbsup".into(), 2)]
+    );
+    assert_eq!(
+        summary.by_user.get(&"U1".into()).map(|emojis| emojis.len()),
+        Some(2)
+    );
+}
+
+#[test]
+fn check_channel_reactions_summary_truncated_users() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|call: &SlackTestCall| match call.method.as_str() {
+        "reactions.get" => Ok(serde_json::json!({
+            "type": "message",
+            "message": {
+                "ts": "1.2",
+                "reactions": [
+                    { "name": "thumbsup", "count": 3, "users": ["U1", "U2", "U3"] }
+                ]
+            }
+        })),
+        _ => Ok(serde_json::json!({
+            "messages": [
+                {
+                    "ts": "1.1",
+                    "reactions": [{ "name": "tada", "count": 1, "users": ["U1"] }]
+                },
+                {
+                    "ts": "1.2",
+                    "reactions": [{ "name": "thumbsup", "count": 3, "users": ["U1"] }]
+                }
+            ],
+            "has_more": false
+        })),
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let summary = futures::executor::block_on(
+        session.channel_reactions_summary(&SlackChannelReactionsSummaryRequest::new("C1".into())),
+    )
+    .unwrap();
+
+    let full_reactions = connector.calls_of("reactions.get");
+    assert_eq!(full_reactions.len(), 1);
+    assert_eq!(full_reactions[0].param("timestamp").as_deref(), Some("1.2"));
+    assert_eq!(full_reactions[0].param("full").as_deref(), Some("true"));
+
+    assert_eq!(summary.messages, 2);
+    assert_eq!(summary.count(&"thumbsup".into()), 3);
+    assert_eq!(
+        summary
+            .by_emoji
+            .get(&"thumbsup".into())
+            .map(|emoji| emoji.users.len()),
+        Some(3)
+    );
+    assert_eq!(
+        summary.by_user.get(&"U3".into()).map(|emojis| emojis.len()),
+        Some(1)
+    );
+    assert_eq!(
+        summary.by_user.get(&"U1".into()).map(|emojis| emojis.len()),
+        Some(2)
+    );
+}
@@ -0,0 +1,369 @@
+//!
+//! Helpers to resolve user provided inputs (such as emails or channel names) to Slack IDs
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use futures::stream::StreamExt;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SlackChannelNameMatchMode {
+    /// Channel names must be equal (ignoring `#` prefix and case)
+    Exact,
+    /// Also ignores separators (`-`, `_`, spaces) and allows partial names,
+    /// preferring the shortest matching channel name.
+    Fuzzy,
+}
+
+/// Resolves `#channel-name` inputs to channels using a periodically refreshed index
+/// built from `conversations.list`.
+#[derive(Debug)]
+pub struct SlackChannelNameResolver {
+    types: Vec<SlackConversationType>,
+    index_ttl: Duration,
+    index: Mutex<Option<SlackChannelNamesIndex>>,
+}
+
+#[derive(Debug, Clone)]
+struct SlackChannelNamesIndex {
+    channels: Arc<Vec<SlackChannelInfo>>,
+    created: Instant,
+}
+
+impl SlackChannelNameResolver {
+    pub const DEFAULT_INDEX_TTL: Duration = Duration::from_secs(600);
+    const LIST_PAGE_LIMIT: u16 = 1000;
+
+    pub fn new() -> Self {
+        Self {
+            types: vec![SlackConversationType::Public],
+            index_ttl: Self::DEFAULT_INDEX_TTL,
+            index: Mutex::new(None),
+        }
+    }
+
+    pub fn with_types(self, types: Vec<SlackConversationType>) -> Self {
+        Self { types, ..self }
+    }
+
+    pub fn with_index_ttl(self, index_ttl: Duration) -> Self {
+        Self { index_ttl, ..self }
+    }
+
+    pub fn invalidate(&self) {
+        *self.index.lock().unwrap() = None;
+    }
+
+    pub async fn resolve<'a, SCHC>(
+        &self,
+        session: &SlackClientSession<'a, SCHC>,
+        input: &str,
+        mode: SlackChannelNameMatchMode,
+    ) -> ClientResult<Option<SlackChannelInfo>>
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        let channels = self.channels_index(session).await?;
+        Ok(Self::find_channel(&channels, input, mode).cloned())
+    }
+
+    pub async fn resolve_id<'a, SCHC>(
+        &self,
+        session: &SlackClientSession<'a, SCHC>,
+        input: &str,
+        mode: SlackChannelNameMatchMode,
+    ) -> ClientResult<Option<SlackChannelId>>
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        Ok(self
+            .resolve(session, input, mode)
+            .await?
+            .map(|channel| channel.id))
+    }
+
+    async fn channels_index<'a, SCHC>(
+        &self,
+        session: &SlackClientSession<'a, SCHC>,
+    ) -> ClientResult<Arc<Vec<SlackChannelInfo>>>
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        let cached_index = self
+            .index
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|index| index.created.elapsed() < self.index_ttl)
+            .map(|index| index.channels.clone());
+
+        match cached_index {
+            Some(channels) => Ok(channels),
+            None => {
+                let channels = Arc::new(self.list_channels(session).await?);
+                debug!(
+                    "Slack channel names index loaded: {} channels",
+                    channels.len()
+                );
+                *self.index.lock().unwrap() = Some(SlackChannelNamesIndex {
+                    channels: channels.clone(),
+                    created: Instant::now(),
+                });
+                Ok(channels)
+            }
+        }
+    }
+
+    async fn list_channels<'a, SCHC>(
+        &self,
+        session: &SlackClientSession<'a, SCHC>,
+    ) -> ClientResult<Vec<SlackChannelInfo>>
+    where
+        SCHC: SlackClientHttpConnector + Send + Sync,
+    {
+        let mut channels = Vec::new();
+        let mut cursor: Option<SlackCursorId> = None;
+        loop {
+            let resp = session
+                .conversations_list(
+                    &SlackApiConversationsListRequest::new()
+                        .with_limit(Self::LIST_PAGE_LIMIT)
+                        .with_exclude_archived(true)
+                        .with_types(self.types.clone())
+                        .opt_cursor(cursor),
+                )
+                .await?;
+            channels.extend(resp.channels);
+            cursor = resp
+                .response_metadata
+                .and_then(|rm| rm.next_cursor)
+                .filter(|next_cursor| !next_cursor.value().is_empty());
+            if cursor.is_none() {
+                return Ok(channels);
+            }
+        }
+    }
+
+    fn find_channel<'c>(
+        channels: &'c [SlackChannelInfo],
+        input: &str,
+        mode: SlackChannelNameMatchMode,
+    ) -> Option<&'c SlackChannelInfo> {
+        let input = Self::parse_input(input);
+
+        if let Some(channel) = channels.iter().find(|channel| channel.id.value() == input) {
+            return Some(channel);
+        }
+
+        let channel_name = |channel: &'c SlackChannelInfo| -> Option<&'c str> {
+            channel
+                .name_normalized
+                .as_deref()
+                .or(channel.name.as_deref())
+        };
+
+        let exact_name = input.to_lowercase();
+        let exact_match = channels.iter().find(|channel| {
+            channel_name(channel).map(|name| name.to_lowercase()) == Some(exact_name.clone())
+        });
+
+        match mode {
+            SlackChannelNameMatchMode::Exact => exact_match,
+            SlackChannelNameMatchMode::Fuzzy => exact_match.or_else(|| {
+                let fuzzy_name = Self::fuzzy_normalize(input);
+                if fuzzy_name.is_empty() {
+                    return None;
+                }
+                channels
+                    .iter()
+                    .filter_map(|channel| {
+                        channel_name(channel).and_then(|name| {
+                            let normalized = Self::fuzzy_normalize(name);
+                            if normalized == fuzzy_name {
+                                Some((0, name.len(), channel))
+                            } else if normalized.starts_with(&fuzzy_name) {
+                                Some((1, name.len(), channel))
+                            } else if normalized.contains(&fuzzy_name) {
+                                Some((2, name.len(), channel))
+                            } else {
+                                None
+                            }
+                        })
+                    })
+                    .min_by_key(|(rank, len, _)| (*rank, *len))
+                    .map(|(_, _, channel)| channel)
+            }),
+        }
+    }
+
+    /// Accepts `#name`, `name`, channel IDs and channel mentions (`<#C12345|name>`)
+    fn parse_input(input: &str) -> &str {
+        let input = input.trim();
+        match input
+            .strip_prefix("<#")
+            .and_then(|mention| mention.strip_suffix('>'))
+        {
+            Some(mention) => mention.split('|').next().unwrap_or(mention),
+            None => input.trim_start_matches('#'),
+        }
+    }
+
+    fn fuzzy_normalize(name: &str) -> String {
+        name.chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' ' | '.'))
+            .flat_map(|c| c.to_lowercase())
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SlackUserGroupMentions {
+    /// Members of each mentioned user group
+    pub usergroups: HashMap<SlackUserGroupId, Vec<SlackUserId>>,
+    /// Unique members of all mentioned user groups in the order of mentions
+    pub users: Vec<SlackUserId>,
+}
+
+impl SlackUserGroupMentions {
+    const MENTION_PREFIX: &'static str = "<!subteam^";
+
+    /// User group IDs mentioned in message text as `<!subteam^S...>` or `<!subteam^S...|@handle>`
+    pub fn parse_ids(text: &str) -> Vec<SlackUserGroupId> {
+        let mut ids: Vec<SlackUserGroupId> = Vec::new();
+        let mut rest = text;
+        while let Some(start) = rest.find(Self::MENTION_PREFIX) {
+            rest = &rest[start + Self::MENTION_PREFIX.len()..];
+            if let Some(end) = rest.find('>') {
+                let id = rest[..end].split('|').next().unwrap_or_default();
+                if !id.is_empty() && !ids.iter().any(|existing| existing.value() == id) {
+                    ids.push(id.into());
+                }
+                rest = &rest[end..];
+            }
+        }
+        ids
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Resolves user group mentions in message text to current members using `usergroups.users.list`.
+    /// Members are cached if the client is configured with `SlackClient::with_api_cache`.
+    pub async fn expand_usergroup_mentions(
+        &self,
+        text: &str,
+    ) -> ClientResult<SlackUserGroupMentions> {
+        let mut mentions = SlackUserGroupMentions::default();
+        for usergroup in SlackUserGroupMentions::parse_ids(text) {
+            let members = self
+                .cached()
+                .usergroups_users_list(&SlackApiUserGroupsUsersListRequest::new(usergroup.clone()))
+                .await?
+                .users;
+            for user in members.iter() {
+                if !mentions.users.contains(user) {
+                    mentions.users.push(user.clone());
+                }
+            }
+            mentions.usergroups.insert(usergroup, members);
+        }
+        Ok(mentions)
+    }
+
+    /// User locale (loaded with `include_locale`) to localize responses to the user.
+    /// Users are cached if the client is configured with `SlackClient::with_api_cache`.
+    pub async fn user_locale(&self, user_id: &SlackUserId) -> ClientResult<Option<SlackLocale>> {
+        Ok(self
+            .cached()
+            .users_info(&SlackApiUsersInfoRequest::new(user_id.clone()).with_include_locale(true))
+            .await?
+            .user
+            .locale)
+    }
+
+    /// Channel locale (loaded with `include_locale`) to localize messages posted to the channel
+    pub async fn channel_locale(
+        &self,
+        channel_id: &SlackChannelId,
+    ) -> ClientResult<Option<SlackLocale>> {
+        Ok(self
+            .cached()
+            .conversations_info(
+                &SlackApiConversationsInfoRequest::new(channel_id.clone())
+                    .with_include_locale(true),
+            )
+            .await?
+            .channel
+            .locale)
+    }
+}
+
+#[test]
+fn check_channel_name_matching() {
+    let channel = |id: &str, name: &str| {
+        SlackChannelInfo::new(
+            id.into(),
+            SlackDateTime(chrono::DateTime::from_timestamp(0, 0).unwrap()),
+            SlackChannelFlags::new(),
+            SlackChannelCurrentState::new(),
+        )
+        .with_name(name.into())
+    };
+    let channels = vec![
+        channel("C1", "general"),
+        channel("C2", "dev-team-backend"),
+        channel("C3", "dev-team"),
+    ];
+
+    let find = |input: &str, mode| {
+        SlackChannelNameResolver::find_channel(&channels, input, mode).map(|c| c.id.value().clone())
+    };
+
+    assert_eq!(
+        find("#general", SlackChannelNameMatchMode::Exact),
+        Some("C1".into())
+    );
+    assert_eq!(
+        find("<#C2|dev-team-backend>", SlackChannelNameMatchMode::Exact),
+        Some("C2".into())
+    );
+    assert_eq!(find("#dev_team", SlackChannelNameMatchMode::Exact), None);
+    assert_eq!(
+        find("#dev_team", SlackChannelNameMatchMode::Fuzzy),
+        Some("C3".into())
+    );
+    assert_eq!(
+        find("#Backend", SlackChannelNameMatchMode::Fuzzy),
+        Some("C2".into())
+    );
+    assert_eq!(find("#random", SlackChannelNameMatchMode::Fuzzy), None);
+}
+
+#[test]
+fn check_usergroup_mentions_parsing() {
+    assert_eq!(
+        SlackUserGroupMentions::parse_ids(
+            "Hey <!subteam^S123|@devs> and <!subteam^S456>, also <!subteam^S123|@devs> <@U1>"
+        ),
+        vec!["S123".into(), "S456".into()]
+    );
+    assert!(SlackUserGroupMentions::parse_ids("<!here> <!subteam^").is_empty());
+}
+
+#[test]
+fn check_locale_parts() {
+    let locale: SlackLocale = "en-US".into();
+    assert_eq!(locale.language(), "en");
+    assert_eq!(locale.region(), Some("US"));
+    assert_eq!(SlackLocale::from("ja").region(), None);
+}
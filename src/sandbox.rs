@@ -0,0 +1,288 @@
+//!
+//! Sandbox routing for staging deployments: posting calls are redirected
+//! to a designated test channel (and optionally a test workspace token),
+//! calls that would change the channel itself are dropped, and only read calls go through unchanged.
+//! Other write calls are sent with the test workspace token, or dropped if there is none,
+//! so no unlisted method reaches the production workspace.
+//!
+
+use crate::errors::*;
+use crate::models::*;
+use crate::token::*;
+use crate::*;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Builder)]
+pub struct SlackSandboxConfig {
+    /// Channel to post to instead of the requested channels
+    pub channel: SlackChannelId,
+    /// Token of a test workspace to use for routed calls
+    pub token: Option<SlackApiToken>,
+    /// Read-only methods sent unchanged. A pattern may start with `*.` to match a method suffix
+    /// or end with `.*` to match a method prefix.
+    #[default = "SlackSandboxConfig::default_read_methods()"]
+    pub read_methods: Vec<String>,
+    /// Methods routed to the sandbox channel even without a test workspace token
+    #[default = "SlackSandboxConfig::default_routed_methods()"]
+    pub routed_methods: Vec<String>,
+    /// Methods not sent at all (logged instead), because they would archive, rename or
+    /// remove members of the sandbox channel itself
+    #[default = "SlackSandboxConfig::default_dropped_methods()"]
+    pub dropped_methods: Vec<String>,
+}
+
+impl SlackSandboxConfig {
+    pub const DEFAULT_READ_METHODS: &'static [&'static str] = &[
+        "*.export",
+        "*.get",
+        "*.history",
+        "*.info",
+        "*.list",
+        "*.lookup",
+        "*.members",
+        "*.replies",
+        "*.validate",
+        "identity.*",
+        "openid.connect.*",
+        "admin.analytics.getFile",
+        "admin.conversations.search",
+        "api.test",
+        "apps.connections.open",
+        "auth.test",
+        "chat.getPermalink",
+        "search.messages",
+        "users.conversations",
+        "users.getPresence",
+        "users.identity",
+        "users.lookupByEmail",
+    ];
+
+    pub const DEFAULT_ROUTED_METHODS: &'static [&'static str] = &[
+        "chat.delete",
+        "chat.deleteScheduledMessage",
+        "chat.postEphemeral",
+        "chat.postMessage",
+        "chat.scheduleMessage",
+        "chat.update",
+        "conversations.invite",
+        "conversations.setPurpose",
+        "conversations.setTopic",
+        "files.completeUploadExternal",
+        "files.getUploadURLExternal",
+        "files.upload",
+        "pins.add",
+        "pins.remove",
+        "reactions.add",
+        "reactions.remove",
+    ];
+
+    pub const DEFAULT_DROPPED_METHODS: &'static [&'static str] = &[
+        "conversations.archive",
+        "conversations.kick",
+        "conversations.rename",
+    ];
+
+    // Request fields with channels in routed methods
+    const CHANNEL_FIELDS: &'static [&'static str] = &["channel", "channel_id", "channels"];
+
+    pub fn default_read_methods() -> Vec<String> {
+        Self::DEFAULT_READ_METHODS
+            .iter()
+            .map(|method| method.to_string())
+            .collect()
+    }
+
+    pub fn default_routed_methods() -> Vec<String> {
+        Self::DEFAULT_ROUTED_METHODS
+            .iter()
+            .map(|method| method.to_string())
+            .collect()
+    }
+
+    pub fn default_dropped_methods() -> Vec<String> {
+        Self::DEFAULT_DROPPED_METHODS
+            .iter()
+            .map(|method| method.to_string())
+            .collect()
+    }
+
+    pub fn is_read_only(&self, method: &str) -> bool {
+        self.read_methods.iter().any(|pattern| {
+            if let Some(suffix) = pattern.strip_prefix('*') {
+                method.ends_with(suffix)
+            } else if let Some(prefix) = pattern.strip_suffix('*') {
+                method.starts_with(prefix)
+            } else {
+                pattern == method
+            }
+        })
+    }
+
+    pub fn is_routed(&self, method: &str) -> bool {
+        !self.is_read_only(method) && !self.is_dropped(method)
+    }
+
+    pub fn is_dropped(&self, method: &str) -> bool {
+        if self.is_read_only(method) {
+            false
+        } else if self.dropped_methods.iter().any(|dropped| dropped == method) {
+            true
+        } else {
+            // Without a test workspace only methods with a channel to replace can be routed
+            self.token.is_none() && !self.routed_methods.iter().any(|routed| routed == method)
+        }
+    }
+
+    /// Replaces channels in a routed request body
+    pub fn route_request(&self, mut request: Value) -> Value {
+        if let Some(fields) = request.as_object_mut() {
+            for field in Self::CHANNEL_FIELDS {
+                if let Some(channel) = fields.get_mut(*field) {
+                    *channel = Value::String(self.channel.value().clone());
+                }
+            }
+        }
+        request
+    }
+
+    /// Replaces channels in routed request parameters (for query and form requests)
+    pub fn route_params<'p, PT, TS>(&self, params: &'p PT) -> Vec<(&'p str, Option<String>)>
+    where
+        PT: std::iter::IntoIterator<Item = (&'p str, Option<TS>)> + Clone,
+        TS: AsRef<str> + 'p,
+    {
+        params
+            .clone()
+            .into_iter()
+            .map(|(name, value)| match value {
+                Some(_) if Self::CHANNEL_FIELDS.contains(&name) => {
+                    (name, Some(self.channel.value().clone()))
+                }
+                value => (name, value.map(|value| value.as_ref().to_string())),
+            })
+            .collect()
+    }
+
+    /// Empty response for a dropped call (or an error if the method response can't be empty)
+    pub(crate) fn dropped_response<RS>(&self, method: &str) -> ClientResult<RS>
+    where
+        RS: for<'de> serde::de::Deserialize<'de>,
+    {
+        warn!(
+            "Dropping {} in the sandbox channel {}",
+            method, self.channel
+        );
+        serde_json::from_value(serde_json::json!({})).map_err(|_| {
+            SlackClientError::SystemError(SlackClientSystemError::new().with_message(format!(
+                "{method} isn't sent in the sandbox channel {}",
+                self.channel
+            )))
+        })
+    }
+}
+
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Redirect all calls except read-only methods of all sessions to a sandbox (e.g. for staging deployments)
+    pub fn with_sandbox(self, sandbox: SlackSandboxConfig) -> Self {
+        Self {
+            sandbox: Some(Arc::new(sandbox)),
+            ..self
+        }
+    }
+}
+
+#[test]
+fn check_sandbox_routing() {
+    let sandbox = SlackSandboxConfig::new("CSANDBOX".into());
+    assert!(sandbox.is_routed("chat.postMessage"));
+    assert!(!sandbox.is_routed("conversations.history"));
+    assert!(sandbox.is_dropped("conversations.archive"));
+    assert!(!sandbox.is_routed("conversations.archive"));
+    assert!(sandbox.is_read_only("users.profile.get"));
+    assert!(sandbox.is_dropped("usergroups.users.update"));
+    assert!(sandbox
+        .clone()
+        .with_token(SlackApiToken::new("xoxb-sandbox".into()))
+        .is_routed("usergroups.users.update"));
+    assert_eq!(
+        sandbox.route_request(serde_json::json!({ "channel": "CPROD", "text": "Hey" })),
+        serde_json::json!({ "channel": "CSANDBOX", "text": "Hey" })
+    );
+}
+
+#[test]
+fn check_sandbox_uploads_and_dropped_calls() {
+    use crate::api::*;
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|_| Ok(serde_json::json!({ "file": { "id": "F1" } })));
+    let client = SlackClient::new(connector.clone())
+        .with_sandbox(SlackSandboxConfig::new("CSANDBOX".into()));
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    futures::executor::block_on(async {
+        #[allow(deprecated)]
+        session
+            .files_upload(
+                &SlackApiFilesUploadRequest::new()
+                    .with_channels(vec!["CPROD".into()])
+                    .with_content("test".into()),
+            )
+            .await
+            .unwrap();
+        session
+            .conversations_archive(&SlackApiConversationsArchiveRequest::new("CPROD".into()))
+            .await
+            .unwrap();
+        assert!(session
+            .conversations_rename(&SlackApiConversationsRenameRequest::new(
+                "CPROD".into(),
+                "renamed".into()
+            ))
+            .await
+            .is_err());
+    });
+
+    let calls = connector.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].method, "files.upload");
+    assert_eq!(calls[0].param("channels"), Some("CSANDBOX".into()));
+}
+
+#[test]
+fn check_sandbox_unlisted_write_methods() {
+    use crate::api::*;
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|_| Ok(serde_json::json!({})));
+    let client = SlackClient::new(connector.clone())
+        .with_sandbox(SlackSandboxConfig::new("CSANDBOX".into()));
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    futures::executor::block_on(async {
+        assert!(session
+            .usergroups_users_update(&SlackApiUserGroupsUsersUpdateRequest::new(
+                "S1".into(),
+                vec!["U1".into()]
+            ))
+            .await
+            .is_err());
+        session
+            .users_info(&SlackApiUsersInfoRequest::new("U1".into()))
+            .await
+            .ok();
+    });
+
+    let calls = connector.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].method, "users.info");
+}
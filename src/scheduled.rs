@@ -0,0 +1,287 @@
+//!
+//! Scheduled messages manager: lists, cancels and reschedules scheduled messages
+//! using `chat.scheduleMessage`, `chat.scheduledMessages.list` and `chat.deleteScheduledMessage`.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use tracing::*;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackScheduledMessagesFilter {
+    pub channel: Option<SlackChannelId>,
+    /// Only messages scheduled to be posted after this time
+    pub post_after: Option<SlackDateTime>,
+    /// Only messages scheduled to be posted before this time
+    pub post_before: Option<SlackDateTime>,
+}
+
+impl SlackScheduledMessagesFilter {
+    const LIST_PAGE_LIMIT: u16 = 100;
+
+    pub fn for_channel(channel: SlackChannelId) -> Self {
+        Self::new().with_channel(channel)
+    }
+}
+
+#[derive(Debug)]
+pub struct SlackScheduledMessagesCancelReport {
+    pub cancelled: Vec<SlackApiChatScheduledMessageInfo>,
+    pub failed: Vec<(SlackApiChatScheduledMessageInfo, SlackClientError)>,
+}
+
+pub struct SlackScheduledMessagesManager<'s, 'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    session: &'s SlackClientSession<'a, SCHC>,
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    pub fn scheduled_messages(&self) -> SlackScheduledMessagesManager<'_, 'a, SCHC> {
+        SlackScheduledMessagesManager { session: self }
+    }
+}
+
+impl<'s, 'a, SCHC> SlackScheduledMessagesManager<'s, 'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    pub async fn schedule(
+        &self,
+        req: &SlackApiChatScheduleMessageRequest,
+    ) -> ClientResult<SlackApiChatScheduleMessageResponse> {
+        self.session.chat_schedule_message(req).await
+    }
+
+    /// Lists all pending scheduled messages matching the filter (walking through all pages)
+    pub async fn list(
+        &self,
+        filter: &SlackScheduledMessagesFilter,
+    ) -> ClientResult<Vec<SlackApiChatScheduledMessageInfo>> {
+        let mut messages = Vec::new();
+        let mut cursor: Option<SlackCursorId> = None;
+        loop {
+            let resp = self
+                .session
+                .chat_scheduled_messages_list(
+                    &SlackApiChatScheduledMessagesListRequest::new()
+                        .with_limit(SlackScheduledMessagesFilter::LIST_PAGE_LIMIT)
+                        .opt_channel(filter.channel.clone())
+                        .opt_oldest(
+                            filter
+                                .post_after
+                                .as_ref()
+                                .map(|dt| SlackTs::from_date_time(&dt.0)),
+                        )
+                        .opt_latest(
+                            filter
+                                .post_before
+                                .as_ref()
+                                .map(|dt| SlackTs::from_date_time(&dt.0)),
+                        )
+                        .opt_cursor(cursor),
+                )
+                .await?;
+            messages.extend(resp.scheduled_messages);
+            cursor = resp
+                .response_metadata
+                .and_then(|rm| rm.next_cursor)
+                .filter(|next_cursor| !next_cursor.value().is_empty());
+            if cursor.is_none() {
+                return Ok(messages);
+            }
+        }
+    }
+
+    pub async fn cancel(&self, message: &SlackApiChatScheduledMessageInfo) -> ClientResult<()> {
+        self.session
+            .chat_delete_scheduled_message(&SlackApiChatDeleteScheduledMessageRequest::new(
+                message.channel_id.clone(),
+                message.id.clone(),
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Cancels all scheduled messages matching the filter and the predicate,
+    /// e.g. `manager.cancel_where(&SlackScheduledMessagesFilter::for_channel(channel), |_| true)`.
+    pub async fn cancel_where<P>(
+        &self,
+        filter: &SlackScheduledMessagesFilter,
+        predicate: P,
+    ) -> ClientResult<SlackScheduledMessagesCancelReport>
+    where
+        P: Fn(&SlackApiChatScheduledMessageInfo) -> bool,
+    {
+        let mut report = SlackScheduledMessagesCancelReport {
+            cancelled: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        for message in self.list(filter).await? {
+            if !predicate(&message) {
+                continue;
+            }
+            match self.cancel(&message).await {
+                Ok(()) => report.cancelled.push(message),
+                Err(err) => {
+                    warn!("Unable to cancel scheduled message {}: {}", message.id, err);
+                    report.failed.push((message, err));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Moves a scheduled message to another time.
+    /// Slack doesn't provide an API to update scheduled messages, so the message is scheduled again
+    /// and only then the previous one is cancelled.
+    /// Slack returns only the text of scheduled messages, so provide `content`
+    /// if the message has blocks or attachments.
+    pub async fn reschedule(
+        &self,
+        message: &SlackApiChatScheduledMessageInfo,
+        post_at: SlackDateTime,
+        content: Option<SlackMessageContent>,
+    ) -> ClientResult<SlackApiChatScheduleMessageResponse> {
+        let content = match content {
+            Some(content) => content,
+            None => {
+                SlackMessageContent::new().with_text(message.text.clone().ok_or_else(|| {
+                    SlackClientError::SystemError(SlackClientSystemError::new().with_message(
+                        format!(
+                            "Scheduled message {} has no text to reschedule, provide its content",
+                            message.id
+                        ),
+                    ))
+                })?)
+            }
+        };
+
+        let resp = self
+            .schedule(&SlackApiChatScheduleMessageRequest::new(
+                message.channel_id.clone(),
+                content,
+                post_at,
+            ))
+            .await?;
+        self.cancel(message).await?;
+        Ok(resp)
+    }
+}
+
+#[test]
+fn check_scheduled_messages_cancel_and_reschedule() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|call: &SlackTestCall| match call.method.as_str() {
+        "chat.scheduledMessages.list" => {
+            let (message, next_cursor) = match call.param("cursor").as_deref() {
+                None => (("Q1", "reminder"), "page2"),
+                _ => (("Q2", "keep"), ""),
+            };
+            Ok(serde_json::json!({
+                "ok": true,
+                "scheduled_messages": [{
+                    "id": message.0,
+                    "channel_id": "C1",
+                    "post_at": 2000000000,
+                    "date_created": 1700000000,
+                    "text": message.1
+                }],
+                "response_metadata": { "next_cursor": next_cursor }
+            }))
+        }
+        "chat.deleteScheduledMessage"
+            if call.param("scheduled_message").as_deref() == Some("Q3") =>
+        {
+            Err(SlackClientError::ApiError(SlackClientApiError::new(
+                "invalid_scheduled_message_id".into(),
+            )))
+        }
+        "chat.scheduleMessage" => Ok(serde_json::json!({
+            "ok": true,
+            "channel": "C1",
+            "scheduled_message_id": "Q9",
+            "post_at": call.body.as_ref().and_then(|body| body.get("post_at")).cloned()
+        })),
+        _ => Ok(serde_json::json!({ "ok": true })),
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+    let manager = session.scheduled_messages();
+
+    futures::executor::block_on(async {
+        let report = manager
+            .cancel_where(
+                &SlackScheduledMessagesFilter::for_channel("C1".into()),
+                |message| message.text.as_deref() == Some("reminder"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(connector.calls_of("chat.scheduledMessages.list").len(), 2);
+        assert_eq!(
+            report
+                .cancelled
+                .iter()
+                .map(|message| message.id.value().as_str())
+                .collect::<Vec<_>>(),
+            vec!["Q1"]
+        );
+        assert!(report.failed.is_empty());
+
+        let scheduled = manager
+            .list(&SlackScheduledMessagesFilter::new())
+            .await
+            .unwrap();
+        let post_at = SlackDateTime(chrono::DateTime::from_timestamp(2100000000, 0).unwrap());
+        let resp = manager
+            .reschedule(&scheduled[0], post_at.clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(resp.post_at, post_at);
+        let methods: Vec<String> = connector
+            .calls()
+            .into_iter()
+            .map(|call| call.method)
+            .filter(|method| method != "chat.scheduledMessages.list")
+            .collect();
+        // The message is scheduled again before the previous one is cancelled
+        assert_eq!(
+            methods,
+            vec![
+                "chat.deleteScheduledMessage",
+                "chat.scheduleMessage",
+                "chat.deleteScheduledMessage"
+            ]
+        );
+
+        let missing_text = SlackApiChatScheduledMessageInfo {
+            text: None,
+            id: "Q3".into(),
+            ..scheduled[0].clone()
+        };
+        assert!(manager
+            .reschedule(&missing_text, post_at.clone(), None)
+            .await
+            .is_err());
+        assert!(manager
+            .reschedule(
+                &missing_text,
+                post_at,
+                Some(SlackMessageContent::new().with_text("new".into()))
+            )
+            .await
+            .is_err());
+    });
+}
@@ -0,0 +1,411 @@
+//!
+//! Typed Slack OAuth scopes and scopes required by implemented Web API methods
+//!
+
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rvstruct::ValueStruct;
+use std::collections::HashSet;
+
+macro_rules! slack_scopes {
+    ($($variant:ident => $value:literal),* $(,)?) => {
+        #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+        pub enum SlackScope {
+            $($variant),*
+        }
+
+        impl SlackScope {
+            pub const ALL: &'static [SlackScope] = &[$(SlackScope::$variant),*];
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(SlackScope::$variant => $value),*
+                }
+            }
+
+            pub fn from_scope_str(scope: &str) -> Option<Self> {
+                match scope {
+                    $($value => Some(SlackScope::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+slack_scopes! {
+    AdminAnalyticsRead => "admin.analytics:read",
+    AdminConversationsRead => "admin.conversations:read",
+    AdminTeamsRead => "admin.teams:read",
+    AppMentionsRead => "app_mentions:read",
+    AuditLogsRead => "auditlogs:read",
+    BookmarksRead => "bookmarks:read",
+    BookmarksWrite => "bookmarks:write",
+    CallsRead => "calls:read",
+    CallsWrite => "calls:write",
+    CanvasesRead => "canvases:read",
+    CanvasesWrite => "canvases:write",
+    ChannelsHistory => "channels:history",
+    ChannelsJoin => "channels:join",
+    ChannelsManage => "channels:manage",
+    ChannelsRead => "channels:read",
+    ChannelsWriteInvites => "channels:write.invites",
+    ChannelsWriteTopic => "channels:write.topic",
+    ChatWrite => "chat:write",
+    ChatWriteCustomize => "chat:write.customize",
+    ChatWritePublic => "chat:write.public",
+    Commands => "commands",
+    ConnectionsWrite => "connections:write",
+    DndRead => "dnd:read",
+    EmojiRead => "emoji:read",
+    FilesRead => "files:read",
+    FilesWrite => "files:write",
+    GroupsHistory => "groups:history",
+    GroupsRead => "groups:read",
+    GroupsWrite => "groups:write",
+    GroupsWriteInvites => "groups:write.invites",
+    GroupsWriteTopic => "groups:write.topic",
+    IdentityAvatar => "identity.avatar",
+    IdentityBasic => "identity.basic",
+    IdentityEmail => "identity.email",
+    IdentityTeam => "identity.team",
+    ImHistory => "im:history",
+    ImRead => "im:read",
+    ImWrite => "im:write",
+    IncomingWebhook => "incoming-webhook",
+    LinksRead => "links:read",
+    LinksWrite => "links:write",
+    MpimHistory => "mpim:history",
+    MpimRead => "mpim:read",
+    MpimWrite => "mpim:write",
+    OpenId => "openid",
+    OpenIdEmail => "email",
+    OpenIdProfile => "profile",
+    PinsRead => "pins:read",
+    PinsWrite => "pins:write",
+    ReactionsRead => "reactions:read",
+    ReactionsWrite => "reactions:write",
+    RemindersRead => "reminders:read",
+    RemindersWrite => "reminders:write",
+    SearchRead => "search:read",
+    StarsRead => "stars:read",
+    StarsWrite => "stars:write",
+    TeamRead => "team:read",
+    TriggersRead => "triggers:read",
+    TriggersWrite => "triggers:write",
+    UsergroupsRead => "usergroups:read",
+    UsergroupsWrite => "usergroups:write",
+    UsersProfileRead => "users.profile:read",
+    UsersProfileWrite => "users.profile:write",
+    UsersRead => "users:read",
+    UsersReadEmail => "users:read.email",
+    UsersWrite => "users:write",
+    WorkflowStepsExecute => "workflow.steps:execute",
+}
+
+impl std::fmt::Display for SlackScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<SlackScope> for SlackApiTokenScope {
+    fn from(scope: SlackScope) -> Self {
+        SlackApiTokenScope(scope.as_str().into())
+    }
+}
+
+const CONVERSATIONS_HISTORY_SCOPES: &[SlackScope] = &[
+    SlackScope::ChannelsHistory,
+    SlackScope::GroupsHistory,
+    SlackScope::ImHistory,
+    SlackScope::MpimHistory,
+];
+
+const CONVERSATIONS_READ_SCOPES: &[SlackScope] = &[
+    SlackScope::ChannelsRead,
+    SlackScope::GroupsRead,
+    SlackScope::ImRead,
+    SlackScope::MpimRead,
+];
+
+const CONVERSATIONS_MANAGE_SCOPES: &[SlackScope] = &[
+    SlackScope::ChannelsManage,
+    SlackScope::GroupsWrite,
+    SlackScope::ImWrite,
+    SlackScope::MpimWrite,
+];
+
+const CONVERSATIONS_INVITE_SCOPES: &[SlackScope] = &[
+    SlackScope::ChannelsManage,
+    SlackScope::ChannelsWriteInvites,
+    SlackScope::GroupsWrite,
+    SlackScope::GroupsWriteInvites,
+    SlackScope::ImWrite,
+    SlackScope::MpimWrite,
+];
+
+const CONVERSATIONS_TOPIC_SCOPES: &[SlackScope] = &[
+    SlackScope::ChannelsManage,
+    SlackScope::ChannelsWriteTopic,
+    SlackScope::GroupsWrite,
+    SlackScope::GroupsWriteTopic,
+    SlackScope::ImWrite,
+    SlackScope::MpimWrite,
+];
+
+pub struct SlackApiMethodScopes;
+
+impl SlackApiMethodScopes {
+    /// Scopes for methods implemented by this library, where a token needs
+    /// at least one of returned scopes (Slack requires different scopes for different conversation types).
+    /// An empty slice means that no scopes are required, and `None` is returned for unknown methods.
+    pub fn required_scopes(method: &str) -> Option<&'static [SlackScope]> {
+        match method {
+            "api.test" | "auth.test" | "oauth.v2.access" | "openid.connect.token" => Some(&[]),
+            "admin.analytics.getFile" => Some(&[SlackScope::AdminAnalyticsRead]),
+            "admin.conversations.search" => Some(&[SlackScope::AdminConversationsRead]),
+            "admin.teams.list" => Some(&[SlackScope::AdminTeamsRead]),
+            "apps.connections.open" => Some(&[SlackScope::ConnectionsWrite]),
+            // App configuration tokens don't have scopes
+            "apps.manifest.create"
+            | "apps.manifest.delete"
+            | "apps.manifest.export"
+            | "apps.manifest.update"
+            | "apps.manifest.validate" => Some(&[]),
+            "bots.info" => Some(&[SlackScope::UsersRead]),
+            "calls.add"
+            | "calls.end"
+            | "calls.participants.add"
+            | "calls.participants.remove"
+            | "calls.update" => Some(&[SlackScope::CallsWrite]),
+            "calls.info" => Some(&[SlackScope::CallsRead]),
+            "canvases.create" | "canvases.delete" | "canvases.edit" => {
+                Some(&[SlackScope::CanvasesWrite])
+            }
+            "canvases.sections.lookup" => Some(&[SlackScope::CanvasesRead]),
+            "chat.delete"
+            | "chat.deleteScheduledMessage"
+            | "chat.postEphemeral"
+            | "chat.postMessage"
+            | "chat.scheduleMessage"
+            | "chat.update" => Some(&[SlackScope::ChatWrite]),
+            "chat.getPermalink" | "chat.scheduledMessages.list" => Some(&[]),
+            "chat.unfurl" => Some(&[SlackScope::LinksWrite]),
+            "conversations.history" | "conversations.replies" => Some(CONVERSATIONS_HISTORY_SCOPES),
+            "conversations.info"
+            | "conversations.list"
+            | "conversations.members"
+            | "users.conversations" => Some(CONVERSATIONS_READ_SCOPES),
+            "conversations.archive"
+            | "conversations.close"
+            | "conversations.create"
+            | "conversations.kick"
+            | "conversations.leave"
+            | "conversations.open"
+            | "conversations.rename" => Some(CONVERSATIONS_MANAGE_SCOPES),
+            "conversations.invite" => Some(CONVERSATIONS_INVITE_SCOPES),
+            "conversations.join" => Some(&[SlackScope::ChannelsJoin]),
+            "conversations.setPurpose" | "conversations.setTopic" => {
+                Some(CONVERSATIONS_TOPIC_SCOPES)
+            }
+            "files.completeUploadExternal" | "files.getUploadURLExternal" | "files.upload" => {
+                Some(&[SlackScope::FilesWrite])
+            }
+            "files.info" | "files.list" => Some(&[SlackScope::FilesRead]),
+            "openid.connect.userInfo" => Some(&[SlackScope::OpenId]),
+            "pins.add" | "pins.remove" => Some(&[SlackScope::PinsWrite]),
+            "reactions.add" | "reactions.remove" => Some(&[SlackScope::ReactionsWrite]),
+            "reactions.get" => Some(&[SlackScope::ReactionsRead]),
+            "search.messages" => Some(&[SlackScope::SearchRead]),
+            "stars.add" | "stars.remove" => Some(&[SlackScope::StarsWrite]),
+            "team.info" => Some(&[SlackScope::TeamRead]),
+            "team.profile.get" | "users.profile.get" => Some(&[SlackScope::UsersProfileRead]),
+            "usergroups.list" | "usergroups.users.list" => Some(&[SlackScope::UsergroupsRead]),
+            "usergroups.users.update" => Some(&[SlackScope::UsergroupsWrite]),
+            "users.getPresence" | "users.info" | "users.list" => Some(&[SlackScope::UsersRead]),
+            "users.identity" => Some(&[SlackScope::IdentityBasic]),
+            "users.lookupByEmail" => Some(&[SlackScope::UsersReadEmail]),
+            "users.profile.set" => Some(&[SlackScope::UsersProfileWrite]),
+            "users.setPresence" => Some(&[SlackScope::UsersWrite]),
+            "views.open" | "views.publish" | "views.push" | "views.update" => Some(&[]),
+            "workflows.triggers.permissions.add"
+            | "workflows.triggers.permissions.remove"
+            | "workflows.triggers.permissions.set" => Some(&[SlackScope::TriggersWrite]),
+            "workflows.triggers.permissions.list" => Some(&[SlackScope::TriggersRead]),
+            _ => None,
+        }
+    }
+}
+
+/// Scopes granted to a token
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SlackTokenScopes {
+    pub scopes: HashSet<SlackScope>,
+    /// Granted scopes not known to this library
+    pub other_scopes: Vec<SlackApiTokenScope>,
+}
+
+impl SlackTokenScopes {
+    /// Parses scopes in the OAuth comma separated format (`chat:write,users:read`)
+    pub fn from_token_scope(scope: &SlackApiTokenScope) -> Self {
+        let mut result = Self::default();
+        for scope_str in scope.value().split(',').map(|s| s.trim()) {
+            match SlackScope::from_scope_str(scope_str) {
+                Some(scope) => {
+                    result.scopes.insert(scope);
+                }
+                None if !scope_str.is_empty() => result.other_scopes.push(scope_str.into()),
+                None => {}
+            }
+        }
+        result
+    }
+
+    pub fn contains(&self, scope: SlackScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    /// Returns false for unknown methods
+    pub fn allows_method(&self, method: &str) -> bool {
+        SlackApiMethodScopes::required_scopes(method)
+            .map(|required| {
+                required.is_empty() || required.iter().any(|scope| self.contains(*scope))
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SlackTokenScopesCheck {
+    pub granted: SlackTokenScopes,
+    /// Methods with their required scopes (any of them) not granted to the token
+    pub missing: Vec<(String, &'static [SlackScope])>,
+    /// Methods without known scope requirements
+    pub unknown_methods: Vec<String>,
+}
+
+impl SlackTokenScopesCheck {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.unknown_methods.is_empty()
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    /// Validates the token with `auth.test` and checks that the token scopes
+    /// (received from OAuth and specified in `SlackApiToken::scope`) allow calling the methods,
+    /// so you can check them before running a job.
+    pub async fn check_token_scopes(
+        &self,
+        methods: &[&str],
+    ) -> ClientResult<SlackTokenScopesCheck> {
+        self.auth_test().await?;
+
+        let token_scope = self
+            .http_session_api
+            .token()
+            .scope
+            .as_ref()
+            .ok_or_else(|| {
+                SlackClientError::SystemError(SlackClientSystemError::new().with_message(
+                    "Token scopes are unknown. Specify them in SlackApiToken::scope".into(),
+                ))
+            })?;
+        let granted = SlackTokenScopes::from_token_scope(token_scope);
+
+        let mut missing = Vec::new();
+        let mut unknown_methods = Vec::new();
+        for method in methods {
+            match SlackApiMethodScopes::required_scopes(method) {
+                Some(required) if !granted.allows_method(method) => {
+                    missing.push((method.to_string(), required))
+                }
+                Some(_) => {}
+                None => unknown_methods.push(method.to_string()),
+            }
+        }
+
+        Ok(SlackTokenScopesCheck {
+            granted,
+            missing,
+            unknown_methods,
+        })
+    }
+}
+
+#[test]
+fn check_token_scopes_allow_methods() {
+    let scopes = SlackTokenScopes::from_token_scope(&"chat:write,im:history,custom:scope".into());
+    assert!(scopes.contains(SlackScope::ChatWrite));
+    assert_eq!(scopes.other_scopes, vec!["custom:scope".into()]);
+    assert!(scopes.allows_method("chat.postMessage"));
+    assert!(scopes.allows_method("conversations.history"));
+    assert!(scopes.allows_method("auth.test"));
+    assert!(!scopes.allows_method("users.info"));
+    assert!(!scopes.allows_method("unknown.method"));
+    assert_eq!(
+        SlackScope::from_scope_str(SlackScope::UsersReadEmail.as_str()),
+        Some(SlackScope::UsersReadEmail)
+    );
+}
+
+#[test]
+fn check_method_scopes_cover_api_methods() {
+    let sources = [
+        include_str!("api/admin.rs"),
+        include_str!("api/apps.rs"),
+        include_str!("api/audit.rs"),
+        include_str!("api/auth.rs"),
+        include_str!("api/bots.rs"),
+        include_str!("api/calls.rs"),
+        include_str!("api/canvases.rs"),
+        include_str!("api/chat.rs"),
+        include_str!("api/conversations.rs"),
+        include_str!("api/files.rs"),
+        include_str!("api/oauth.rs"),
+        include_str!("api/openid.rs"),
+        include_str!("api/pins.rs"),
+        include_str!("api/reactions.rs"),
+        include_str!("api/search.rs"),
+        include_str!("api/stars.rs"),
+        include_str!("api/team.rs"),
+        include_str!("api/test.rs"),
+        include_str!("api/usergroups.rs"),
+        include_str!("api/users.rs"),
+        include_str!("api/views.rs"),
+        include_str!("api/workflows.rs"),
+    ];
+    let call_markers = [
+        ".http_get(",
+        ".http_post(",
+        ".http_post_multipart_form(",
+        "create_method_uri_path(",
+    ];
+
+    let methods: Vec<&str> = sources
+        .iter()
+        .flat_map(|source| {
+            call_markers.iter().flat_map(move |marker| {
+                source.match_indices(marker).filter_map(move |(idx, _)| {
+                    source[idx + marker.len()..]
+                        .trim_start()
+                        .strip_prefix('"')
+                        .and_then(|rest| rest.split('"').next())
+                })
+            })
+        })
+        .collect();
+
+    assert!(methods.len() > 80);
+    assert!(methods.contains(&"files.info"));
+    for method in methods {
+        assert!(
+            SlackApiMethodScopes::required_scopes(method).is_some(),
+            "{method} has no required scopes"
+        );
+    }
+}
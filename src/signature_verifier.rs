@@ -31,6 +31,7 @@ impl SlackEventSignatureVerifier {
         )
     }
 
+    #[allow(deprecated)]
     pub fn verify<'b>(
         &self,
         hash: &'b str,
@@ -129,6 +129,11 @@ where
                             ))
                             .unwrap();
 
+                        if let Some(api_cache) = self.listener_environment.client.api_cache.as_ref()
+                        {
+                            api_cache.invalidate_by_event(&event.payload.event);
+                        }
+
                         match self
                             .callbacks
                             .push_events_callback
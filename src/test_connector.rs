@@ -0,0 +1,234 @@
+//!
+//! Scripted HTTP connector for unit tests: records calls and answers them with a handler
+//! instead of sending requests to Slack.
+//!
+
+use crate::errors::*;
+use crate::models::*;
+use crate::multipart_form::FileMultipartData;
+use crate::*;
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{BoxStream, TryStreamExt};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+#[derive(Debug, Clone)]
+pub(crate) struct SlackTestCall {
+    /// Web API method name (or the URL path for other URLs)
+    pub method: String,
+    pub url: Url,
+    /// JSON body for POST requests, form fields for multipart requests, content for binary uploads
+    pub body: Option<Value>,
+}
+
+impl SlackTestCall {
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        self.url
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.to_string())
+    }
+
+    /// A request field from the JSON body or the query parameters
+    pub fn param(&self, name: &str) -> Option<String> {
+        self.body
+            .as_ref()
+            .and_then(|body| body.get(name))
+            .map(|value| match value {
+                Value::String(value) => value.clone(),
+                other => other.to_string(),
+            })
+            .or_else(|| self.query_param(name))
+    }
+}
+
+type SlackTestHandler = dyn Fn(&SlackTestCall) -> ClientResult<Value> + Send + Sync;
+
+/// Answers every call with the handler result. Binary downloads are answered with string values
+/// as they are, or other values as JSON.
+#[derive(Clone)]
+pub(crate) struct SlackTestConnector {
+    handler: Arc<SlackTestHandler>,
+    calls: Arc<Mutex<Vec<SlackTestCall>>>,
+}
+
+impl SlackTestConnector {
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&SlackTestCall) -> ClientResult<Value> + Send + Sync + 'static,
+    {
+        Self {
+            handler: Arc::new(handler),
+            calls: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn calls(&self) -> Vec<SlackTestCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    pub fn calls_of(&self, method: &str) -> Vec<SlackTestCall> {
+        self.calls()
+            .into_iter()
+            .filter(|call| call.method == method)
+            .collect()
+    }
+
+    fn call(&self, url: Url, body: Option<Value>) -> ClientResult<Value> {
+        let call = SlackTestCall {
+            method: url
+                .path()
+                .strip_prefix("/api/")
+                .unwrap_or(url.path())
+                .to_string(),
+            url,
+            body,
+        };
+        self.calls.lock().unwrap().push(call.clone());
+        (self.handler)(&call)
+    }
+
+    fn respond<RS>(&self, result: ClientResult<Value>) -> ClientResult<RS>
+    where
+        RS: for<'de> serde::de::Deserialize<'de>,
+    {
+        result.and_then(|value| {
+            serde_json::from_value(value).map_err(|err| map_serde_error(err, None))
+        })
+    }
+
+    fn params_to_json<'p, PT, TS>(params: &'p PT) -> Value
+    where
+        PT: std::iter::IntoIterator<Item = (&'p str, Option<TS>)> + Clone,
+        TS: AsRef<str> + 'p + Send,
+    {
+        Value::Object(
+            params
+                .clone()
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    value.map(|value| (key.to_string(), Value::String(value.as_ref().into())))
+                })
+                .collect(),
+        )
+    }
+}
+
+impl SlackClientHttpConnector for SlackTestConnector {
+    fn http_get_uri<'a, RS>(
+        &'a self,
+        full_uri: Url,
+        _context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<RS>>
+    where
+        RS: for<'de> serde::de::Deserialize<'de> + Send + 'a + 'a + Send,
+    {
+        let result = self.call(full_uri, None);
+        std::future::ready(self.respond(result)).boxed()
+    }
+
+    fn http_get_with_client_secret<'a, RS>(
+        &'a self,
+        full_uri: Url,
+        _client_id: &'a SlackClientId,
+        _client_secret: &'a SlackClientSecret,
+    ) -> BoxFuture<'a, ClientResult<RS>>
+    where
+        RS: for<'de> serde::de::Deserialize<'de> + Send + 'a + 'a + Send,
+    {
+        let result = self.call(full_uri, None);
+        std::future::ready(self.respond(result)).boxed()
+    }
+
+    fn http_post_uri<'a, RQ, RS>(
+        &'a self,
+        full_uri: Url,
+        request_body: &'a RQ,
+        _context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<RS>>
+    where
+        RQ: serde::ser::Serialize + Send + Sync,
+        RS: for<'de> serde::de::Deserialize<'de> + Send + 'a + Send + 'a,
+    {
+        let result = serde_json::to_value(request_body)
+            .map_err(|err| map_serde_error(err, None))
+            .and_then(|body| self.call(full_uri, Some(body)));
+        std::future::ready(self.respond(result)).boxed()
+    }
+
+    fn http_post_uri_multipart_form<'a, 'p, RS, PT, TS>(
+        &'a self,
+        full_uri: Url,
+        file: Option<FileMultipartData<'p>>,
+        params: &'p PT,
+        _context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<RS>>
+    where
+        RS: for<'de> serde::de::Deserialize<'de> + Send + 'a + Send + 'a,
+        PT: std::iter::IntoIterator<Item = (&'p str, Option<TS>)> + Clone,
+        TS: AsRef<str> + 'p + Send,
+    {
+        let mut body = Self::params_to_json(params);
+        if let (Some(fields), Some(file)) = (body.as_object_mut(), file) {
+            fields.insert("file".into(), Value::String(file.name));
+        }
+        let result = self.call(full_uri, Some(body));
+        std::future::ready(self.respond(result)).boxed()
+    }
+
+    fn http_post_uri_binary<'a, 'p, RS>(
+        &'a self,
+        full_uri: Url,
+        _content_type: String,
+        data: &'a [u8],
+        _context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<RS>>
+    where
+        RS: for<'de> serde::de::Deserialize<'de> + Send + 'a + Send + 'a,
+    {
+        let body = Value::String(String::from_utf8_lossy(data).into());
+        let result = self.call(full_uri, Some(body));
+        std::future::ready(self.respond(result)).boxed()
+    }
+
+    fn http_post_uri_stream<'a, RS>(
+        &'a self,
+        full_uri: Url,
+        _content_type: String,
+        _content_length: u64,
+        content: BoxStream<'static, std::io::Result<bytes::Bytes>>,
+        _context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<RS>>
+    where
+        RS: for<'de> serde::de::Deserialize<'de> + Send + 'a,
+    {
+        async move {
+            let data: Vec<u8> = content
+                .map_ok(|chunk| chunk.to_vec())
+                .try_concat()
+                .await
+                .map_err(|err| {
+                    SlackClientError::HttpProtocolError(
+                        SlackClientHttpProtocolError::new().with_cause(Box::new(err)),
+                    )
+                })?;
+            let body = Value::String(String::from_utf8_lossy(&data).into());
+            let result = self.call(full_uri, Some(body));
+            self.respond(result)
+        }
+        .boxed()
+    }
+
+    fn http_get_uri_binary<'a>(
+        &'a self,
+        full_uri: Url,
+        _context: SlackClientApiCallContext<'a>,
+    ) -> BoxFuture<'a, ClientResult<bytes::Bytes>> {
+        let result = self.call(full_uri, None).map(|value| match value {
+            Value::String(content) => bytes::Bytes::from(content),
+            other => bytes::Bytes::from(other.to_string()),
+        });
+        std::future::ready(result).boxed()
+    }
+}
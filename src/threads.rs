@@ -0,0 +1,157 @@
+//!
+//! Complete thread fetching (parent message and all replies)
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::models::*;
+use crate::*;
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackThread {
+    pub channel: SlackChannelId,
+    pub parent: SlackHistoryMessage,
+    /// Replies ordered by their timestamps (oldest first)
+    pub replies: Vec<SlackHistoryMessage>,
+    /// Users replied in the thread in order of their first reply
+    pub reply_users: Vec<SlackUserId>,
+}
+
+impl SlackThread {
+    pub fn thread_ts(&self) -> &SlackTs {
+        &self.parent.origin.ts
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &SlackHistoryMessage> {
+        std::iter::once(&self.parent).chain(self.replies.iter())
+    }
+}
+
+impl<'a, SCHC> SlackClientSession<'a, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Fetches a thread with `fetch_thread`, separating the parent message from the replies.
+    pub async fn fetch_full_thread(
+        &self,
+        channel: &SlackChannelId,
+        thread_ts: &SlackTs,
+    ) -> ClientResult<SlackThread> {
+        let mut parent: Option<SlackHistoryMessage> = None;
+        let mut replies: Vec<SlackHistoryMessage> = Vec::new();
+
+        for message in self.fetch_thread(channel, thread_ts).await? {
+            if message.origin.ts == *thread_ts {
+                parent.get_or_insert(message);
+            } else {
+                replies.push(message);
+            }
+        }
+
+        let parent = parent.ok_or_else(|| {
+            SlackClientError::SystemError(SlackClientSystemError::new().with_message(format!(
+                "Thread parent message {} is not found in {}",
+                thread_ts, channel
+            )))
+        })?;
+
+        replies.sort_by(|a, b| a.origin.ts.cmp(&b.origin.ts));
+        replies.dedup_by(|a, b| a.origin.ts == b.origin.ts);
+
+        let mut reply_users: Vec<SlackUserId> = Vec::new();
+        for user in replies
+            .iter()
+            .filter_map(|reply| reply.sender.user.as_ref())
+            .chain(parent.parent.reply_users.iter().flatten())
+        {
+            if !reply_users.contains(user) {
+                reply_users.push(user.clone());
+            }
+        }
+
+        Ok(SlackThread::new(
+            channel.clone(),
+            parent,
+            replies,
+            reply_users,
+        ))
+    }
+}
+
+#[test]
+fn check_fetch_full_thread_pages() {
+    use crate::test_connector::*;
+    use rvstruct::ValueStruct;
+
+    let connector = SlackTestConnector::new(|call| {
+        let (messages, next_cursor) = match call.query_param("cursor").as_deref() {
+            None => (vec!["99.000001", "100.000020"], "page2"),
+            _ => (vec!["99.000001", "99.000500"], ""),
+        };
+        Ok(serde_json::json!({
+            "messages": messages
+                .into_iter()
+                .map(|ts| serde_json::json!({ "ts": ts, "user": format!("U{ts}") }))
+                .collect::<Vec<_>>(),
+            "response_metadata": { "next_cursor": next_cursor }
+        }))
+    });
+    let client = SlackClient::new(connector.clone());
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let thread =
+        futures::executor::block_on(session.fetch_full_thread(&"C1".into(), &"99.000001".into()))
+            .unwrap();
+
+    assert_eq!(connector.calls_of("conversations.replies").len(), 2);
+    assert_eq!(thread.thread_ts(), &SlackTs::new("99.000001".into()));
+    assert_eq!(
+        thread
+            .replies
+            .iter()
+            .map(|reply| reply.origin.ts.value().as_str())
+            .collect::<Vec<_>>(),
+        vec!["99.000500", "100.000020"]
+    );
+}
+
+#[test]
+fn check_full_thread_reply_users_and_missing_parent() {
+    use crate::test_connector::*;
+
+    let connector = SlackTestConnector::new(|call| {
+        let messages = match call.param("ts").as_deref() {
+            Some("1.1") => serde_json::json!([
+                { "ts": "1.1", "user": "U1", "reply_users": ["U3", "U1"] },
+                { "ts": "1.3", "user": "U2" },
+                { "ts": "1.2", "user": "U1" },
+                { "ts": "1.3", "user": "U2" }
+            ]),
+            _ => serde_json::json!([{ "ts": "2.2", "user": "U1" }]),
+        };
+        Ok(serde_json::json!({ "messages": messages }))
+    });
+    let client = SlackClient::new(connector);
+    let token = SlackApiToken::new("xoxb-test".into());
+    let session = client.open_session(&token);
+
+    let thread =
+        futures::executor::block_on(session.fetch_full_thread(&"C1".into(), &"1.1".into()))
+            .unwrap();
+    assert_eq!(thread.replies.len(), 2);
+    assert_eq!(thread.messages().count(), 3);
+    assert_eq!(
+        thread.reply_users,
+        vec!["U1".into(), "U2".into(), "U3".into()]
+    );
+
+    assert!(matches!(
+        futures::executor::block_on(session.fetch_full_thread(&"C1".into(), &"2.1".into())),
+        Err(SlackClientError::SystemError(_))
+    ));
+}
@@ -3,7 +3,7 @@ use rvstruct::ValueStruct;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
-use crate::models::{SlackApiTokenScope, SlackTeamId};
+use crate::models::{SlackApiTokenScope, SlackEnterpriseId, SlackInstallationKey, SlackTeamId};
 
 #[derive(Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackApiTokenValue(pub String);
@@ -50,6 +50,27 @@ pub struct SlackApiToken {
     pub token_value: SlackApiTokenValue,
     pub cookie: Option<SlackApiCookieValue>,
     pub team_id: Option<SlackTeamId>,
+    /// Specified for tokens of org-wide (Enterprise Grid) installations.
+    /// For such tokens `team_id` is sent as the `team_id` API parameter to set a workspace context.
+    pub enterprise_id: Option<SlackEnterpriseId>,
     pub scope: Option<SlackApiTokenScope>,
     pub token_type: Option<SlackApiTokenType>,
 }
+
+impl SlackApiToken {
+    pub fn is_org_token(&self) -> bool {
+        self.enterprise_id.is_some()
+    }
+
+    pub fn installation_key(&self) -> Option<SlackInstallationKey> {
+        SlackInstallationKey::from_ids(
+            self.team_id.as_ref(),
+            self.enterprise_id.as_ref(),
+            self.is_org_token(),
+        )
+    }
+
+    pub(crate) fn org_team_context(&self) -> Option<&SlackTeamId> {
+        self.team_id.as_ref().filter(|_| self.is_org_token())
+    }
+}
@@ -0,0 +1,237 @@
+//!
+//! Opt-in sessions typed by token kind (bot, user or app-level), so methods
+//! supported only by specific tokens are checked at compile time.
+//!
+
+use crate::api::*;
+use crate::errors::*;
+use crate::*;
+use rvstruct::ValueStruct;
+use std::marker::PhantomData;
+
+pub trait SlackTokenKind: Send + Sync {
+    const TOKEN_TYPE: SlackApiTokenType;
+    /// Token value prefixes used when `SlackApiToken::token_type` isn't specified
+    const TOKEN_PREFIXES: &'static [&'static str];
+}
+
+/// Token kinds supporting Web API methods in workspaces (bot and user tokens)
+pub trait SlackWebApiTokenKind: SlackTokenKind {}
+
+/// Token kinds supporting user token only methods
+pub trait SlackUserTokenMethods: SlackWebApiTokenKind {}
+
+/// Token kinds supporting app-level token only methods
+pub trait SlackAppTokenMethods: SlackTokenKind {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SlackBotTokenKind;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SlackUserTokenKind;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SlackAppTokenKind;
+
+impl SlackTokenKind for SlackBotTokenKind {
+    const TOKEN_TYPE: SlackApiTokenType = SlackApiTokenType::Bot;
+    const TOKEN_PREFIXES: &'static [&'static str] = &["xoxb-"];
+}
+
+impl SlackTokenKind for SlackUserTokenKind {
+    const TOKEN_TYPE: SlackApiTokenType = SlackApiTokenType::User;
+    const TOKEN_PREFIXES: &'static [&'static str] = &["xoxp-", "xoxe.xoxp-"];
+}
+
+impl SlackTokenKind for SlackAppTokenKind {
+    const TOKEN_TYPE: SlackApiTokenType = SlackApiTokenType::App;
+    const TOKEN_PREFIXES: &'static [&'static str] = &["xapp-"];
+}
+
+impl SlackWebApiTokenKind for SlackBotTokenKind {}
+impl SlackWebApiTokenKind for SlackUserTokenKind {}
+impl SlackUserTokenMethods for SlackUserTokenKind {}
+impl SlackAppTokenMethods for SlackAppTokenKind {}
+
+/// A session with methods available only for the token kind `K`.
+///
+/// Methods supported only by user tokens are available in user sessions:
+///
+/// ```
+/// use slack_morphism::prelude::*;
+///
+/// async fn search<SCHC: SlackClientHttpConnector + Send + Sync>(
+///     client: &SlackClient<SCHC>,
+///     token: &SlackApiToken,
+/// ) -> ClientResult<SlackApiSearchMessagesResponse> {
+///     let session = client.open_typed_session::<SlackUserTokenKind>(token)?;
+///     session
+///         .search_messages(&SlackApiSearchMessagesRequest::new("query".into()))
+///         .await
+/// }
+/// ```
+///
+/// but they don't compile for bot sessions:
+///
+/// ```compile_fail
+/// use slack_morphism::prelude::*;
+///
+/// async fn search<SCHC: SlackClientHttpConnector + Send + Sync>(
+///     client: &SlackClient<SCHC>,
+///     token: &SlackApiToken,
+/// ) -> ClientResult<SlackApiSearchMessagesResponse> {
+///     let session = client.open_typed_session::<SlackBotTokenKind>(token)?;
+///     session
+///         .search_messages(&SlackApiSearchMessagesRequest::new("query".into()))
+///         .await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SlackTypedSession<'a, SCHC, K>
+where
+    SCHC: SlackClientHttpConnector + Send,
+    K: SlackTokenKind,
+{
+    session: SlackClientSession<'a, SCHC>,
+    _token_kind: PhantomData<K>,
+}
+
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync,
+{
+    /// Opens a session where only methods compatible with the token kind are available.
+    /// Returns an error if the token doesn't look like a token of this kind.
+    pub fn open_typed_session<'a, K>(
+        &'a self,
+        token: &'a SlackApiToken,
+    ) -> ClientResult<SlackTypedSession<'a, SCHC, K>>
+    where
+        K: SlackTokenKind,
+    {
+        let is_compatible = match token.token_type.as_ref() {
+            Some(token_type) => *token_type == K::TOKEN_TYPE,
+            None => K::TOKEN_PREFIXES
+                .iter()
+                .any(|prefix| token.token_value.value().starts_with(prefix)),
+        };
+
+        if is_compatible {
+            Ok(SlackTypedSession {
+                session: self.open_session(token),
+                _token_kind: PhantomData,
+            })
+        } else {
+            Err(SlackClientError::SystemError(
+                SlackClientSystemError::new().with_message(format!(
+                    "Token isn't compatible with the session token kind: {}",
+                    K::TOKEN_TYPE.to_string()
+                )),
+            ))
+        }
+    }
+}
+
+impl<'a, SCHC, K> SlackTypedSession<'a, SCHC, K>
+where
+    SCHC: SlackClientHttpConnector + Send,
+    K: SlackTokenKind,
+{
+    /// Untyped session to call any other method (without compile time checks)
+    pub fn untyped(&self) -> &SlackClientSession<'a, SCHC> {
+        &self.session
+    }
+}
+
+macro_rules! slack_typed_session_methods {
+    ($kind_bound:path { $($method:ident($req:ty) -> $resp:ty;)* }) => {
+        impl<'a, SCHC, K> SlackTypedSession<'a, SCHC, K>
+        where
+            SCHC: SlackClientHttpConnector + Send,
+            K: $kind_bound,
+        {
+            $(
+                pub async fn $method(&self, req: &$req) -> ClientResult<$resp> {
+                    self.session.$method(req).await
+                }
+            )*
+        }
+    };
+}
+
+slack_typed_session_methods!(SlackWebApiTokenKind {
+    chat_delete(SlackApiChatDeleteRequest) -> SlackApiChatDeleteResponse;
+    chat_post_ephemeral(SlackApiChatPostEphemeralRequest) -> SlackApiChatPostEphemeralResponse;
+    chat_post_message(SlackApiChatPostMessageRequest) -> SlackApiChatPostMessageResponse;
+    chat_update(SlackApiChatUpdateRequest) -> SlackApiChatUpdateResponse;
+    conversations_history(SlackApiConversationsHistoryRequest) -> SlackApiConversationsHistoryResponse;
+    conversations_info(SlackApiConversationsInfoRequest) -> SlackApiConversationsInfoResponse;
+    conversations_list(SlackApiConversationsListRequest) -> SlackApiConversationsListResponse;
+    conversations_members(SlackApiConversationsMembersRequest) -> SlackApiConversationsMembersResponse;
+    conversations_replies(SlackApiConversationsRepliesRequest) -> SlackApiConversationsRepliesResponse;
+    reactions_add(SlackApiReactionsAddRequest) -> SlackApiReactionsAddResponse;
+    reactions_remove(SlackApiReactionsRemoveRequest) -> SlackApiReactionsRemoveResponse;
+    users_info(SlackApiUsersInfoRequest) -> SlackApiUsersInfoResponse;
+    users_list(SlackApiUsersListRequest) -> SlackApiUsersListResponse;
+    views_open(SlackApiViewsOpenRequest) -> SlackApiViewsOpenResponse;
+    views_publish(SlackApiViewsPublishRequest) -> SlackApiViewsPublishResponse;
+});
+
+// Methods supported only by user tokens
+slack_typed_session_methods!(SlackUserTokenMethods {
+    search_messages(SlackApiSearchMessagesRequest) -> SlackApiSearchMessagesResponse;
+    stars_add(SlackApiStarsAddRequest) -> SlackApiStarsAddResponse;
+    stars_remove(SlackApiStarsRemoveRequest) -> SlackApiStarsRemoveResponse;
+    users_profile_set(SlackApiUsersProfileSetRequest) -> SlackApiUsersProfileSetResponse;
+    users_set_presence(SlackApiUsersSetPresenceRequest) -> SlackApiUsersSetPresenceResponse;
+});
+
+// Methods supported only by app-level tokens
+slack_typed_session_methods!(SlackAppTokenMethods {
+    apps_connections_open(SlackApiAppsConnectionOpenRequest) -> SlackApiAppsConnectionOpenResponse;
+});
+
+impl<'a, SCHC, K> SlackTypedSession<'a, SCHC, K>
+where
+    SCHC: SlackClientHttpConnector + Send,
+    K: SlackWebApiTokenKind,
+{
+    pub async fn auth_test(&self) -> ClientResult<SlackApiAuthTestResponse> {
+        self.session.auth_test().await
+    }
+}
+
+#[test]
+fn check_typed_session_token_kinds() {
+    use crate::test_connector::*;
+
+    let client = SlackClient::new(SlackTestConnector::new(|_| Ok(serde_json::json!({}))));
+    let bot_token = SlackApiToken::new("xoxb-test".into());
+    let user_token = SlackApiToken::new("xoxe.xoxp-test".into());
+    let app_token = SlackApiToken::new("xapp-test".into());
+
+    assert!(client
+        .open_typed_session::<SlackBotTokenKind>(&bot_token)
+        .is_ok());
+    assert!(client
+        .open_typed_session::<SlackUserTokenKind>(&bot_token)
+        .is_err());
+    assert!(client
+        .open_typed_session::<SlackUserTokenKind>(&user_token)
+        .is_ok());
+    assert!(client
+        .open_typed_session::<SlackAppTokenKind>(&app_token)
+        .is_ok());
+    assert!(client
+        .open_typed_session::<SlackBotTokenKind>(&app_token)
+        .is_err());
+
+    // Explicit token types take precedence over token prefixes
+    let typed_token = bot_token.clone().with_token_type(SlackApiTokenType::User);
+    assert!(client
+        .open_typed_session::<SlackUserTokenKind>(&typed_token)
+        .is_ok());
+    assert!(client
+        .open_typed_session::<SlackBotTokenKind>(&typed_token)
+        .is_err());
+}